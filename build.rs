@@ -1,13 +1,17 @@
+#[cfg(target_os = "macos")]
 use std::env;
+#[cfg(target_os = "macos")]
 use std::path::PathBuf;
+#[cfg(target_os = "macos")]
 use std::process::Command;
 
 fn main() {
-    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
-
-    // Compile Metal shaders on macOS
+    // Compile Metal shaders on macOS; other targets build without the Metal backend
     #[cfg(target_os = "macos")]
-    compile_metal_shaders(&out_dir);
+    {
+        let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+        compile_metal_shaders(&out_dir);
+    }
 
     // Compile SPIR-V shaders for Vulkan (when implemented)
     // compile_vulkan_shaders(&out_dir);
@@ -9,26 +9,37 @@ using namespace metal;
 // Tests Montgomery multiplication for the BN254 base field.
 // This is the most critical operation for ZK proof systems.
 
+// One accumulator chain, kept scalar
 kernel void bench_bn254_field_mul(
     device const uint* input [[buffer(0)]],
     device uint* output [[buffer(1)]],
     constant BenchParams& params [[buffer(2)]],
-    uint tid [[thread_position_in_grid]]
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
 ) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
     // Initialize field elements from input
     BigInt256 a;
     BigInt256 b;
 
+    uint base = tid * 2 * NUM_LIMBS;
     for (uint i = 0; i < NUM_LIMBS; i++) {
-        a.limbs[i] = (input[(tid + i) % 16] ^ (tid * (i + 1u))) & W_mask;
-        b.limbs[i] = (input[(tid + i + 8) % 16] ^ (tid * (i + 17u))) & W_mask;
+        if (params.dataset != 0u) {
+            a.limbs[i] = input[base + i];
+            b.limbs[i] = input[base + NUM_LIMBS + i];
+        } else {
+            a.limbs[i] = (input[(tid + i) % 16] ^ (tid * (i + 1u))) & W_mask;
+            b.limbs[i] = (input[(tid + i + 8) % 16] ^ (tid * (i + 17u))) & W_mask;
+        }
     }
 
     // Reduce to valid field elements
     a = field_reduce(a);
     b = field_reduce(b);
 
-    // Accumulator in Montgomery form
     BigInt256 acc = a;
 
     // Main benchmark loop
@@ -47,3 +58,61 @@ kernel void bench_bn254_field_mul(
     }
     output[tid] = result;
 }
+
+// Independent accumulator chains (see INSTANTIATE_CHAINS)
+template <uint CHAINS>
+[[kernel]] void bench_bn254_field_mul_impl(
+    device const uint* input [[buffer(0)]],
+    device uint* output [[buffer(1)]],
+    constant BenchParams& params [[buffer(2)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
+    // Initialize each chain's field elements from input
+    BigInt256 acc[CHAINS];
+    BigInt256 b[CHAINS];
+
+    for (uint c = 0; c < CHAINS; c++) {
+        BigInt256 a_c;
+        BigInt256 b_c;
+        uint base = (tid * CHAINS + c) * 2 * NUM_LIMBS;
+        for (uint i = 0; i < NUM_LIMBS; i++) {
+            if (params.dataset != 0u) {
+                a_c.limbs[i] = input[base + i];
+                b_c.limbs[i] = input[base + NUM_LIMBS + i];
+            } else {
+                a_c.limbs[i] = (input[(tid + i + c) % 16] ^ (tid * (i + 1u))) & W_mask;
+                b_c.limbs[i] = (input[(tid + i + c + 8) % 16] ^ (tid * (i + 17u))) & W_mask;
+            }
+        }
+
+        // Reduce to valid field elements
+        acc[c] = field_reduce(a_c);
+        b[c] = field_reduce(b_c);
+    }
+
+    // Main benchmark loop - one field multiplication per chain
+    for (uint i = 0; i < params.iterations; i++) {
+        for (uint c = 0; c < CHAINS; c++) {
+            acc[c] = field_mul(acc[c], b[c]);
+
+            // Data-dependent modification to prevent optimization
+            b[c].limbs[0] = (b[c].limbs[0] ^ (acc[c].limbs[0] & 0xFFu)) & W_mask;
+        }
+    }
+
+    // Write result (XOR all limbs of every chain to a single value)
+    uint result = 0u;
+    for (uint c = 0; c < CHAINS; c++) {
+        for (uint i = 0; i < NUM_LIMBS; i++) {
+            result ^= acc[c].limbs[i];
+        }
+    }
+    output[tid] = result;
+}
+
+INSTANTIATE_CHAINS(bench_bn254_field_mul)
@@ -27,15 +27,29 @@ inline uint mersenne_add(uint a, uint b) {
     return mersenne_reduce(sum);
 }
 
+// One accumulator chain, kept scalar
 kernel void bench_m31_field_add(
     device const uint* input [[buffer(0)]],
     device uint* output [[buffer(1)]],
     constant BenchParams& params [[buffer(2)]],
-    uint tid [[thread_position_in_grid]]
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
 ) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
     // Initialize with thread-unique seed, reduced to field
-    uint acc = (params.seed ^ tid) & MERSENNE_P;
-    uint b = input[(tid + 8) % 16] & MERSENNE_P;
+    uint acc;
+    uint b;
+    if (params.dataset != 0u) {
+        // Already reduced by the host
+        acc = input[tid * 2];
+        b = input[tid * 2 + 1];
+    } else {
+        acc = (params.seed ^ tid) & MERSENNE_P;
+        b = input[(tid + 8) % 16] & MERSENNE_P;
+    }
 
     // Main benchmark loop - field addition operations
     for (uint i = 0; i < params.iterations; i++) {
@@ -49,3 +63,52 @@ kernel void bench_m31_field_add(
     // Write result to prevent dead code elimination
     output[tid] = acc;
 }
+
+// Independent accumulator chains (see INSTANTIATE_CHAINS)
+template <uint CHAINS>
+[[kernel]] void bench_m31_field_add_impl(
+    device const uint* input [[buffer(0)]],
+    device uint* output [[buffer(1)]],
+    constant BenchParams& params [[buffer(2)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
+    // Initialize each chain with a thread-unique seed, reduced to field
+    uint acc[CHAINS];
+    uint b[CHAINS];
+    for (uint c = 0; c < CHAINS; c++) {
+        if (params.dataset != 0u) {
+            // Already reduced by the host
+            uint base = (tid * CHAINS + c) * 2;
+            acc[c] = input[base];
+            b[c] = input[base + 1];
+        } else {
+            acc[c] = (params.seed ^ tid ^ (c * 0x9E3779B9u)) & MERSENNE_P;
+            b[c] = input[(tid + 8 + c) % 16] & MERSENNE_P;
+        }
+    }
+
+    // Main benchmark loop - one field addition per chain
+    for (uint i = 0; i < params.iterations; i++) {
+        for (uint c = 0; c < CHAINS; c++) {
+            // Field addition: acc = (acc + b) mod p
+            acc[c] = mersenne_add(acc[c], b[c]);
+
+            // Data-dependent modification to prevent optimization
+            b[c] = (b[c] ^ (acc[c] & 0xFFu)) & MERSENNE_P;
+        }
+    }
+
+    // Write result to prevent dead code elimination
+    uint result = 0u;
+    for (uint c = 0; c < CHAINS; c++) {
+        result ^= acc[c];
+    }
+    output[tid] = result;
+}
+
+INSTANTIATE_CHAINS(bench_m31_field_add)
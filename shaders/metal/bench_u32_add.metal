@@ -8,15 +8,28 @@ using namespace metal;
 // ============================================================================
 // This benchmark measures the raw throughput of native u32 addition.
 
+// One accumulator chain, kept scalar
 kernel void bench_u32_add(
     device const uint* input [[buffer(0)]],
     device uint* output [[buffer(1)]],
     constant BenchParams& params [[buffer(2)]],
-    uint tid [[thread_position_in_grid]]
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
 ) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
     // Initialize with thread-unique seed
-    uint acc = params.seed ^ tid;
-    uint b = input[(tid + 8) % 16];
+    uint acc;
+    uint b;
+    if (params.dataset != 0u) {
+        acc = input[tid * 2];
+        b = input[tid * 2 + 1];
+    } else {
+        acc = params.seed ^ tid;
+        b = input[(tid + 8) % 16];
+    }
 
     // Main benchmark loop - addition operations
     for (uint i = 0; i < params.iterations; i++) {
@@ -30,3 +43,51 @@ kernel void bench_u32_add(
     // Write result to prevent dead code elimination
     output[tid] = acc;
 }
+
+// Independent accumulator chains (see INSTANTIATE_CHAINS)
+template <uint CHAINS>
+[[kernel]] void bench_u32_add_impl(
+    device const uint* input [[buffer(0)]],
+    device uint* output [[buffer(1)]],
+    constant BenchParams& params [[buffer(2)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
+    // Initialize each chain with a thread-unique seed
+    uint acc[CHAINS];
+    uint b[CHAINS];
+    for (uint c = 0; c < CHAINS; c++) {
+        if (params.dataset != 0u) {
+            uint base = (tid * CHAINS + c) * 2;
+            acc[c] = input[base];
+            b[c] = input[base + 1];
+        } else {
+            acc[c] = params.seed ^ tid ^ (c * 0x9E3779B9u);
+            b[c] = input[(tid + 8 + c) % 16];
+        }
+    }
+
+    // Main benchmark loop - one addition per chain
+    for (uint i = 0; i < params.iterations; i++) {
+        for (uint c = 0; c < CHAINS; c++) {
+            // Addition: acc = acc + b
+            acc[c] = acc[c] + b[c];
+
+            // Data-dependent modification to prevent optimization
+            b[c] = b[c] ^ (acc[c] & 0xFFu);
+        }
+    }
+
+    // Write result to prevent dead code elimination
+    uint result = 0u;
+    for (uint c = 0; c < CHAINS; c++) {
+        result ^= acc[c];
+    }
+    output[tid] = result;
+}
+
+INSTANTIATE_CHAINS(bench_u32_add)
@@ -9,15 +9,30 @@ using namespace metal;
 // This benchmark measures the throughput of native 64-bit addition
 // available in Metal. Used to compare against emulated u64 in WebGPU.
 
+// One accumulator chain, kept scalar
 kernel void bench_u64_add(
     device const uint* input [[buffer(0)]],
     device ulong* output [[buffer(1)]],
     constant BenchParams& params [[buffer(2)]],
-    uint tid [[thread_position_in_grid]]
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
 ) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
     // Initialize with thread-unique seed (combine two u32s into u64)
-    ulong acc = (ulong(params.seed) << 32) | (params.seed ^ tid);
-    ulong b = (ulong(input[(tid + 2) % 16]) << 32) | input[(tid + 3) % 16];
+    ulong acc;
+    ulong b;
+    if (params.dataset != 0u) {
+        // Operands are stored as (lo, hi) word pairs
+        uint base = tid * 4;
+        acc = (ulong(input[base + 1]) << 32) | input[base];
+        b = (ulong(input[base + 3]) << 32) | input[base + 2];
+    } else {
+        acc = (ulong(params.seed) << 32) | (params.seed ^ tid);
+        b = (ulong(input[(tid + 2) % 16]) << 32) | input[(tid + 3) % 16];
+    }
 
     // Main benchmark loop - 64-bit addition operations
     for (uint i = 0; i < params.iterations; i++) {
@@ -31,3 +46,52 @@ kernel void bench_u64_add(
     // Write result to prevent dead code elimination
     output[tid] = acc;
 }
+
+// Independent accumulator chains (see INSTANTIATE_CHAINS)
+template <uint CHAINS>
+[[kernel]] void bench_u64_add_impl(
+    device const uint* input [[buffer(0)]],
+    device ulong* output [[buffer(1)]],
+    constant BenchParams& params [[buffer(2)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint tid = position.y * grid.x + position.x;
+    if (tid >= params.threads) return;
+
+    // Initialize each chain with a thread-unique seed (combine two u32s into u64)
+    ulong acc[CHAINS];
+    ulong b[CHAINS];
+    for (uint c = 0; c < CHAINS; c++) {
+        if (params.dataset != 0u) {
+            // Operands are stored as (lo, hi) word pairs
+            uint base = (tid * CHAINS + c) * 4;
+            acc[c] = (ulong(input[base + 1]) << 32) | input[base];
+            b[c] = (ulong(input[base + 3]) << 32) | input[base + 2];
+        } else {
+            acc[c] = (ulong(params.seed) << 32) | (params.seed ^ tid ^ (c * 0x9E3779B9u));
+            b[c] = (ulong(input[(tid + 2 + c) % 16]) << 32) | input[(tid + 3 + c) % 16];
+        }
+    }
+
+    // Main benchmark loop - one 64-bit addition per chain
+    for (uint i = 0; i < params.iterations; i++) {
+        for (uint c = 0; c < CHAINS; c++) {
+            // 64-bit addition: acc = acc + b
+            acc[c] = acc[c] + b[c];
+
+            // Data-dependent modification to prevent optimization
+            b[c] = b[c] ^ (acc[c] & 0xFFull);
+        }
+    }
+
+    // Write result to prevent dead code elimination
+    ulong result = 0ull;
+    for (uint c = 0; c < CHAINS; c++) {
+        result ^= acc[c];
+    }
+    output[tid] = result;
+}
+
+INSTANTIATE_CHAINS(bench_u64_add)
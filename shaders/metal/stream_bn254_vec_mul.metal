@@ -0,0 +1,37 @@
+#include <metal_stdlib>
+#include "bn254_field.metal"
+
+using namespace metal;
+
+// ============================================================================
+// Stream: BN254 Vector Multiplication (Montgomery)
+// ============================================================================
+// out[i] = a[i] * b[i] * R^-1 mod p; element i is 16 limbs at words [16i, 16i + 16).
+
+[[kernel]] void stream_bn254_vec_mul(
+    device const uint* a [[buffer(0)]],
+    device const uint* b [[buffer(1)]],
+    device uint* out [[buffer(2)]],
+    constant StreamParams& params [[buffer(3)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint i = position.y * grid.x + position.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    BigInt256 x;
+    BigInt256 y;
+    uint base = i * NUM_LIMBS;
+    for (uint j = 0; j < NUM_LIMBS; j++) {
+        x.limbs[j] = a[base + j];
+        y.limbs[j] = b[base + j];
+    }
+
+    BigInt256 r = mont_mul_cios(x, y);
+    for (uint j = 0; j < NUM_LIMBS; j++) {
+        out[base + j] = r.limbs[j];
+    }
+}
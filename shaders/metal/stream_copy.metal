@@ -0,0 +1,27 @@
+#include <metal_stdlib>
+#include "types.metal"
+
+using namespace metal;
+
+// ============================================================================
+// Stream: Copy (bandwidth baseline)
+// ============================================================================
+// Copies one buffer to another with no arithmetic: the memory-bandwidth
+// ceiling the field streams are compared against. `b` is bound but not read.
+
+[[kernel]] void stream_copy(
+    device const uint* a [[buffer(0)]],
+    device const uint* b [[buffer(1)]],
+    device uint* out [[buffer(2)]],
+    constant StreamParams& params [[buffer(3)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint i = position.y * grid.x + position.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    out[i] = a[i];
+}
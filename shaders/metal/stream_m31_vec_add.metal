@@ -0,0 +1,43 @@
+#include <metal_stdlib>
+#include "types.metal"
+
+using namespace metal;
+
+// ============================================================================
+// Stream: Mersenne (2^31-1) Vector Addition
+// ============================================================================
+// out[i] = a[i] + b[i] mod p, one field element per thread.
+
+constant uint MERSENNE_P = 0x7FFFFFFFu;  // 2^31 - 1
+
+// Reduce to [0, p) range
+// Input: value in range [0, 2*p)
+// Output: value in range [0, p)
+inline uint mersenne_reduce(uint x) {
+    uint r = (x & MERSENNE_P) + (x >> 31);
+    return r >= MERSENNE_P ? r - MERSENNE_P : r;
+}
+
+// Field addition: (a + b) mod p
+// Assumes a, b < p
+inline uint mersenne_add(uint a, uint b) {
+    uint sum = a + b;  // Range: [0, 2p-2]
+    return mersenne_reduce(sum);
+}
+
+[[kernel]] void stream_m31_vec_add(
+    device const uint* a [[buffer(0)]],
+    device const uint* b [[buffer(1)]],
+    device uint* out [[buffer(2)]],
+    constant StreamParams& params [[buffer(3)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint i = position.y * grid.x + position.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    out[i] = mersenne_add(a[i], b[i]);
+}
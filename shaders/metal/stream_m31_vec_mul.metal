@@ -0,0 +1,50 @@
+#include <metal_stdlib>
+#include "types.metal"
+
+using namespace metal;
+
+// ============================================================================
+// Stream: Mersenne (2^31-1) Vector Multiplication
+// ============================================================================
+// out[i] = a[i] * b[i] mod p, one field element per thread.
+
+constant uint MERSENNE_P = 0x7FFFFFFFu;  // 2^31 - 1
+
+// Reduce a 64-bit value modulo Mersenne prime
+// Uses the identity: 2^31 = 1 (mod p)
+inline uint mersenne_reduce_u64(ulong x) {
+    // First reduction: split into 31-bit chunks
+    uint low = uint(x) & MERSENNE_P;           // bits 0-30
+    uint mid = uint(x >> 31) & MERSENNE_P;     // bits 31-61
+    uint sum = low + mid;                       // at most 32 bits
+
+    // Second reduction if needed
+    sum = (sum & MERSENNE_P) + (sum >> 31);
+
+    // Final reduction if sum == p
+    return sum >= MERSENNE_P ? sum - MERSENNE_P : sum;
+}
+
+// Field multiplication: (a * b) mod p
+// Assumes a, b < p
+inline uint mersenne_mul(uint a, uint b) {
+    ulong product = ulong(a) * ulong(b);  // 62 bits max
+    return mersenne_reduce_u64(product);
+}
+
+[[kernel]] void stream_m31_vec_mul(
+    device const uint* a [[buffer(0)]],
+    device const uint* b [[buffer(1)]],
+    device uint* out [[buffer(2)]],
+    constant StreamParams& params [[buffer(3)]],
+    uint2 position [[thread_position_in_grid]],
+    uint2 grid [[threads_per_grid]]
+) {
+    // Grids too large for one dimension are split into rows by the host
+    uint i = position.y * grid.x + position.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    out[i] = mersenne_mul(a[i], b[i]);
+}
@@ -53,10 +53,28 @@ constant uint BN254_R2[NUM_LIMBS] = {
     0x0D62u, 0x0EB4u, 0x01D6u, 0x06F9u
 };
 
+// Instantiate a benchmark kernel templated on its number of independent
+// accumulator chains as `name_chainsN`; one chain is the scalar kernel `name`
+#define INSTANTIATE_CHAINS_N(name, n) \
+    template [[host_name(#name "_chains" #n)]] [[kernel]] decltype(name##_impl<n>) name##_impl<n>;
+
+#define INSTANTIATE_CHAINS(name) \
+    INSTANTIATE_CHAINS_N(name, 2) \
+    INSTANTIATE_CHAINS_N(name, 4) \
+    INSTANTIATE_CHAINS_N(name, 8)
+
 // Benchmark parameters passed from host
 struct BenchParams {
     uint iterations;
     uint seed;
+    uint dataset;    // Non-zero: input holds one operand pair per thread and chain
+    uint threads;    // Threads with work; the last row of the grid may run past them
+};
+
+// Streaming kernel parameters passed from host
+struct StreamParams {
+    uint count;      // Elements in each buffer
     uint _pad0;
     uint _pad1;
+    uint _pad2;
 };
@@ -17,11 +17,20 @@ const BN254_P: array<u32, 16> = array<u32, 16>(
     0x057Bu, 0x074Eu, 0x09D6u, 0x030Cu
 );
 
+// Specialized by the host (see shaders.rs): threads per workgroup and
+// independent accumulator chains per thread. One chain runs the scalar entry
+// point, so the default kernel keeps its accumulator in registers; more run
+// the `_chains` entry point.
+const WORKGROUP_SIZE: u32 = 64u;
+const CHAINS: u32 = 1u;
+
 struct BenchParams {
     iterations: u32,
     seed: u32,
-    _pad0: u32,
-    _pad1: u32,
+    // Non-zero: input holds one operand pair per thread and chain
+    dataset: u32,
+    // Threads with work; the last row of the grid may run past them
+    threads: u32,
 }
 
 @group(0) @binding(0) var<storage, read> input: array<u32>;
@@ -151,17 +160,30 @@ fn field_mul(a: array<u32, 16>, b: array<u32, 16>) -> array<u32, 16> {
     return mont_mul_cios(a, b);
 }
 
-@compute @workgroup_size(64)
-fn bench_bn254_field_mul(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let tid = global_id.x;
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bench_bn254_field_mul(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let tid = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (tid >= params.threads) {
+        return;
+    }
 
     // Initialize field elements
     var a: array<u32, 16>;
     var b: array<u32, 16>;
 
+    let base = tid * 2u * NUM_LIMBS;
     for (var i: u32 = 0u; i < NUM_LIMBS; i = i + 1u) {
-        a[i] = (input[(tid + i) % 16u] ^ (tid * (i + 1u))) & W_mask;
-        b[i] = (input[(tid + i + 8u) % 16u] ^ (tid * (i + 17u))) & W_mask;
+        if (params.dataset != 0u) {
+            a[i] = input[base + i];
+            b[i] = input[base + NUM_LIMBS + i];
+        } else {
+            a[i] = (input[(tid + i) % 16u] ^ (tid * (i + 1u))) & W_mask;
+            b[i] = (input[(tid + i + 8u) % 16u] ^ (tid * (i + 17u))) & W_mask;
+        }
     }
 
     // Reduce to valid field elements
@@ -183,3 +205,55 @@ fn bench_bn254_field_mul(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
     output[tid] = result;
 }
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bench_bn254_field_mul_chains(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let tid = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (tid >= params.threads) {
+        return;
+    }
+
+    // Initialize each chain's field elements
+    var acc: array<array<u32, 16>, CHAINS>;
+    var b: array<array<u32, 16>, CHAINS>;
+
+    for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+        var a_c: array<u32, 16>;
+        var b_c: array<u32, 16>;
+        let base = (tid * CHAINS + c) * 2u * NUM_LIMBS;
+        for (var i: u32 = 0u; i < NUM_LIMBS; i = i + 1u) {
+            if (params.dataset != 0u) {
+                a_c[i] = input[base + i];
+                b_c[i] = input[base + NUM_LIMBS + i];
+            } else {
+                a_c[i] = (input[(tid + i + c) % 16u] ^ (tid * (i + 1u))) & W_mask;
+                b_c[i] = (input[(tid + i + c + 8u) % 16u] ^ (tid * (i + 17u))) & W_mask;
+            }
+        }
+
+        // Reduce to valid field elements
+        acc[c] = field_reduce(a_c);
+        b[c] = field_reduce(b_c);
+    }
+
+    // Main benchmark loop - one field multiplication per chain
+    for (var iter: u32 = 0u; iter < params.iterations; iter = iter + 1u) {
+        for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+            acc[c] = field_mul(acc[c], b[c]);
+            b[c][0] = (b[c][0] ^ (acc[c][0] & 0xFFu)) & W_mask;
+        }
+    }
+
+    // Write result
+    var result: u32 = 0u;
+    for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+        for (var i: u32 = 0u; i < NUM_LIMBS; i = i + 1u) {
+            result = result ^ acc[c][i];
+        }
+    }
+    output[tid] = result;
+}
@@ -3,24 +3,47 @@
 // ============================================================================
 // This benchmark measures the raw throughput of native u32 addition.
 
+// Specialized by the host (see shaders.rs): threads per workgroup and
+// independent accumulator chains per thread. One chain runs the scalar entry
+// point, so the default kernel keeps its accumulator in registers; more run
+// the `_chains` entry point.
+const WORKGROUP_SIZE: u32 = 64u;
+const CHAINS: u32 = 1u;
+
 struct BenchParams {
     iterations: u32,
     seed: u32,
-    _pad0: u32,
-    _pad1: u32,
+    // Non-zero: input holds one operand pair per thread and chain
+    dataset: u32,
+    // Threads with work; the last row of the grid may run past them
+    threads: u32,
 }
 
 @group(0) @binding(0) var<storage, read> input: array<u32>;
 @group(0) @binding(1) var<storage, read_write> output: array<u32>;
 @group(0) @binding(2) var<uniform> params: BenchParams;
 
-@compute @workgroup_size(64)
-fn bench_u32_add(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let tid = global_id.x;
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bench_u32_add(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let tid = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (tid >= params.threads) {
+        return;
+    }
 
     // Initialize with thread-unique seed
-    var acc: u32 = params.seed ^ tid;
-    var b: u32 = input[(tid + 8u) % 16u];
+    var acc: u32;
+    var b: u32;
+    if (params.dataset != 0u) {
+        acc = input[tid * 2u];
+        b = input[tid * 2u + 1u];
+    } else {
+        acc = params.seed ^ tid;
+        b = input[(tid + 8u) % 16u];
+    }
 
     // Main benchmark loop - addition operations
     for (var i: u32 = 0u; i < params.iterations; i = i + 1u) {
@@ -34,3 +57,47 @@ fn bench_u32_add(@builtin(global_invocation_id) global_id: vec3<u32>) {
     // Write result to prevent dead code elimination
     output[tid] = acc;
 }
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bench_u32_add_chains(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let tid = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (tid >= params.threads) {
+        return;
+    }
+
+    // Initialize each chain with a thread-unique seed
+    var acc: array<u32, CHAINS>;
+    var b: array<u32, CHAINS>;
+    for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+        if (params.dataset != 0u) {
+            let base = (tid * CHAINS + c) * 2u;
+            acc[c] = input[base];
+            b[c] = input[base + 1u];
+        } else {
+            acc[c] = params.seed ^ tid ^ (c * 0x9E3779B9u);
+            b[c] = input[(tid + 8u + c) % 16u];
+        }
+    }
+
+    // Main benchmark loop - one addition per chain
+    for (var i: u32 = 0u; i < params.iterations; i = i + 1u) {
+        for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+            // Addition: acc = acc + b
+            acc[c] = acc[c] + b[c];
+
+            // Data-dependent modification to prevent optimization
+            b[c] = b[c] ^ (acc[c] & 0xFFu);
+        }
+    }
+
+    // Write result to prevent dead code elimination
+    var result: u32 = 0u;
+    for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+        result = result ^ acc[c];
+    }
+    output[tid] = result;
+}
@@ -4,11 +4,20 @@
 // This benchmark measures the overhead of emulating 64-bit addition
 // using 32-bit pairs with carry propagation.
 
+// Specialized by the host (see shaders.rs): threads per workgroup and
+// independent accumulator chains per thread. One chain runs the scalar entry
+// point, so the default kernel keeps its accumulator in registers; more run
+// the `_chains` entry point.
+const WORKGROUP_SIZE: u32 = 64u;
+const CHAINS: u32 = 1u;
+
 struct BenchParams {
     iterations: u32,
     seed: u32,
-    _pad0: u32,
-    _pad1: u32,
+    // Non-zero: input holds one operand pair per thread and chain
+    dataset: u32,
+    // Threads with work; the last row of the grid may run past them
+    threads: u32,
 }
 
 @group(0) @binding(0) var<storage, read> input: array<u32>;
@@ -29,13 +38,28 @@ fn u64_add(a: U64, b: U64) -> U64 {
     return U64(lo, hi);
 }
 
-@compute @workgroup_size(64)
-fn bench_u64_add(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let tid = global_id.x;
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bench_u64_add(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let tid = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (tid >= params.threads) {
+        return;
+    }
 
     // Initialize with thread-unique seed
-    var acc = U64(params.seed ^ tid, params.seed);
-    var b = U64(input[(tid + 2u) % 16u], input[(tid + 3u) % 16u]);
+    var acc: U64;
+    var b: U64;
+    if (params.dataset != 0u) {
+        let base = tid * 4u;
+        acc = U64(input[base], input[base + 1u]);
+        b = U64(input[base + 2u], input[base + 3u]);
+    } else {
+        acc = U64(params.seed ^ tid, params.seed);
+        b = U64(input[(tid + 2u) % 16u], input[(tid + 3u) % 16u]);
+    }
 
     // Main benchmark loop - emulated 64-bit addition
     for (var i: u32 = 0u; i < params.iterations; i = i + 1u) {
@@ -49,3 +73,47 @@ fn bench_u64_add(@builtin(global_invocation_id) global_id: vec3<u32>) {
     // Write result (XOR both halves)
     output[tid] = acc.lo ^ acc.hi;
 }
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bench_u64_add_chains(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let tid = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (tid >= params.threads) {
+        return;
+    }
+
+    // Initialize each chain with a thread-unique seed
+    var acc: array<U64, CHAINS>;
+    var b: array<U64, CHAINS>;
+    for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+        if (params.dataset != 0u) {
+            let base = (tid * CHAINS + c) * 4u;
+            acc[c] = U64(input[base], input[base + 1u]);
+            b[c] = U64(input[base + 2u], input[base + 3u]);
+        } else {
+            acc[c] = U64(params.seed ^ tid ^ (c * 0x9E3779B9u), params.seed);
+            b[c] = U64(input[(tid + 2u + c) % 16u], input[(tid + 3u + c) % 16u]);
+        }
+    }
+
+    // Main benchmark loop - one emulated 64-bit addition per chain
+    for (var i: u32 = 0u; i < params.iterations; i = i + 1u) {
+        for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+            // Emulated 64-bit addition: acc = acc + b
+            acc[c] = u64_add(acc[c], b[c]);
+
+            // Data-dependent modification
+            b[c].lo = b[c].lo ^ (acc[c].lo & 0xFFu);
+        }
+    }
+
+    // Write result (XOR both halves of every chain)
+    var result: u32 = 0u;
+    for (var c: u32 = 0u; c < CHAINS; c = c + 1u) {
+        result = result ^ acc[c].lo ^ acc[c].hi;
+    }
+    output[tid] = result;
+}
@@ -0,0 +1,141 @@
+// ============================================================================
+// Stream: BN254 Vector Multiplication (Montgomery)
+// ============================================================================
+// out[i] = a[i] * b[i] * R^-1 mod p; element i is 16 limbs at words [16i, 16i + 16).
+
+const W: u32 = 16u;
+const W_mask: u32 = 0xFFFFu;
+const NUM_LIMBS: u32 = 16u;
+const MONTGOMERY_INV: u32 = 0x63B9u;
+
+// BN254 modulus p
+const BN254_P: array<u32, 16> = array<u32, 16>(
+    0x0D87u, 0x06C3u, 0x0550u, 0x048Du,
+    0x09D5u, 0x01E3u, 0x0E88u, 0x0879u,
+    0x051Au, 0x0181u, 0x0B20u, 0x0C1Cu,
+    0x057Bu, 0x074Eu, 0x09D6u, 0x030Cu
+);
+
+// Specialized by the host (see shaders.rs): threads per workgroup
+const WORKGROUP_SIZE: u32 = 64u;
+
+struct StreamParams {
+    // Elements in each buffer
+    count: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<storage, read> a: array<u32>;
+@group(0) @binding(1) var<storage, read> b: array<u32>;
+@group(0) @binding(2) var<storage, read_write> out: array<u32>;
+@group(0) @binding(3) var<uniform> params: StreamParams;
+
+// Check if BigInt256 >= BN254_P
+fn bigint_gte_p(a: array<u32, 16>) -> bool {
+    for (var i: i32 = 15; i >= 0; i = i - 1) {
+        if (a[i] > BN254_P[i]) { return true; }
+        if (a[i] < BN254_P[i]) { return false; }
+    }
+    return true;
+}
+
+// BigInt256 subtraction with BN254_P
+fn bigint_sub_p(a: array<u32, 16>) -> array<u32, 16> {
+    var result: array<u32, 16>;
+    var borrow: u32 = 0u;
+
+    for (var i: u32 = 0u; i < NUM_LIMBS; i = i + 1u) {
+        if (a[i] >= BN254_P[i] + borrow) {
+            result[i] = a[i] - BN254_P[i] - borrow;
+            borrow = 0u;
+        } else {
+            result[i] = ((1u << W) + a[i]) - BN254_P[i] - borrow;
+            borrow = 1u;
+        }
+    }
+
+    return result;
+}
+
+// Reduce modulo p
+fn field_reduce(a: array<u32, 16>) -> array<u32, 16> {
+    if (bigint_gte_p(a)) {
+        return bigint_sub_p(a);
+    }
+    return a;
+}
+
+// CIOS Montgomery multiplication: computes (a * b * R^-1) mod p
+// Fuses multiplication and reduction in a single pass using only 18 limbs
+fn mont_mul_cios(a: array<u32, 16>, b: array<u32, 16>) -> array<u32, 16> {
+    var t: array<u32, 18>;
+    for (var i: u32 = 0u; i < 18u; i = i + 1u) {
+        t[i] = 0u;
+    }
+
+    for (var i: u32 = 0u; i < NUM_LIMBS; i = i + 1u) {
+        // Phase 1: Multiply-accumulate a[i] * b
+        var c: u32 = 0u;
+        for (var j: u32 = 0u; j < NUM_LIMBS; j = j + 1u) {
+            let prod = a[i] * b[j];
+            let sum = t[j] + (prod & W_mask) + c;
+            t[j] = sum & W_mask;
+            c = (prod >> W) + (sum >> W);
+        }
+        let sum16 = t[16] + c;
+        t[16] = sum16 & W_mask;
+        t[17] = t[17] + (sum16 >> W);
+
+        // Phase 2: Reduction - compute m and add m * p
+        let m = (t[0] * MONTGOMERY_INV) & W_mask;
+        c = 0u;
+        for (var j: u32 = 0u; j < NUM_LIMBS; j = j + 1u) {
+            let prod = m * BN254_P[j];
+            let sum = t[j] + (prod & W_mask) + c;
+            t[j] = sum & W_mask;
+            c = (prod >> W) + (sum >> W);
+        }
+        let sum16_2 = t[16] + c + t[17];
+        t[16] = sum16_2 & W_mask;
+        t[17] = sum16_2 >> W;
+
+        // Phase 3: Shift right (discard t[0] which is now 0)
+        for (var j: u32 = 0u; j < 17u; j = j + 1u) {
+            t[j] = t[j + 1u];
+        }
+        t[17] = 0u;
+    }
+
+    var result: array<u32, 16>;
+    for (var i: u32 = 0u; i < NUM_LIMBS; i = i + 1u) {
+        result[i] = t[i];
+    }
+    return field_reduce(result);
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn stream_bn254_vec_mul(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let i = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    var x: array<u32, 16>;
+    var y: array<u32, 16>;
+    let base = i * NUM_LIMBS;
+    for (var j: u32 = 0u; j < NUM_LIMBS; j = j + 1u) {
+        x[j] = a[base + j];
+        y[j] = b[base + j];
+    }
+
+    let r = mont_mul_cios(x, y);
+    for (var j: u32 = 0u; j < NUM_LIMBS; j = j + 1u) {
+        out[base + j] = r[j];
+    }
+}
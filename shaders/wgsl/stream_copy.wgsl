@@ -0,0 +1,35 @@
+// ============================================================================
+// Stream: Copy (bandwidth baseline)
+// ============================================================================
+// Copies one buffer to another with no arithmetic: the memory-bandwidth
+// ceiling the field streams are compared against. `b` is bound but not read.
+
+// Specialized by the host (see shaders.rs): threads per workgroup
+const WORKGROUP_SIZE: u32 = 64u;
+
+struct StreamParams {
+    // Elements in each buffer
+    count: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<storage, read> a: array<u32>;
+@group(0) @binding(1) var<storage, read> b: array<u32>;
+@group(0) @binding(2) var<storage, read_write> out: array<u32>;
+@group(0) @binding(3) var<uniform> params: StreamParams;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn stream_copy(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let i = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    out[i] = a[i];
+}
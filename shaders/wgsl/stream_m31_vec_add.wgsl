@@ -0,0 +1,52 @@
+// ============================================================================
+// Stream: Mersenne (2^31-1) Vector Addition
+// ============================================================================
+// out[i] = a[i] + b[i] mod p, one field element per thread.
+
+const MERSENNE_P: u32 = 0x7FFFFFFFu;  // 2^31 - 1
+
+// Specialized by the host (see shaders.rs): threads per workgroup
+const WORKGROUP_SIZE: u32 = 64u;
+
+struct StreamParams {
+    // Elements in each buffer
+    count: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<storage, read> a: array<u32>;
+@group(0) @binding(1) var<storage, read> b: array<u32>;
+@group(0) @binding(2) var<storage, read_write> out: array<u32>;
+@group(0) @binding(3) var<uniform> params: StreamParams;
+
+// Reduce to [0, p) range
+// Input: value in range [0, 2*p)
+fn mersenne_reduce(x: u32) -> u32 {
+    let r = (x & MERSENNE_P) + (x >> 31u);
+    if (r >= MERSENNE_P) {
+        return r - MERSENNE_P;
+    }
+    return r;
+}
+
+// Field addition: (a + b) mod p
+fn mersenne_add(a: u32, b: u32) -> u32 {
+    let sum = a + b;  // Range: [0, 2p-2]
+    return mersenne_reduce(sum);
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn stream_m31_vec_add(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Grids too large for one dimension are split into rows by the host
+    let i = global_id.y * num_workgroups.x * WORKGROUP_SIZE + global_id.x;
+    if (i >= params.count) {
+        return;
+    }
+
+    out[i] = mersenne_add(a[i], b[i]);
+}
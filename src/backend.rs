@@ -0,0 +1,583 @@
+//! Backend-agnostic benchmark interface
+//!
+//! Every GPU runner implements [`BenchmarkBackend`], so a suite can be run
+//! against any of them (or a downstream backend) through [`run_suite`].
+
+use crate::calibration;
+use crate::config::BenchmarkConfig;
+use crate::dataset;
+use crate::device::{DeviceSelector, DeviceType};
+use crate::results::{BenchmarkReport, BenchmarkResult, Drift, StartupLatency};
+use crate::stream::{StreamLayout, StreamOperation, StreamTimings};
+use crate::transfer::TransferMethod;
+use crate::{Backend, BenchmarkError, Operation};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Device capabilities relevant to the benchmark kernels
+#[derive(Debug, Clone)]
+pub struct BackendCapabilities {
+    /// Kernels can use native 64-bit integers
+    pub native_u64: bool,
+
+    /// GPU-side timestamp queries are available
+    pub timestamp_queries: bool,
+
+    /// Maximum threads per (one-dimensional) workgroup
+    pub max_workgroup_size: u32,
+
+    /// Maximum workgroups per dispatch dimension
+    pub max_workgroups_per_dimension: u32,
+
+    /// Largest storage buffer a kernel can bind, in bytes
+    pub max_buffer_size: u64,
+}
+
+/// Workgroups dispatched along x and y
+///
+/// Grids larger than the per-dimension limit are split into `y` rows of `x`
+/// workgroups; the kernels linearize the thread index across rows and skip the
+/// threads of the last row that run past the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchGrid {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl DispatchGrid {
+    /// Smallest grid of at least `num_workgroups`, for kernels that bounds-check their index
+    pub fn covering(num_workgroups: u64, max_per_dimension: u32) -> Result<Self, BenchmarkError> {
+        let max_per_dimension = max_per_dimension.max(1) as u64;
+        let rows = num_workgroups.div_ceil(max_per_dimension).max(1);
+        if rows > max_per_dimension {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "max_workgroups_per_dimension (squared)",
+                requested: num_workgroups,
+                max: max_per_dimension * max_per_dimension,
+            });
+        }
+        Ok(Self {
+            x: num_workgroups.div_ceil(rows) as u32,
+            y: rows as u32,
+        })
+    }
+}
+
+impl BackendCapabilities {
+    /// Check that a workgroup of `workgroup_size` threads fits on the device
+    pub fn check_workgroup_size(&self, workgroup_size: u32) -> Result<(), BenchmarkError> {
+        if workgroup_size > self.max_workgroup_size {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "max_workgroup_size",
+                requested: workgroup_size as u64,
+                max: self.max_workgroup_size as u64,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check `config` for `operation` against the device limits and size its dispatch grid
+    pub fn validate(
+        &self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<DispatchGrid, BenchmarkError> {
+        self.check_workgroup_size(config.workgroup_size)?;
+        if config.num_workgroups == 0 {
+            return Err(BenchmarkError::Execution(
+                "num_workgroups must be at least 1".to_string(),
+            ));
+        }
+
+        let word = std::mem::size_of::<u32>() as u64;
+        let output_bytes = config.total_threads() * dataset::output_words(operation) as u64 * word;
+        let input_bytes = dataset::input_words(operation, config) * word;
+        for bytes in [output_bytes, input_bytes] {
+            if bytes > self.max_buffer_size {
+                return Err(BenchmarkError::LimitExceeded {
+                    limit: "max_buffer_size (bytes)",
+                    requested: bytes,
+                    max: self.max_buffer_size,
+                });
+            }
+        }
+
+        // Kernels index threads with a u32
+        let total_threads = config.total_threads();
+        if total_threads > u32::MAX as u64 {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "total threads (u32 thread index)",
+                requested: total_threads,
+                max: u32::MAX as u64,
+            });
+        }
+
+        DispatchGrid::covering(
+            config.num_workgroups as u64,
+            self.max_workgroups_per_dimension,
+        )
+    }
+}
+
+/// A GPU backend that can prepare and time benchmark kernels
+pub trait BenchmarkBackend {
+    /// Backend name used in reports (e.g. "Metal", "WebGPU")
+    fn name(&self) -> &str;
+
+    /// Name of the device the backend runs on
+    fn device_name(&self) -> String;
+
+    /// Class of the device the backend runs on
+    fn device_type(&self) -> DeviceType;
+
+    /// Capabilities of the device
+    fn capabilities(&self) -> BackendCapabilities;
+
+    /// Operations this backend can run
+    fn supported_operations(&self) -> Vec<Operation> {
+        Operation::available_with(self.capabilities().native_u64)
+    }
+
+    /// Create the pipeline and buffers for an operation
+    fn prepare(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<(), BenchmarkError>;
+
+    /// Run warmup and timed iterations of the prepared operation
+    fn run(&mut self) -> Result<BenchmarkResult, BenchmarkError>;
+
+    /// Time a single dispatch of the prepared operation
+    fn dispatch_once(&mut self) -> Result<Duration, BenchmarkError>;
+
+    /// Release the prepared pipeline and buffers
+    fn teardown(&mut self);
+
+    /// Time shader module, pipeline and first-dispatch creation, cold then warm
+    ///
+    /// Leaves nothing prepared. Backends that cannot split the stages return None.
+    fn measure_startup(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<Option<StartupLatency>, BenchmarkError> {
+        let _ = (operation, config);
+        Ok(None)
+    }
+
+    /// Time `config.measurement_iterations` dispatches of a streaming kernel over `layout`
+    ///
+    /// Builds and releases its own pipeline and buffers; nothing needs to be prepared.
+    fn run_stream(
+        &mut self,
+        operation: StreamOperation,
+        layout: &StreamLayout,
+        config: &BenchmarkConfig,
+    ) -> Result<StreamTimings, BenchmarkError> {
+        let _ = (operation, layout, config);
+        Err(BenchmarkError::BackendNotAvailable(format!(
+            "{} has no streaming kernels",
+            self.name()
+        )))
+    }
+
+    /// Time `config.measurement_iterations` transfers of `bytes` with `method`
+    ///
+    /// Each sample is the host-side latency of one whole transfer.
+    fn run_transfer(
+        &mut self,
+        method: TransferMethod,
+        bytes: u64,
+        config: &BenchmarkConfig,
+    ) -> Result<Vec<Duration>, BenchmarkError> {
+        let _ = (bytes, config);
+        Err(BenchmarkError::BackendNotAvailable(format!(
+            "{} has no {} transfer",
+            self.name(),
+            method
+        )))
+    }
+
+    /// Prepare, run and tear down a single operation
+    fn run_benchmark(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<BenchmarkResult, BenchmarkError> {
+        self.prepare(operation, config)?;
+        let result = self.run();
+        self.teardown();
+        result
+    }
+}
+
+/// Progress notifications emitted by [`run_suite_with_progress`]
+#[derive(Debug)]
+pub enum SuiteEvent<'a> {
+    Started(Operation),
+    Finished(Operation, &'a BenchmarkResult),
+    Failed(Operation, &'a BenchmarkError),
+    /// The drift sentinel is about to run (before and after the suite)
+    Sentinel(Operation),
+}
+
+/// Run each supported operation on the backend and collect a report
+///
+/// Operations the backend does not support, or that fail, are left out of
+/// the report; use [`run_suite_with_progress`] to observe failures.
+pub fn run_suite(
+    backend: &mut dyn BenchmarkBackend,
+    operations: &[Operation],
+    config: &BenchmarkConfig,
+) -> BenchmarkReport {
+    run_suite_with_progress(backend, operations, config, &mut |_| {})
+}
+
+/// Like [`run_suite`], reporting each operation's progress to `on_event`
+pub fn run_suite_with_progress(
+    backend: &mut dyn BenchmarkBackend,
+    operations: &[Operation],
+    config: &BenchmarkConfig,
+    on_event: &mut dyn FnMut(SuiteEvent),
+) -> BenchmarkReport {
+    let mut report = BenchmarkReport::new(backend.device_name(), backend.name().to_string());
+    report.device_type = Some(backend.device_type());
+
+    let supported = backend.supported_operations();
+
+    // Startup latency first, so "cold" is each pipeline's first creation in the process
+    let mut startup: HashMap<Operation, StartupLatency> = HashMap::new();
+    if config.measure_startup {
+        for op in config
+            .drift_sentinel
+            .into_iter()
+            .chain(operations.iter().copied())
+        {
+            if supported.contains(&op) && !startup.contains_key(&op) {
+                let op_config = config
+                    .for_operation(op)
+                    .with_dispatches_per_submit(1, config.submit_batching);
+                if let Ok(Some(latency)) = backend.measure_startup(op, &op_config) {
+                    startup.insert(op, latency);
+                }
+            }
+        }
+    }
+
+    // Sentinel run before the suite, repeated after it to measure drift
+    let sentinel = match config.drift_sentinel.filter(|op| supported.contains(op)) {
+        Some(op) => {
+            on_event(SuiteEvent::Sentinel(op));
+            run_operation(backend, op, config).ok().map(|r| (op, r))
+        }
+        None => None,
+    };
+
+    for (i, op) in operations
+        .iter()
+        .filter(|op| supported.contains(op))
+        .enumerate()
+    {
+        if let (Some(cooldown), true) = (config.cooldown, i > 0) {
+            std::thread::sleep(cooldown);
+        }
+
+        on_event(SuiteEvent::Started(*op));
+
+        match run_operation(backend, *op, config) {
+            Ok(result) => {
+                let result = result.with_startup_latency(startup.get(op).cloned());
+                on_event(SuiteEvent::Finished(*op, &result));
+                report.add_result(result);
+            }
+            Err(e) => on_event(SuiteEvent::Failed(*op, &e)),
+        }
+    }
+
+    if let Some((op, start)) = sentinel {
+        on_event(SuiteEvent::Sentinel(op));
+        if let Ok(end) = run_operation(backend, op, config) {
+            report.drift = Drift::from_sentinels(&start, &end);
+        }
+    }
+
+    report
+}
+
+/// Calibrate (if enabled) and run one operation
+pub(crate) fn run_operation(
+    backend: &mut dyn BenchmarkBackend,
+    operation: Operation,
+    config: &BenchmarkConfig,
+) -> Result<BenchmarkResult, BenchmarkError> {
+    // Get operation-specific config
+    let mut op_config = config.for_operation(operation);
+
+    let calibration = if config.auto_calibrate {
+        let calibration = calibration::calibrate(backend, operation, &op_config)?;
+        op_config.ops_per_thread = calibration.ops_per_thread;
+        Some(calibration)
+    } else {
+        None
+    };
+
+    let result = backend.run_benchmark(operation, &op_config)?;
+    Ok(result.with_calibration(calibration))
+}
+
+/// Create the runner for a built-in backend on the selected device
+pub fn create_backend(
+    backend: Backend,
+    selector: &DeviceSelector,
+) -> Result<Box<dyn BenchmarkBackend>, BenchmarkError> {
+    match backend {
+        #[cfg(all(feature = "metal", target_os = "macos"))]
+        Backend::Metal => Ok(Box::new(crate::metal::MetalRunner::with_selector(
+            selector,
+        )?)),
+        #[cfg(all(feature = "metal", target_os = "macos"))]
+        Backend::MetalFromWgsl => Ok(Box::new(crate::metal::MetalRunner::from_wgsl(selector)?)),
+
+        #[cfg(feature = "webgpu")]
+        Backend::WebGPU => Ok(Box::new(crate::webgpu::WebGpuRunner::with_selector(
+            selector,
+        )?)),
+
+        #[allow(unreachable_patterns)]
+        _ => {
+            let _ = selector;
+            Err(BenchmarkError::BackendNotAvailable(
+                backend.availability().err().unwrap_or_default(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backend whose kernels take 1µs, except for one operation that fails to prepare
+    struct FakeBackend {
+        failing: Operation,
+        prepared: Option<(Operation, BenchmarkConfig)>,
+        teardowns: u32,
+    }
+
+    impl FakeBackend {
+        fn new(failing: Operation) -> Self {
+            Self {
+                failing,
+                prepared: None,
+                teardowns: 0,
+            }
+        }
+    }
+
+    impl BenchmarkBackend for FakeBackend {
+        fn name(&self) -> &str {
+            "Fake"
+        }
+
+        fn device_name(&self) -> String {
+            "fake device".to_string()
+        }
+
+        fn device_type(&self) -> DeviceType {
+            DeviceType::Other
+        }
+
+        fn capabilities(&self) -> BackendCapabilities {
+            capabilities(65535)
+        }
+
+        fn prepare(
+            &mut self,
+            operation: Operation,
+            config: &BenchmarkConfig,
+        ) -> Result<(), BenchmarkError> {
+            if operation == self.failing {
+                return Err(BenchmarkError::Execution("unsupported kernel".to_string()));
+            }
+            self.prepared = Some((operation, config.clone()));
+            Ok(())
+        }
+
+        fn run(&mut self) -> Result<BenchmarkResult, BenchmarkError> {
+            let (operation, config) = self.prepared.as_ref().expect("run before prepare");
+            let timings = vec![Duration::from_micros(1); config.measurement_iterations as usize];
+            Ok(BenchmarkResult::from_timings_named(
+                self.name(),
+                *operation,
+                config.workgroup_size,
+                config.total_threads(),
+                config.ops_per_thread,
+                &timings,
+            ))
+        }
+
+        fn dispatch_once(&mut self) -> Result<Duration, BenchmarkError> {
+            Ok(Duration::from_micros(1))
+        }
+
+        fn teardown(&mut self) {
+            self.prepared = None;
+            self.teardowns += 1;
+        }
+    }
+
+    fn suite_config() -> BenchmarkConfig {
+        BenchmarkConfig::default()
+            .with_iterations(3)
+            .with_auto_calibrate(false)
+    }
+
+    /// Run the suite, recording each event as `kind:operation`
+    fn run_logged(
+        backend: &mut FakeBackend,
+        operations: &[Operation],
+        config: &BenchmarkConfig,
+    ) -> (BenchmarkReport, Vec<String>) {
+        let mut events = Vec::new();
+        let report = run_suite_with_progress(backend, operations, config, &mut |event| {
+            events.push(match event {
+                SuiteEvent::Started(op) => format!("started:{}", op.name()),
+                SuiteEvent::Finished(op, _) => format!("finished:{}", op.name()),
+                SuiteEvent::Failed(op, _) => format!("failed:{}", op.name()),
+                SuiteEvent::Sentinel(op) => format!("sentinel:{}", op.name()),
+            })
+        });
+        (report, events)
+    }
+
+    #[test]
+    fn suite_skips_unsupported_and_reports_failed_operations() {
+        let mut backend = FakeBackend::new(Operation::Bn254FieldMul);
+        let operations = [
+            Operation::U32Add,
+            Operation::U64AddNative,
+            Operation::Bn254FieldMul,
+            Operation::MersenneFieldAdd,
+        ];
+        let (report, events) = run_logged(&mut backend, &operations, &suite_config());
+
+        // No native u64 on this backend, so that operation never starts
+        assert_eq!(
+            events,
+            vec![
+                "started:u32_add",
+                "finished:u32_add",
+                "started:bn254_field_mul",
+                "failed:bn254_field_mul",
+                "started:m31_field_add",
+                "finished:m31_field_add",
+            ]
+        );
+        let names: Vec<&str> = report
+            .results
+            .iter()
+            .map(|r| r.operation.as_str())
+            .collect();
+        assert_eq!(names, vec!["u32_add", "m31_field_add"]);
+        assert_eq!(report.device_name, "fake device");
+        assert_eq!(report.device_type, Some(DeviceType::Other));
+        assert!(report
+            .results
+            .iter()
+            .all(|r| r.device_name == "fake device"));
+        assert_eq!(backend.teardowns, 2);
+    }
+
+    #[test]
+    fn suite_brackets_the_operations_with_the_sentinel() {
+        let mut backend = FakeBackend::new(Operation::Bn254FieldMul);
+        let config = suite_config().with_drift_sentinel(Some(Operation::U32Add));
+        let (report, events) = run_logged(&mut backend, &[Operation::MersenneFieldMul], &config);
+
+        assert_eq!(
+            events,
+            vec![
+                "sentinel:u32_add",
+                "started:m31_field_mul",
+                "finished:m31_field_mul",
+                "sentinel:u32_add",
+            ]
+        );
+        // Sentinel runs are not results of their own
+        assert_eq!(report.results.len(), 1);
+    }
+
+    fn capabilities(max_workgroups_per_dimension: u32) -> BackendCapabilities {
+        BackendCapabilities {
+            native_u64: false,
+            timestamp_queries: false,
+            max_workgroup_size: 256,
+            max_workgroups_per_dimension,
+            max_buffer_size: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn covering_fits_in_one_row() {
+        let grid = DispatchGrid::covering(1024, 65535).unwrap();
+        assert_eq!(grid, DispatchGrid { x: 1024, y: 1 });
+    }
+
+    #[test]
+    fn covering_splits_a_prime_into_rows() {
+        // 65537 is prime, so no exact split exists; one extra workgroup runs idle
+        let grid = DispatchGrid::covering(65537, 65535).unwrap();
+        assert_eq!(grid, DispatchGrid { x: 32769, y: 2 });
+        assert!(grid.x <= 65535);
+        assert!(grid.x as u64 * grid.y as u64 >= 65537);
+    }
+
+    #[test]
+    fn covering_rejects_more_than_the_limit_squared() {
+        assert!(DispatchGrid::covering(16, 4).is_ok());
+        assert!(matches!(
+            DispatchGrid::covering(17, 4),
+            Err(BenchmarkError::LimitExceeded { max: 16, .. })
+        ));
+    }
+
+    #[test]
+    fn validate_accepts_any_count_up_to_the_limit_squared() {
+        let config = BenchmarkConfig::default()
+            .with_workgroup_size(64)
+            .with_num_workgroups(65537);
+        let grid = capabilities(65535)
+            .validate(Operation::U32Add, &config)
+            .unwrap();
+        assert_eq!(grid.y, 2);
+    }
+
+    #[test]
+    fn validate_checks_workgroup_size_and_count() {
+        let too_wide = BenchmarkConfig::default().with_workgroup_size(512);
+        assert!(matches!(
+            capabilities(65535).validate(Operation::U32Add, &too_wide),
+            Err(BenchmarkError::LimitExceeded { requested: 512, .. })
+        ));
+
+        let empty = BenchmarkConfig::default().with_num_workgroups(0);
+        assert!(capabilities(65535)
+            .validate(Operation::U32Add, &empty)
+            .is_err());
+    }
+
+    #[test]
+    fn validate_rejects_thread_counts_past_u32() {
+        let config = BenchmarkConfig::default()
+            .with_workgroup_size(256)
+            .with_num_workgroups(u32::MAX);
+        assert!(matches!(
+            capabilities(u32::MAX).validate(Operation::U32Add, &config),
+            Err(BenchmarkError::LimitExceeded {
+                limit: "total threads (u32 thread index)",
+                ..
+            })
+        ));
+    }
+}
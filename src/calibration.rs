@@ -0,0 +1,399 @@
+//! Calibration of ops_per_thread to a target kernel duration
+//!
+//! Starting from the operation's default, ops_per_thread is doubled (or halved)
+//! until one dispatch brackets the target time, then bisected. Results are cached
+//! on disk per backend, device and dispatch shape so later runs skip the search.
+
+use crate::backend::BenchmarkBackend;
+use crate::config::BenchmarkConfig;
+use crate::{BenchmarkError, Operation};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Largest ops_per_thread the search will try
+const MAX_OPS_PER_THREAD: u32 = 1 << 24;
+
+/// Bisection stops once a dispatch is within this fraction of the target
+const TOLERANCE: f64 = 0.1;
+
+/// Upper bound on bisection steps after the target is bracketed
+const MAX_BISECT_STEPS: u32 = 8;
+
+/// The calibrated ops_per_thread for one operation, recorded in its result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calibration {
+    /// Target duration of one dispatch (in milliseconds)
+    pub target_ms: f64,
+
+    /// Duration of one dispatch at the chosen ops_per_thread (in milliseconds)
+    pub measured_ms: f64,
+
+    /// Chosen ops_per_thread
+    pub ops_per_thread: u32,
+
+    /// True if the value came from the on-disk cache
+    pub cached: bool,
+}
+
+/// On-disk cache of calibrated ops_per_thread values
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CalibrationCache {
+    entries: HashMap<String, Calibration>,
+}
+
+impl CalibrationCache {
+    /// Default cache location (`$XDG_CACHE_HOME` or `~/.cache`), if one can be found
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(base.join("field-ops-benchmarks").join("calibration.json"))
+    }
+
+    /// Load the cache, starting empty if the file is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), BenchmarkError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Key identifying a device, operation, dispatch shape, chain count, input mode
+    /// and buffer strategy
+    fn key(
+        backend: &dyn BenchmarkBackend,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> String {
+        format!(
+            "{}|{}|{}|wg{}x{}|c{}|{}|{}|{}ms",
+            backend.name(),
+            backend.device_name(),
+            operation.name(),
+            config.workgroup_size,
+            config.num_workgroups,
+            config.independent_chains,
+            config.input_mode.name(),
+            config.buffer_strategy.name(),
+            config.calibration_target.as_millis()
+        )
+    }
+
+    pub fn get(
+        &self,
+        backend: &dyn BenchmarkBackend,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Option<&Calibration> {
+        self.entries.get(&Self::key(backend, operation, config))
+    }
+
+    pub fn insert(
+        &mut self,
+        backend: &dyn BenchmarkBackend,
+        operation: Operation,
+        config: &BenchmarkConfig,
+        calibration: Calibration,
+    ) {
+        self.entries
+            .insert(Self::key(backend, operation, config), calibration);
+    }
+}
+
+/// Find ops_per_thread so one dispatch takes about `config.calibration_target`
+///
+/// Uses the cache at `config.calibration_cache` when set (unless `config.recalibrate`),
+/// and stores new results there.
+pub fn calibrate(
+    backend: &mut dyn BenchmarkBackend,
+    operation: Operation,
+    config: &BenchmarkConfig,
+) -> Result<Calibration, BenchmarkError> {
+    let cache_path = config.calibration_cache.as_deref();
+    let mut cache = cache_path.map(CalibrationCache::load).unwrap_or_default();
+
+    if let Some(cached) = cache
+        .get(backend, operation, config)
+        .filter(|_| !config.recalibrate)
+    {
+        return Ok(Calibration {
+            cached: true,
+            ..cached.clone()
+        });
+    }
+
+    let calibration = search(backend, operation, config)?;
+
+    if let Some(path) = cache_path {
+        cache.insert(backend, operation, config, calibration.clone());
+        // An unwritable cache only costs a fresh search next run
+        let _ = cache.save(path);
+    }
+
+    Ok(calibration)
+}
+
+/// Double/halve to bracket the target, then bisect
+fn search(
+    backend: &mut dyn BenchmarkBackend,
+    operation: Operation,
+    config: &BenchmarkConfig,
+) -> Result<Calibration, BenchmarkError> {
+    let target = config.calibration_target.as_secs_f64();
+    // Calibrate a single dispatch, whatever the batching of the timed samples
+    let single = config
+        .clone()
+        .with_dispatches_per_submit(1, config.submit_batching);
+    let mut measure = |ops: u32| -> Result<f64, BenchmarkError> {
+        let time = time_dispatch(backend, operation, &single.clone().with_ops_per_thread(ops));
+        time.map(|t| t.as_secs_f64())
+    };
+
+    let mut ops = operation
+        .calibrated_ops_per_thread()
+        .clamp(1, MAX_OPS_PER_THREAD);
+    let mut time = measure(ops)?;
+
+    // Bracket the target: lo is too fast, hi is too slow
+    let (mut lo, mut hi) = if time < target {
+        let mut lo = (ops, time);
+        while time < target && ops < MAX_OPS_PER_THREAD {
+            lo = (ops, time);
+            ops = ops.saturating_mul(2).min(MAX_OPS_PER_THREAD);
+            time = measure(ops)?;
+        }
+        (lo, (ops, time))
+    } else {
+        let mut hi = (ops, time);
+        while time >= target && ops > 1 {
+            hi = (ops, time);
+            ops /= 2;
+            time = measure(ops)?;
+        }
+        ((ops, time), hi)
+    };
+
+    // Bisect until a dispatch is within tolerance of the target
+    let mut best = if (lo.1 - target).abs() < (hi.1 - target).abs() {
+        lo
+    } else {
+        hi
+    };
+    for _ in 0..MAX_BISECT_STEPS {
+        if (best.1 - target).abs() <= target * TOLERANCE || hi.0 - lo.0 <= 1 {
+            break;
+        }
+        let mid = lo.0 + (hi.0 - lo.0) / 2;
+        let t = measure(mid)?;
+        if t < target {
+            lo = (mid, t);
+        } else {
+            hi = (mid, t);
+        }
+        if (t - target).abs() < (best.1 - target).abs() {
+            best = (mid, t);
+        }
+    }
+
+    Ok(Calibration {
+        target_ms: target * 1e3,
+        measured_ms: best.1 * 1e3,
+        ops_per_thread: best.0,
+        cached: false,
+    })
+}
+
+/// Prepare the operation and return the faster of two timed dispatches after a warmup
+fn time_dispatch(
+    backend: &mut dyn BenchmarkBackend,
+    operation: Operation,
+    config: &BenchmarkConfig,
+) -> Result<Duration, BenchmarkError> {
+    backend.prepare(operation, config)?;
+    let result = (|| {
+        backend.dispatch_once()?;
+        Ok(backend.dispatch_once()?.min(backend.dispatch_once()?))
+    })();
+    backend.teardown();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::BackendCapabilities;
+    use crate::config::{BufferStrategy, OutputStorage, SubmitBatching};
+    use crate::device::DeviceType;
+    use crate::results::BenchmarkResult;
+
+    /// Backend whose dispatch takes `ns_per_op` per op of the prepared config
+    struct LinearBackend {
+        ns_per_op: u64,
+        prepared: Option<BenchmarkConfig>,
+        dispatches: u32,
+    }
+
+    impl LinearBackend {
+        fn new(ns_per_op: u64) -> Self {
+            Self {
+                ns_per_op,
+                prepared: None,
+                dispatches: 0,
+            }
+        }
+    }
+
+    impl BenchmarkBackend for LinearBackend {
+        fn name(&self) -> &str {
+            "Linear"
+        }
+
+        fn device_name(&self) -> String {
+            "test device".to_string()
+        }
+
+        fn device_type(&self) -> DeviceType {
+            DeviceType::Other
+        }
+
+        fn capabilities(&self) -> BackendCapabilities {
+            BackendCapabilities {
+                native_u64: true,
+                timestamp_queries: false,
+                max_workgroup_size: 1024,
+                max_workgroups_per_dimension: u32::MAX,
+                max_buffer_size: u64::MAX,
+            }
+        }
+
+        fn prepare(
+            &mut self,
+            _operation: Operation,
+            config: &BenchmarkConfig,
+        ) -> Result<(), BenchmarkError> {
+            assert_eq!(config.dispatches_per_submit, 1);
+            self.prepared = Some(config.clone());
+            Ok(())
+        }
+
+        fn run(&mut self) -> Result<BenchmarkResult, BenchmarkError> {
+            unreachable!("calibration only dispatches once at a time")
+        }
+
+        fn dispatch_once(&mut self) -> Result<Duration, BenchmarkError> {
+            let config = self.prepared.as_ref().expect("dispatch before prepare");
+            self.dispatches += 1;
+            Ok(Duration::from_nanos(
+                config.ops_per_thread as u64 * self.ns_per_op,
+            ))
+        }
+
+        fn teardown(&mut self) {
+            self.prepared = None;
+        }
+    }
+
+    fn config(target_ms: u64) -> BenchmarkConfig {
+        BenchmarkConfig::default()
+            .with_calibration_target(Duration::from_millis(target_ms))
+            .with_calibration_cache(None)
+    }
+
+    fn assert_within_tolerance(calibration: &Calibration) {
+        let error = (calibration.measured_ms - calibration.target_ms).abs();
+        assert!(
+            error <= calibration.target_ms * TOLERANCE,
+            "{:?} is not within tolerance",
+            calibration
+        );
+    }
+
+    #[test]
+    fn search_doubles_then_bisects_to_the_target() {
+        // 1 ns per op from 100k ops: brackets at 3.2M..6.4M, then bisects
+        let mut backend = LinearBackend::new(1);
+        let calibration = calibrate(&mut backend, Operation::U32Add, &config(5)).unwrap();
+        assert_within_tolerance(&calibration);
+        assert_eq!(calibration.ops_per_thread, 4_800_000);
+        assert!(!calibration.cached);
+    }
+
+    #[test]
+    fn search_halves_when_the_default_is_too_slow() {
+        // 1 ms per op from 100 ops: halves down to 3..6, then bisects to 5
+        let mut backend = LinearBackend::new(1_000_000);
+        let calibration = calibrate(&mut backend, Operation::Bn254FieldMul, &config(5)).unwrap();
+        assert_eq!(calibration.ops_per_thread, 5);
+        assert_within_tolerance(&calibration);
+    }
+
+    #[test]
+    fn search_stops_at_the_ops_limits() {
+        let mut slow = LinearBackend::new(1_000_000_000);
+        let calibration = calibrate(&mut slow, Operation::Bn254FieldMul, &config(5)).unwrap();
+        assert_eq!(calibration.ops_per_thread, 1);
+
+        let mut fast = LinearBackend::new(0);
+        let calibration = calibrate(&mut fast, Operation::U32Add, &config(5)).unwrap();
+        assert_eq!(calibration.ops_per_thread, MAX_OPS_PER_THREAD);
+    }
+
+    #[test]
+    fn search_calibrates_one_dispatch_per_submit() {
+        // LinearBackend::prepare asserts a single dispatch per submit
+        let batched = config(5).with_dispatches_per_submit(16, SubmitBatching::SinglePass);
+        let mut backend = LinearBackend::new(1);
+        calibrate(&mut backend, Operation::U32Add, &batched).unwrap();
+    }
+
+    #[test]
+    fn cache_is_keyed_by_buffer_strategy() {
+        let path = std::env::temp_dir().join(format!(
+            "field-ops-calibration-test-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let cached = config(5).with_calibration_cache(Some(path.clone()));
+        let mut backend = LinearBackend::new(1);
+
+        let first = calibrate(&mut backend, Operation::U32Add, &cached).unwrap();
+        assert!(!first.cached);
+
+        let dispatches = backend.dispatches;
+        let second = calibrate(&mut backend, Operation::U32Add, &cached).unwrap();
+        assert!(second.cached);
+        assert_eq!(second.ops_per_thread, first.ops_per_thread);
+        assert_eq!(backend.dispatches, dispatches);
+
+        let shared = cached.clone().with_buffer_strategy(BufferStrategy {
+            metal_output: OutputStorage::Shared,
+            ..BufferStrategy::default()
+        });
+        assert!(
+            !calibrate(&mut backend, Operation::U32Add, &shared)
+                .unwrap()
+                .cached
+        );
+
+        let recalibrate = cached.with_recalibrate(true);
+        assert!(
+            !calibrate(&mut backend, Operation::U32Add, &recalibrate)
+                .unwrap()
+                .cached
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,134 @@
+//! Statistical comparison of two benchmark results
+
+use crate::results::BenchmarkResult;
+use crate::stats::{self, Statistic};
+use serde::{Deserialize, Serialize};
+
+/// Significance level used for verdicts
+pub const DEFAULT_ALPHA: f64 = 0.05;
+
+/// Hypothesis test used to decide whether two results differ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignificanceTest {
+    /// Welch's unequal-variance t-test on per-sample throughput
+    #[default]
+    Welch,
+    /// Mann-Whitney U rank test (no normality assumption)
+    MannWhitney,
+}
+
+impl SignificanceTest {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignificanceTest::Welch => "welch",
+            SignificanceTest::MannWhitney => "mann-whitney",
+        }
+    }
+
+    /// Look up a test by its `--test` name
+    pub fn from_name(name: &str) -> Option<SignificanceTest> {
+        Self::all().into_iter().find(|t| t.name() == name)
+    }
+
+    pub fn all() -> Vec<SignificanceTest> {
+        vec![SignificanceTest::Welch, SignificanceTest::MannWhitney]
+    }
+
+    /// Two-sided p-value for the two sample sets
+    pub fn p_value(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            SignificanceTest::Welch => stats::welch_t_test(a, b),
+            SignificanceTest::MannWhitney => stats::mann_whitney_u_test(a, b),
+        }
+    }
+}
+
+impl std::fmt::Display for SignificanceTest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Throughput ratio of two results with its uncertainty
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comparison {
+    /// GOP/s of the first result divided by GOP/s of the second
+    pub ratio: f64,
+
+    /// Bootstrap 95% confidence interval on the ratio
+    pub ratio_ci_low: f64,
+    pub ratio_ci_high: f64,
+
+    /// Test used and its two-sided p-value
+    pub test: SignificanceTest,
+    pub p_value: f64,
+
+    /// True if p < alpha and the ratio CI excludes 1.0
+    pub significant: bool,
+}
+
+impl Comparison {
+    pub fn verdict(&self) -> &'static str {
+        if self.significant {
+            "significant"
+        } else {
+            "within noise"
+        }
+    }
+}
+
+/// Compare the throughput of `a` against `b` from their raw samples
+///
+/// The ratio uses `a`'s statistic for both sides. Returns None if either result
+/// has fewer than two samples (e.g. loaded from an old report).
+pub fn compare(
+    a: &BenchmarkResult,
+    b: &BenchmarkResult,
+    test: SignificanceTest,
+    alpha: f64,
+) -> Option<Comparison> {
+    if a.samples_ns.len() < 2 || b.samples_ns.len() < 2 {
+        return None;
+    }
+
+    let statistic = a.statistic;
+    let times_a = sorted_times(a, statistic);
+    let times_b = sorted_times(b, statistic);
+
+    // Results may use different ops_per_thread, so compare per-sample throughput
+    let throughput_a = throughput(a, &times_a);
+    let throughput_b = throughput(b, &times_b);
+    let p_value = test.p_value(&throughput_a, &throughput_b);
+
+    // GOP/s ratio = (ops_a / t_a) / (ops_b / t_b) = scale * (t_b / t_a)
+    let scale = a.total_operations as f64 / b.total_operations.max(1) as f64;
+    let ratio =
+        scale * statistic.evaluate(&times_b) / statistic.evaluate(&times_a).max(f64::MIN_POSITIVE);
+    let (lo, hi) = stats::bootstrap_ratio_ci(&times_a, &times_b, statistic, 0.95);
+    let (ratio_ci_low, ratio_ci_high) = (lo * scale, hi * scale);
+
+    Some(Comparison {
+        ratio,
+        ratio_ci_low,
+        ratio_ci_high,
+        test,
+        p_value,
+        significant: p_value < alpha && (ratio_ci_low > 1.0 || ratio_ci_high < 1.0),
+    })
+}
+
+/// Sorted sample times the statistic is evaluated over
+fn sorted_times(result: &BenchmarkResult, statistic: Statistic) -> Vec<f64> {
+    let mut sorted: Vec<f64> = result.samples_ns.iter().map(|&t| t as f64).collect();
+    sorted.sort_by(f64::total_cmp);
+    stats::samples_for(&sorted, statistic)
+}
+
+/// GOP/s of each sample
+fn throughput(result: &BenchmarkResult, times_ns: &[f64]) -> Vec<f64> {
+    times_ns
+        .iter()
+        .map(|ns| result.total_operations as f64 / ns.max(1.0))
+        .collect()
+}
@@ -1,4 +1,8 @@
-use crate::{Backend, Operation};
+use crate::stats::Statistic;
+use crate::{Backend, BenchmarkError, Operation};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Benchmark configuration
 #[derive(Debug, Clone)]
@@ -15,14 +19,59 @@ pub struct BenchmarkConfig {
     /// Number of warmup iterations (not timed)
     pub warmup_iterations: u32,
 
-    /// Number of measurement iterations
+    /// Number of measurement iterations (the minimum when sampling adaptively)
     pub measurement_iterations: u32,
 
+    /// Keep sampling until the throughput CI converges, instead of a fixed count
+    pub adaptive: Option<AdaptiveSampling>,
+
     /// Random seed for input data
     pub seed: u32,
 
-    /// Use operation-specific ops_per_thread for faster completion
+    /// Calibrate ops_per_thread per operation so one dispatch takes `calibration_target`
     pub auto_calibrate: bool,
+
+    /// Target duration of one dispatch when auto-calibrating
+    pub calibration_target: Duration,
+
+    /// File caching calibrated ops_per_thread per device and operation (None disables caching)
+    pub calibration_cache: Option<PathBuf>,
+
+    /// Ignore cached calibrations and overwrite them with fresh ones
+    pub recalibrate: bool,
+
+    /// Time an empty (zero-iteration) dispatch to estimate fixed submit/sync overhead
+    pub measure_overhead: bool,
+
+    /// Time shader module, pipeline and first-dispatch creation before each operation
+    pub measure_startup: bool,
+
+    /// Timing statistic that drives the reported throughput
+    pub statistic: Statistic,
+
+    /// Operation run before and after a suite to measure drift (None disables)
+    pub drift_sentinel: Option<Operation>,
+
+    /// Pause between operations to let the device cool down
+    pub cooldown: Option<Duration>,
+
+    /// Dispatches recorded per submission (each sample times one submission)
+    pub dispatches_per_submit: u32,
+
+    /// How batched dispatches are recorded
+    pub submit_batching: SubmitBatching,
+
+    /// Independent accumulator chains per thread (1, 2, 4 or 8); more chains expose ILP
+    pub independent_chains: u32,
+
+    /// Where kernels read their operands from
+    pub input_mode: InputMode,
+
+    /// Read back the output and check sampled threads against a CPU reference
+    pub verify: bool,
+
+    /// Storage modes and usages of the output and params buffers
+    pub buffer_strategy: BufferStrategy,
 }
 
 impl Default for BenchmarkConfig {
@@ -33,8 +82,23 @@ impl Default for BenchmarkConfig {
             num_workgroups: 1024,
             warmup_iterations: 3,
             measurement_iterations: 10,
+            adaptive: None,
             seed: 0x12345678,
             auto_calibrate: true,
+            calibration_target: Duration::from_millis(20),
+            calibration_cache: None,
+            recalibrate: false,
+            measure_overhead: false,
+            measure_startup: false,
+            statistic: Statistic::Min,
+            drift_sentinel: None,
+            cooldown: None,
+            dispatches_per_submit: 1,
+            submit_batching: SubmitBatching::SinglePass,
+            independent_chains: 1,
+            input_mode: InputMode::ComputeBound,
+            verify: false,
+            buffer_strategy: BufferStrategy::default(),
         }
     }
 }
@@ -46,6 +110,12 @@ impl BenchmarkConfig {
         self
     }
 
+    /// Create a new config with the given number of workgroups
+    pub fn with_num_workgroups(mut self, count: u32) -> Self {
+        self.num_workgroups = count;
+        self
+    }
+
     /// Create a new config with the given ops per thread
     pub fn with_ops_per_thread(mut self, ops: u32) -> Self {
         self.ops_per_thread = ops;
@@ -58,13 +128,110 @@ impl BenchmarkConfig {
         self
     }
 
+    /// Create a new config with the given warmup iterations
+    pub fn with_warmup_iterations(mut self, iterations: u32) -> Self {
+        self.warmup_iterations = iterations;
+        self
+    }
+
+    /// Create a new config with the given input seed
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sample adaptively until the throughput CI converges
+    pub fn with_adaptive_sampling(mut self, adaptive: AdaptiveSampling) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
     /// Enable or disable auto-calibration
     pub fn with_auto_calibrate(mut self, enabled: bool) -> Self {
         self.auto_calibrate = enabled;
         self
     }
 
-    /// Get operation-specific config (uses calibrated ops_per_thread if auto_calibrate is true)
+    /// Set the target dispatch duration for auto-calibration
+    pub fn with_calibration_target(mut self, target: Duration) -> Self {
+        self.calibration_target = target;
+        self
+    }
+
+    /// Set (or disable, with None) the calibration cache file
+    pub fn with_calibration_cache(mut self, path: Option<PathBuf>) -> Self {
+        self.calibration_cache = path;
+        self
+    }
+
+    /// Ignore cached calibrations and overwrite them
+    pub fn with_recalibrate(mut self, enabled: bool) -> Self {
+        self.recalibrate = enabled;
+        self
+    }
+
+    /// Enable or disable dispatch overhead calibration
+    pub fn with_overhead_calibration(mut self, enabled: bool) -> Self {
+        self.measure_overhead = enabled;
+        self
+    }
+
+    /// Enable or disable startup latency measurement
+    pub fn with_startup_latency(mut self, enabled: bool) -> Self {
+        self.measure_startup = enabled;
+        self
+    }
+
+    /// Choose the timing statistic that drives the reported throughput
+    pub fn with_statistic(mut self, statistic: Statistic) -> Self {
+        self.statistic = statistic;
+        self
+    }
+
+    /// Set (or disable, with None) the drift sentinel operation
+    pub fn with_drift_sentinel(mut self, operation: Option<Operation>) -> Self {
+        self.drift_sentinel = operation;
+        self
+    }
+
+    /// Pause between operations
+    pub fn with_cooldown(mut self, cooldown: Option<Duration>) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Record `count` dispatches per submission, batched as `batching`
+    pub fn with_dispatches_per_submit(mut self, count: u32, batching: SubmitBatching) -> Self {
+        self.dispatches_per_submit = count.max(1);
+        self.submit_batching = batching;
+        self
+    }
+
+    /// Run `chains` independent accumulator chains per thread
+    pub fn with_independent_chains(mut self, chains: u32) -> Self {
+        self.independent_chains = chains;
+        self
+    }
+
+    /// Create a new config with the given input mode
+    pub fn with_input_mode(mut self, mode: InputMode) -> Self {
+        self.input_mode = mode;
+        self
+    }
+
+    /// Check outputs against the CPU reference after each operation
+    pub fn with_verification(mut self, enabled: bool) -> Self {
+        self.verify = enabled;
+        self
+    }
+
+    /// Create a new config with the given buffer strategy
+    pub fn with_buffer_strategy(mut self, strategy: BufferStrategy) -> Self {
+        self.buffer_strategy = strategy;
+        self
+    }
+
+    /// Get operation-specific config (starts from the default ops_per_thread if auto_calibrate is true)
     pub fn for_operation(&self, op: Operation) -> Self {
         if self.auto_calibrate {
             Self {
@@ -81,9 +248,269 @@ impl BenchmarkConfig {
         self.workgroup_size as u64 * self.num_workgroups as u64
     }
 
-    /// Total number of operations
+    /// Total number of operations in one dispatch (every chain runs ops_per_thread)
     pub fn total_operations(&self) -> u64 {
-        self.total_threads() * self.ops_per_thread as u64
+        self.total_threads() * self.ops_per_thread as u64 * self.independent_chains as u64
+    }
+}
+
+/// How `dispatches_per_submit` dispatches are recorded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitBatching {
+    /// All dispatches in one compute pass of one command buffer
+    #[default]
+    SinglePass,
+    /// One command buffer per dispatch, submitted together
+    CommandBuffers,
+}
+
+impl SubmitBatching {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubmitBatching::SinglePass => "single_pass",
+            SubmitBatching::CommandBuffers => "command_buffers",
+        }
+    }
+}
+
+/// Where kernels read their operands from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputMode {
+    /// Every thread reads a shared 16-word buffer, which stays in cache
+    #[default]
+    ComputeBound,
+    /// One random, reduced operand pair per thread and chain
+    Dataset,
+}
+
+impl InputMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputMode::ComputeBound => "compute_bound",
+            InputMode::Dataset => "dataset",
+        }
+    }
+
+    /// Look up a mode by its `--input` name
+    pub fn from_name(name: &str) -> Option<InputMode> {
+        Self::all()
+            .into_iter()
+            .find(|m| m.name() == name || m.name().replace('_', "-") == name)
+    }
+
+    pub fn all() -> Vec<InputMode> {
+        vec![InputMode::ComputeBound, InputMode::Dataset]
+    }
+}
+
+/// Storage mode of the Metal output buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStorage {
+    /// GPU-only memory, read back through a blit
+    #[default]
+    Private,
+    /// Memory visible to both the CPU and the GPU
+    Shared,
+}
+
+impl OutputStorage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputStorage::Private => "private_output",
+            OutputStorage::Shared => "shared_output",
+        }
+    }
+
+    pub fn all() -> Vec<OutputStorage> {
+        vec![OutputStorage::Private, OutputStorage::Shared]
+    }
+}
+
+/// Usages of the wgpu output buffer besides `STORAGE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputUsage {
+    /// `STORAGE` alone; `COPY_SRC` is added only when `--verify` reads it back
+    #[default]
+    StorageOnly,
+    /// `COPY_SRC` even when nothing is read back
+    CopySrc,
+    /// `MAP_READ`, mapped directly for `--verify` (needs `MAPPABLE_PRIMARY_BUFFERS`)
+    MapRead,
+    /// `COPY_SRC`, created mapped and zeroed through the mapping
+    MappedAtCreation,
+}
+
+impl OutputUsage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputUsage::StorageOnly => "storage_only_output",
+            OutputUsage::CopySrc => "copy_src_output",
+            OutputUsage::MapRead => "map_read_output",
+            OutputUsage::MappedAtCreation => "mapped_output",
+        }
+    }
+
+    pub fn all() -> Vec<OutputUsage> {
+        vec![
+            OutputUsage::StorageOnly,
+            OutputUsage::CopySrc,
+            OutputUsage::MapRead,
+            OutputUsage::MappedAtCreation,
+        ]
+    }
+}
+
+/// How the kernel parameters are bound
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamsBinding {
+    /// Uniform buffer (Metal: `constant` address space)
+    #[default]
+    Uniform,
+    /// Read-only storage buffer (Metal: `device` address space)
+    Storage,
+    /// Push constants (Metal: `set_bytes`); needs `PUSH_CONSTANTS` on wgpu
+    PushConstants,
+}
+
+impl ParamsBinding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParamsBinding::Uniform => "uniform_params",
+            ParamsBinding::Storage => "storage_params",
+            ParamsBinding::PushConstants => "push_constant_params",
+        }
+    }
+
+    pub fn all() -> Vec<ParamsBinding> {
+        vec![
+            ParamsBinding::Uniform,
+            ParamsBinding::Storage,
+            ParamsBinding::PushConstants,
+        ]
+    }
+}
+
+/// Buffer choices of a benchmark, one per axis; each backend reads only its own axes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BufferStrategy {
+    pub metal_output: OutputStorage,
+    pub wgpu_output: OutputUsage,
+    pub params: ParamsBinding,
+}
+
+impl BufferStrategy {
+    /// The non-default choices joined by `+`, or `default`
+    pub fn name(&self) -> String {
+        let mut choices = Vec::new();
+        if self.metal_output != OutputStorage::default() {
+            choices.push(self.metal_output.name());
+        }
+        if self.wgpu_output != OutputUsage::default() {
+            choices.push(self.wgpu_output.name());
+        }
+        if self.params != ParamsBinding::default() {
+            choices.push(self.params.name());
+        }
+        if choices.is_empty() {
+            "default".to_string()
+        } else {
+            choices.join("+")
+        }
+    }
+
+    /// Parse `default` or choices joined by `+`, e.g. `shared_output+storage_params`
+    pub fn from_name(name: &str) -> Result<BufferStrategy, String> {
+        let mut strategy = BufferStrategy::default();
+        for choice in name.split('+').map(|c| c.trim().replace('-', "_")) {
+            if choice == "default" {
+                continue;
+            }
+            if let Some(storage) = OutputStorage::all()
+                .into_iter()
+                .find(|s| s.name() == choice)
+            {
+                strategy.metal_output = storage;
+            } else if let Some(usage) = OutputUsage::all().into_iter().find(|u| u.name() == choice)
+            {
+                strategy.wgpu_output = usage;
+            } else if let Some(params) = ParamsBinding::all()
+                .into_iter()
+                .find(|p| p.name() == choice)
+            {
+                strategy.params = params;
+            } else {
+                return Err(format!(
+                    "unknown buffer strategy '{}', expected default or one of: {}",
+                    choice,
+                    Self::choice_names().join(", ")
+                ));
+            }
+        }
+        Ok(strategy)
+    }
+
+    /// Every choice accepted by [`BufferStrategy::from_name`]
+    pub fn choice_names() -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = OutputStorage::all().iter().map(|s| s.name()).collect();
+        names.extend(OutputUsage::all().iter().map(|u| u.name()));
+        names.extend(ParamsBinding::all().iter().map(|p| p.name()));
+        names
+    }
+
+    /// True if every non-default choice takes effect on `backend`
+    ///
+    /// Native Metal kernels are precompiled with `constant` params, so only the
+    /// naga-translated ones can bind them as storage.
+    pub fn applies_to(&self, backend: Backend) -> bool {
+        match backend {
+            Backend::Metal => {
+                self.wgpu_output == OutputUsage::default() && self.params != ParamsBinding::Storage
+            }
+            Backend::MetalFromWgsl => self.wgpu_output == OutputUsage::default(),
+            Backend::WebGPU => self.metal_output == OutputStorage::default(),
+        }
+    }
+
+    /// Fail with `BackendNotAvailable` unless the strategy [applies](Self::applies_to) to `backend`
+    pub fn check(&self, backend: Backend) -> Result<(), BenchmarkError> {
+        if self.applies_to(backend) {
+            Ok(())
+        } else {
+            Err(BenchmarkError::BackendNotAvailable(format!(
+                "buffer strategy {} does not apply to {}",
+                self.name(),
+                backend.name()
+            )))
+        }
+    }
+}
+
+/// Stopping rule for adaptive sampling
+#[derive(Debug, Clone)]
+pub struct AdaptiveSampling {
+    /// Target half-width of the 95% throughput CI relative to the estimate (0.01 = ±1%)
+    pub target_relative_ci: f64,
+
+    /// Stop after this much sampling time even if the CI has not converged
+    pub time_budget: Duration,
+
+    /// Upper bound on the number of samples
+    pub max_samples: u32,
+}
+
+impl Default for AdaptiveSampling {
+    fn default() -> Self {
+        Self {
+            target_relative_ci: 0.01,
+            time_budget: Duration::from_secs(10),
+            max_samples: 1000,
+        }
     }
 }
 
@@ -112,3 +539,119 @@ impl BenchmarkRun {
 
 /// Available workgroup sizes
 pub const WORKGROUP_SIZES: [u32; 3] = [64, 128, 256];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library_defaults_are_opt_in() {
+        let config = BenchmarkConfig::default();
+        // The CLI turns these on in build_config
+        assert_eq!(config.calibration_cache, None);
+        assert!(!config.measure_overhead);
+        assert!(!config.measure_startup);
+        assert_eq!(config.drift_sentinel, None);
+    }
+
+    #[test]
+    fn default_strategy_name() {
+        assert_eq!(BufferStrategy::default().name(), "default");
+        assert_eq!(
+            BufferStrategy::default().wgpu_output,
+            OutputUsage::StorageOnly
+        );
+        assert_eq!(
+            BufferStrategy::from_name("default").unwrap(),
+            BufferStrategy::default()
+        );
+    }
+
+    #[test]
+    fn name_round_trips() {
+        let strategy = BufferStrategy {
+            metal_output: OutputStorage::Shared,
+            wgpu_output: OutputUsage::MapRead,
+            params: ParamsBinding::PushConstants,
+        };
+        assert_eq!(
+            strategy.name(),
+            "shared_output+map_read_output+push_constant_params"
+        );
+        assert_eq!(
+            BufferStrategy::from_name(&strategy.name()).unwrap(),
+            strategy
+        );
+    }
+
+    #[test]
+    fn from_name_accepts_dashes_and_spaces() {
+        let strategy = BufferStrategy::from_name("shared-output + storage-params").unwrap();
+        assert_eq!(strategy.metal_output, OutputStorage::Shared);
+        assert_eq!(strategy.wgpu_output, OutputUsage::StorageOnly);
+        assert_eq!(strategy.params, ParamsBinding::Storage);
+    }
+
+    #[test]
+    fn from_name_rejects_unknown_choice() {
+        let err = BufferStrategy::from_name("shared_output+fast").unwrap_err();
+        assert!(err.contains("'fast'"));
+        assert!(err.contains("copy_src_output"));
+    }
+
+    #[test]
+    fn every_choice_parses() {
+        let names = BufferStrategy::choice_names();
+        assert_eq!(names.len(), 9);
+        for name in names {
+            // Choosing an axis's default leaves the strategy at `default`
+            let parsed = BufferStrategy::from_name(name).unwrap().name();
+            assert!(
+                parsed == name || parsed == "default",
+                "{} -> {}",
+                name,
+                parsed
+            );
+        }
+        assert_eq!(
+            BufferStrategy::from_name("private_output+storage_only_output+uniform_params").unwrap(),
+            BufferStrategy::default()
+        );
+    }
+
+    #[test]
+    fn default_applies_everywhere() {
+        let strategy = BufferStrategy::default();
+        for backend in [Backend::WebGPU, Backend::Metal, Backend::MetalFromWgsl] {
+            assert!(strategy.check(backend).is_ok());
+        }
+    }
+
+    #[test]
+    fn check_rejects_other_backends_axes() {
+        let metal = BufferStrategy::from_name("shared_output").unwrap();
+        assert!(metal.check(Backend::Metal).is_ok());
+        assert!(metal.check(Backend::MetalFromWgsl).is_ok());
+        assert!(matches!(
+            metal.check(Backend::WebGPU),
+            Err(BenchmarkError::BackendNotAvailable(_))
+        ));
+
+        let wgpu = BufferStrategy::from_name("copy_src_output").unwrap();
+        assert!(wgpu.check(Backend::WebGPU).is_ok());
+        assert!(wgpu.check(Backend::Metal).is_err());
+        assert!(wgpu.check(Backend::MetalFromWgsl).is_err());
+    }
+
+    #[test]
+    fn storage_params_need_translated_metal_kernels() {
+        let storage = BufferStrategy::from_name("storage_params").unwrap();
+        assert!(storage.applies_to(Backend::WebGPU));
+        assert!(storage.applies_to(Backend::MetalFromWgsl));
+        assert!(!storage.applies_to(Backend::Metal));
+
+        // Native kernels take push constants through `set_bytes`
+        let push = BufferStrategy::from_name("push_constant_params").unwrap();
+        assert!(push.applies_to(Backend::Metal));
+    }
+}
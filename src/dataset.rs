@@ -0,0 +1,164 @@
+//! Kernel input buffers
+//!
+//! In compute-bound mode every thread reads the same 16 words. In dataset mode
+//! the host generates one random, reduced operand pair per thread and chain:
+//! record `tid * chains + c` holds `a` then `b`, each `element_words` long.
+
+use crate::config::{BenchmarkConfig, InputMode};
+use crate::Operation;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Words in the compute-bound input buffer
+pub const COMPUTE_BOUND_WORDS: usize = 16;
+
+/// Mersenne-31 modulus
+pub const MERSENNE_P: u32 = 0x7FFF_FFFF;
+
+/// BN254 modulus as used by the kernels: 16 limbs of 16 bits, least significant first
+pub const BN254_P: [u32; 16] = [
+    0x0D87, 0x06C3, 0x0550, 0x048D, 0x09D5, 0x01E3, 0x0E88, 0x0879, 0x051A, 0x0181, 0x0B20, 0x0C1C,
+    0x057B, 0x074E, 0x09D6, 0x030C,
+];
+
+/// Words of one field element (or integer) operand
+pub fn element_words(operation: Operation) -> usize {
+    match operation {
+        Operation::U32Add | Operation::MersenneFieldAdd | Operation::MersenneFieldMul => 1,
+        Operation::U64AddNative | Operation::U64AddEmulated => 2,
+        Operation::Bn254FieldAdd | Operation::Bn254FieldMul => BN254_P.len(),
+    }
+}
+
+/// Words each thread writes to the output buffer
+pub fn output_words(operation: Operation) -> usize {
+    match operation {
+        // Metal's native u64 kernel writes a ulong per thread
+        Operation::U64AddNative => 2,
+        _ => 1,
+    }
+}
+
+/// Size of the input buffer for `config`, in words
+pub fn input_words(operation: Operation, config: &BenchmarkConfig) -> u64 {
+    match config.input_mode {
+        InputMode::ComputeBound => COMPUTE_BOUND_WORDS as u64,
+        InputMode::Dataset => {
+            config.total_threads()
+                * config.independent_chains as u64
+                * 2
+                * element_words(operation) as u64
+        }
+    }
+}
+
+/// Generate the input buffer for `config`, seeded by `config.seed`
+pub fn generate(operation: Operation, config: &BenchmarkConfig) -> Vec<u32> {
+    match config.input_mode {
+        InputMode::ComputeBound => (0..COMPUTE_BOUND_WORDS as u32)
+            .map(|i| config.seed.wrapping_add(i).wrapping_mul(0x9E3779B9))
+            .collect(),
+        InputMode::Dataset => {
+            let elements = input_words(operation, config) as usize / element_words(operation);
+            random_elements(operation, elements, config.seed)
+        }
+    }
+}
+
+/// `count` random operands reduced for the operation's field, seeded by `seed`
+pub fn random_elements(operation: Operation, count: usize, seed: u32) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(seed as u64);
+    let mut data = Vec::with_capacity(count * element_words(operation));
+    for _ in 0..count {
+        random_element(operation, &mut rng, &mut data);
+    }
+    data
+}
+
+/// Append one random operand, reduced for the operation's field
+fn random_element(operation: Operation, rng: &mut StdRng, data: &mut Vec<u32>) {
+    match operation {
+        Operation::U32Add => data.push(rng.gen()),
+        Operation::U64AddNative | Operation::U64AddEmulated => {
+            data.extend([rng.gen::<u32>(), rng.gen::<u32>()])
+        }
+        Operation::MersenneFieldAdd => data.push(rng.gen_range(0..MERSENNE_P)),
+        // Non-zero, so products never collapse to zero
+        Operation::MersenneFieldMul => data.push(rng.gen_range(1..MERSENNE_P)),
+        Operation::Bn254FieldAdd | Operation::Bn254FieldMul => {
+            // Rejection-sample below p; the top limb is masked to p's bit length
+            let top_mask = (BN254_P[15] + 1).next_power_of_two() - 1;
+            loop {
+                let mut limbs = [0u32; 16];
+                for limb in limbs.iter_mut() {
+                    *limb = rng.gen::<u32>() & 0xFFFF;
+                }
+                limbs[15] &= top_mask;
+                if limbs.iter().rev().lt(BN254_P.iter().rev()) {
+                    data.extend(limbs);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset_config() -> BenchmarkConfig {
+        BenchmarkConfig::default()
+            .with_workgroup_size(64)
+            .with_num_workgroups(2)
+            .with_input_mode(InputMode::Dataset)
+    }
+
+    #[test]
+    fn compute_bound_input_is_fixed_size() {
+        let config = BenchmarkConfig::default();
+        for operation in Operation::all() {
+            assert_eq!(input_words(operation, &config), COMPUTE_BOUND_WORDS as u64);
+            assert_eq!(generate(operation, &config).len(), COMPUTE_BOUND_WORDS);
+        }
+    }
+
+    #[test]
+    fn dataset_holds_a_pair_per_thread_and_chain() {
+        let config = dataset_config().with_independent_chains(4);
+        for operation in Operation::all() {
+            let words = 128 * 4 * 2 * element_words(operation) as u64;
+            assert_eq!(input_words(operation, &config), words);
+            assert_eq!(generate(operation, &config).len() as u64, words);
+        }
+    }
+
+    #[test]
+    fn dataset_is_seeded() {
+        let config = dataset_config();
+        let first = generate(Operation::U32Add, &config);
+        assert_eq!(first, generate(Operation::U32Add, &config));
+        assert_ne!(
+            first,
+            generate(Operation::U32Add, &config.clone().with_seed(7))
+        );
+    }
+
+    #[test]
+    fn mersenne_operands_are_reduced() {
+        let add = random_elements(Operation::MersenneFieldAdd, 1000, 1);
+        assert!(add.iter().all(|&x| x < MERSENNE_P));
+        let mul = random_elements(Operation::MersenneFieldMul, 1000, 1);
+        assert!(mul.iter().all(|&x| x > 0 && x < MERSENNE_P));
+    }
+
+    #[test]
+    fn bn254_operands_are_reduced_16_bit_limbs() {
+        let data = random_elements(Operation::Bn254FieldMul, 200, 3);
+        assert_eq!(data.len(), 200 * 16);
+        for element in data.chunks(16) {
+            assert!(element.iter().all(|&limb| limb <= 0xFFFF));
+            assert!(element.iter().rev().lt(BN254_P.iter().rev()));
+        }
+    }
+}
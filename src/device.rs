@@ -0,0 +1,153 @@
+use crate::Backend;
+use serde::{Deserialize, Serialize};
+
+/// Which GPU device (adapter) a backend should run on
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DeviceSelector {
+    /// Backend's preferred device (high-performance adapter / system default)
+    #[default]
+    Default,
+
+    /// Device at the given index in the backend's enumeration order
+    Index(usize),
+
+    /// First device whose name contains the given string (case-insensitive)
+    Name(String),
+
+    /// Software fallback adapter (llvmpipe, lavapipe, WARP)
+    Software,
+}
+
+impl DeviceSelector {
+    /// Parse an `--adapter` argument: a number selects by index, anything else by name
+    pub fn parse(value: &str) -> Self {
+        match value.trim().parse::<usize>() {
+            Ok(index) => DeviceSelector::Index(index),
+            Err(_) => DeviceSelector::Name(value.trim().to_string()),
+        }
+    }
+
+    /// Returns true if the device at `index` named `name` matches this selector
+    pub fn matches(&self, index: usize, name: &str) -> bool {
+        match self {
+            DeviceSelector::Default => index == 0,
+            DeviceSelector::Index(i) => *i == index,
+            DeviceSelector::Name(n) => name.to_lowercase().contains(&n.to_lowercase()),
+            DeviceSelector::Software => false,
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceSelector::Default => write!(f, "default"),
+            DeviceSelector::Index(i) => write!(f, "#{}", i),
+            DeviceSelector::Name(n) => write!(f, "'{}'", n),
+            DeviceSelector::Software => write!(f, "software fallback"),
+        }
+    }
+}
+
+/// Class of device a benchmark ran on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceType {
+    DiscreteGpu,
+    IntegratedGpu,
+    VirtualGpu,
+    /// Software rasterizer; results are not GPU performance numbers
+    Cpu,
+    Other,
+}
+
+impl DeviceType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeviceType::DiscreteGpu => "DiscreteGpu",
+            DeviceType::IntegratedGpu => "IntegratedGpu",
+            DeviceType::VirtualGpu => "VirtualGpu",
+            DeviceType::Cpu => "Cpu",
+            DeviceType::Other => "Other",
+        }
+    }
+
+    /// Returns true for software adapters that must not be compared with hardware
+    pub fn is_software(&self) -> bool {
+        matches!(self, DeviceType::Cpu)
+    }
+}
+
+impl std::fmt::Display for DeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(feature = "webgpu")]
+impl From<wgpu::DeviceType> for DeviceType {
+    fn from(device_type: wgpu::DeviceType) -> Self {
+        match device_type {
+            wgpu::DeviceType::DiscreteGpu => DeviceType::DiscreteGpu,
+            wgpu::DeviceType::IntegratedGpu => DeviceType::IntegratedGpu,
+            wgpu::DeviceType::VirtualGpu => DeviceType::VirtualGpu,
+            wgpu::DeviceType::Cpu => DeviceType::Cpu,
+            wgpu::DeviceType::Other => DeviceType::Other,
+        }
+    }
+}
+
+/// Description of an enumerated GPU device, used by `list-devices`
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    /// Backend that enumerated this device
+    pub backend: Backend,
+
+    /// Index in the backend's enumeration order (usable with `--adapter`)
+    pub index: usize,
+
+    /// Device name as reported by the driver
+    pub name: String,
+
+    /// Device class (discrete, integrated, cpu, ...)
+    pub device_type: DeviceType,
+
+    /// Driver / native API description
+    pub driver: String,
+
+    /// Compute-relevant limits
+    pub limits: Vec<(&'static str, u64)>,
+
+    /// Supported optional features
+    pub features: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selector_parses_index_or_name() {
+        assert_eq!(DeviceSelector::parse("1"), DeviceSelector::Index(1));
+        assert_eq!(DeviceSelector::parse(" 0 "), DeviceSelector::Index(0));
+        assert_eq!(
+            DeviceSelector::parse(" Radeon "),
+            DeviceSelector::Name("Radeon".to_string())
+        );
+        assert_eq!(
+            DeviceSelector::parse("-1"),
+            DeviceSelector::Name("-1".to_string())
+        );
+    }
+
+    #[test]
+    fn selector_matches_by_index_or_case_insensitive_name() {
+        assert!(DeviceSelector::Default.matches(0, "any"));
+        assert!(!DeviceSelector::Default.matches(1, "any"));
+        assert!(DeviceSelector::Index(2).matches(2, "any"));
+        assert!(!DeviceSelector::Index(2).matches(0, "any"));
+
+        let name = DeviceSelector::parse("rtx");
+        assert!(name.matches(3, "NVIDIA GeForce RTX 4090"));
+        assert!(!name.matches(0, "Apple M2"));
+    }
+}
@@ -1,9 +1,24 @@
+pub mod backend;
+pub mod calibration;
+pub mod compare;
 pub mod config;
+pub mod dataset;
+pub mod device;
+pub mod occupancy;
 pub mod reporter;
 pub mod results;
+pub mod sampling;
+pub mod shaders;
+pub mod stats;
+pub mod stream;
+pub mod suite;
+pub mod sweep;
+pub mod transfer;
+pub mod translate;
 pub mod tui;
+pub mod verify;
 
-#[cfg(feature = "metal")]
+#[cfg(all(feature = "metal", target_os = "macos"))]
 pub mod metal;
 
 #[cfg(feature = "webgpu")]
@@ -11,11 +26,16 @@ pub mod webgpu;
 
 use thiserror::Error;
 
+pub use backend::{run_suite, BenchmarkBackend};
+
 #[derive(Error, Debug)]
 pub enum BenchmarkError {
     #[error("No GPU device found")]
     NoDevice,
 
+    #[error("No GPU device matching {0}")]
+    DeviceNotFound(String),
+
     #[error("Backend not available: {0}")]
     BackendNotAvailable(String),
 
@@ -31,6 +51,16 @@ pub enum BenchmarkError {
     #[error("Execution failed: {0}")]
     Execution(String),
 
+    #[error("{limit} is {max} on this device, but {requested} was requested")]
+    LimitExceeded {
+        limit: &'static str,
+        requested: u64,
+        max: u64,
+    },
+
+    #[error("Invalid suite file: {0}")]
+    InvalidSuite(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -39,6 +69,8 @@ pub enum BenchmarkError {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Backend {
     Metal,
+    /// Metal running naga-generated MSL from the WGSL kernels
+    MetalFromWgsl,
     WebGPU,
 }
 
@@ -46,31 +78,72 @@ impl Backend {
     pub fn name(&self) -> &'static str {
         match self {
             Backend::Metal => "Metal",
+            Backend::MetalFromWgsl => "Metal-WGSL",
             Backend::WebGPU => "WebGPU",
         }
     }
 
+    /// Name accepted by `--backend`
+    pub fn cli_name(&self) -> &'static str {
+        match self {
+            Backend::Metal => "metal",
+            Backend::MetalFromWgsl => "metal-wgsl",
+            Backend::WebGPU => "webgpu",
+        }
+    }
+
+    /// Look up a backend by its `--backend` name
+    pub fn from_cli_name(name: &str) -> Option<Backend> {
+        Self::all().into_iter().find(|b| b.cli_name() == name)
+    }
+
     /// Returns true if this backend has native u64 support
     pub fn has_native_u64(&self) -> bool {
         matches!(self, Backend::Metal)
     }
 
     pub fn is_available(&self) -> bool {
+        self.availability().is_ok()
+    }
+
+    /// Returns `Err` with a human-readable reason if this backend cannot run here
+    pub fn availability(&self) -> Result<(), String> {
         match self {
-            #[cfg(feature = "metal")]
-            Backend::Metal => cfg!(target_os = "macos"),
+            #[cfg(all(feature = "metal", target_os = "macos"))]
+            Backend::Metal | Backend::MetalFromWgsl => Ok(()),
+            #[cfg(all(feature = "metal", not(target_os = "macos")))]
+            Backend::Metal | Backend::MetalFromWgsl => Err(format!(
+                "Metal requires macOS (this build targets {})",
+                std::env::consts::OS
+            )),
             #[cfg(not(feature = "metal"))]
-            Backend::Metal => false,
+            Backend::Metal | Backend::MetalFromWgsl => {
+                Err("built without the `metal` feature".to_string())
+            }
 
             #[cfg(feature = "webgpu")]
-            Backend::WebGPU => true,
+            Backend::WebGPU => Ok(()),
             #[cfg(not(feature = "webgpu"))]
-            Backend::WebGPU => false,
+            Backend::WebGPU => Err("built without the `webgpu` feature".to_string()),
         }
     }
 
     pub fn all() -> Vec<Backend> {
-        vec![Backend::Metal, Backend::WebGPU]
+        vec![Backend::Metal, Backend::MetalFromWgsl, Backend::WebGPU]
+    }
+
+    /// Enumerate the devices this backend can run on
+    pub fn enumerate_devices(&self) -> Vec<device::DeviceInfo> {
+        match self {
+            #[cfg(all(feature = "metal", target_os = "macos"))]
+            Backend::Metal | Backend::MetalFromWgsl => metal::MetalContext::enumerate_devices(),
+
+            #[cfg(feature = "webgpu")]
+            Backend::WebGPU => webgpu::WebGpuContext::enumerate_devices(),
+
+            #[allow(unreachable_patterns)]
+            _ => Vec::new(),
+        }
     }
 
     pub fn available() -> Vec<Backend> {
@@ -129,7 +202,7 @@ impl Operation {
         matches!(self, Operation::U64AddNative)
     }
 
-    /// Returns calibrated ops_per_thread for stable measurements
+    /// Default ops_per_thread (tuned on an Apple M3), the starting point for calibration
     pub fn calibrated_ops_per_thread(&self) -> u32 {
         match self {
             Operation::U32Add => 100_000,
@@ -161,16 +234,21 @@ impl Operation {
 
     /// Returns operations available for a specific backend
     pub fn available_for(backend: Backend) -> Vec<Operation> {
+        Self::available_with(backend.has_native_u64())
+    }
+
+    /// Returns operations available on a device with or without native u64 support
+    pub fn available_with(native_u64: bool) -> Vec<Operation> {
         Self::all()
             .into_iter()
             .filter(|op| {
                 // u64_add_native only available on backends with native u64 support
                 if op.requires_native_u64() {
-                    backend.has_native_u64()
+                    native_u64
                 }
                 // u64_add_emulated only needed for backends without native u64
                 else if op.is_emulation_only() {
-                    !backend.has_native_u64()
+                    !native_u64
                 } else {
                     true
                 }
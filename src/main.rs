@@ -1,19 +1,34 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use console::Style;
 use field_ops_benchmarks::{
-    config::BenchmarkConfig, reporter, results::BenchmarkReport, tui::InteractiveTui, Backend,
-    Operation,
+    backend::{create_backend, run_suite_with_progress, SuiteEvent},
+    calibration::CalibrationCache,
+    compare::SignificanceTest,
+    config::{AdaptiveSampling, BenchmarkConfig, BufferStrategy, InputMode, SubmitBatching},
+    device::DeviceSelector,
+    occupancy::{self, OccupancySearch},
+    reporter,
+    results::BenchmarkReport,
+    stats::Statistic,
+    stream::{self, StreamOperation},
+    suite::SuiteFile,
+    sweep::{self, SweepSpec},
+    transfer::{self, TransferMethod},
+    translate,
+    tui::InteractiveTui,
+    Backend, Operation,
 };
 use indicatif::{ProgressBar, ProgressStyle};
-
-// Embedded metallib (compiled at build time)
-#[cfg(feature = "metal")]
-const METAL_LIB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/benchmarks.metallib"));
+use std::path::Path;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "field-ops-bench")]
 #[command(about = "GPU benchmark for u256/field arithmetic operations")]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Run in batch mode (non-interactive)
     #[arg(long)]
     batch: bool,
@@ -22,7 +37,11 @@ struct Args {
     #[arg(long)]
     compare: bool,
 
-    /// Backend to use (metal, webgpu)
+    /// Run the benchmarks described by a TOML suite file, in order, into one report
+    #[arg(long, conflicts_with_all = ["batch", "compare"])]
+    suite: Option<String>,
+
+    /// Backend to use (metal, metal-wgsl, webgpu)
     #[arg(long, short = 'b')]
     backend: Option<String>,
 
@@ -34,6 +53,10 @@ struct Args {
     #[arg(long, short = 'w', default_value = "64")]
     workgroup: u32,
 
+    /// Number of workgroups to dispatch
+    #[arg(long, default_value = "1024")]
+    workgroups: u32,
+
     /// Operations per thread
     #[arg(long, default_value = "10000")]
     ops: u32,
@@ -49,12 +72,201 @@ struct Args {
     /// Run full benchmark (10000 ops, 100 iterations) - takes much longer
     #[arg(long)]
     full: bool,
+
+    /// Adapter to run on, by index or name (see `list-devices`)
+    #[arg(long, short = 'a')]
+    adapter: Option<String>,
+
+    /// Run the benchmarks on every adapter of each selected backend
+    #[arg(long, conflicts_with = "adapter")]
+    all_adapters: bool,
+
+    /// Run on the software fallback adapter (llvmpipe/lavapipe/WARP); results are labelled CPU
+    #[arg(long, conflicts_with_all = ["adapter", "all_adapters"])]
+    software: bool,
+
+    /// Show ratios between software (CPU) and hardware results in comparisons
+    #[arg(long)]
+    allow_software_ratios: bool,
+
+    /// Target duration of one dispatch when calibrating ops_per_thread, in milliseconds
+    #[arg(long, default_value = "20")]
+    calibration_target: u64,
+
+    /// Ignore cached ops_per_thread calibrations and measure them again
+    #[arg(long)]
+    recalibrate: bool,
+
+    /// Skip the empty-kernel dispatch overhead measurement
+    #[arg(long)]
+    no_overhead: bool,
+
+    /// Skip the shader, pipeline and first-dispatch latency measurement
+    #[arg(long)]
+    no_startup: bool,
+
+    /// Sample until the 95% GOP/s CI is within ±this percent (adaptive sampling)
+    #[arg(long, value_parser = parse_target_ci)]
+    target_ci: Option<f64>,
+
+    /// Time budget per operation for adaptive sampling, in seconds
+    #[arg(long, default_value = "10", requires = "target_ci", value_parser = parse_time_budget)]
+    time_budget: Duration,
+
+    /// Significance test for comparisons (welch, mann-whitney)
+    #[arg(long, default_value = "welch", value_parser = parse_test)]
+    test: SignificanceTest,
+
+    /// Skip the drift sentinel (u32_add) run before and after the suite
+    #[arg(long)]
+    no_drift_check: bool,
+
+    /// Pause between operations to let the device cool down, in seconds
+    #[arg(long, value_parser = parse_seconds)]
+    cooldown: Option<Duration>,
+
+    /// Timing statistic that drives GOP/s (min, median, mean)
+    #[arg(long, default_value = "min", value_parser = parse_statistic)]
+    statistic: Statistic,
+
+    /// Dispatches per submission; a list (e.g. 1,4,16) runs each in batch mode
+    #[arg(long, value_delimiter = ',', default_value = "1")]
+    dispatches_per_submit: Vec<u32>,
+
+    /// Independent accumulator chains per thread (1, 2, 4, 8); a list runs each in batch mode
+    #[arg(long, value_delimiter = ',', default_value = "1")]
+    independent_chains: Vec<u32>,
+
+    /// Record batched dispatches as separate command buffers in one submit
+    #[arg(long)]
+    separate_command_buffers: bool,
+
+    /// Kernel inputs (compute-bound: 16 shared words, dataset: a random operand pair per thread)
+    #[arg(long, default_value = "compute-bound", value_parser = parse_input_mode)]
+    input: InputMode,
+
+    /// Read back the output and check sampled threads against a CPU reference
+    #[arg(long)]
+    verify: bool,
+
+    /// Buffer choices, e.g. shared_output+storage_params; a list runs each in batch mode
+    #[arg(long, value_delimiter = ',', default_value = "default", value_parser = parse_buffer_strategy)]
+    buffer_strategy: Vec<BufferStrategy>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List all GPU adapters with their limits and features
+    ListDevices,
+
+    /// Translate the WGSL benchmark and streaming kernels to MSL, SPIR-V and HLSL with naga
+    ExportShaders {
+        /// Directory to write the translated shaders to
+        #[arg(long, default_value = "exported_shaders")]
+        dir: String,
+    },
+
+    /// Compare two JSON reports (e.g. before/after runs) with significance tests
+    CompareReports {
+        /// First report (numerator of the ratio)
+        first: String,
+
+        /// Second report
+        second: String,
+    },
+
+    /// Double the workgroup count until throughput plateaus, reporting the knee per operation
+    Occupancy {
+        /// First number of workgroups
+        #[arg(long, default_value = "1")]
+        start_workgroups: u32,
+
+        /// Largest number of workgroups to try
+        #[arg(long, default_value = "65536")]
+        max_workgroups: u32,
+
+        /// Gain (percent) below which a doubling counts as saturated
+        #[arg(long, default_value = "5")]
+        tolerance: f64,
+    },
+
+    /// Run every combination of workgroup size, workgroup count and ops per thread
+    ///
+    /// Each range is a list (64,128,256), a doubling range (64..1024) or a mix.
+    Sweep {
+        /// Workgroup sizes to sweep
+        #[arg(long, default_value = "64..256")]
+        workgroup_sizes: String,
+
+        /// Workgroup counts to sweep
+        #[arg(long, default_value = "256..4096")]
+        num_workgroups: String,
+
+        /// Ops per thread to sweep (calibrated per combination if omitted)
+        #[arg(long)]
+        ops_per_thread: Option<String>,
+    },
+
+    /// Measure GB/s of element-wise field streams over large buffers against a plain copy
+    Stream {
+        /// Buffer sizes in MB, as a list (1,4,16) or a doubling range (1..256)
+        #[arg(long, default_value = "1,4,16,64,256")]
+        sizes: String,
+
+        /// Streams to run (copy, m31_vec_add, m31_vec_mul, bn254_vec_add, bn254_vec_mul); all if omitted
+        #[arg(long, value_delimiter = ',')]
+        streams: Option<Vec<String>>,
+    },
+
+    /// Measure upload and download latency and bandwidth of each host-device transfer path
+    Transfer {
+        /// Transfer sizes in KB, as a list (4,64,1024) or a doubling range (4..262144)
+        #[arg(long, default_value = "4,64,1024,16384,262144")]
+        sizes: String,
+
+        /// Methods to run (e.g. write_buffer, staging_readback, private_blit_upload); all of the backend's if omitted
+        #[arg(long, value_delimiter = ',')]
+        methods: Option<Vec<String>>,
+    },
 }
 
 fn main() {
     let args = Args::parse();
 
-    if args.compare {
+    if let Some(command) = &args.command {
+        match command {
+            Command::ListDevices => run_list_devices(),
+            Command::ExportShaders { dir } => run_export_shaders(dir),
+            Command::CompareReports { first, second } => run_compare_reports(first, second, &args),
+            Command::Occupancy {
+                start_workgroups,
+                max_workgroups,
+                tolerance,
+            } => run_occupancy(
+                OccupancySearch {
+                    start_workgroups: *start_workgroups,
+                    max_workgroups: *max_workgroups,
+                    tolerance: tolerance / 100.0,
+                    ..OccupancySearch::default()
+                },
+                &args,
+            ),
+            Command::Sweep {
+                workgroup_sizes,
+                num_workgroups,
+                ops_per_thread,
+            } => run_sweep(
+                workgroup_sizes,
+                num_workgroups,
+                ops_per_thread.as_deref(),
+                &args,
+            ),
+            Command::Stream { sizes, streams } => run_stream(sizes, streams.as_deref(), &args),
+            Command::Transfer { sizes, methods } => run_transfer(sizes, methods.as_deref(), &args),
+        }
+    } else if let Some(path) = &args.suite {
+        run_suite_file(path, &args);
+    } else if args.compare {
         run_comparison_mode(args);
     } else if args.batch {
         run_batch_mode(args);
@@ -67,6 +279,13 @@ fn run_interactive_mode() {
     let tui = InteractiveTui::new();
 
     if let Some(selection) = tui.quick_run() {
+        let config = selection
+            .config
+            .clone()
+            .with_calibration_cache(CalibrationCache::default_path())
+            .with_overhead_calibration(true)
+            .with_startup_latency(true)
+            .with_drift_sentinel(Some(Operation::U32Add));
         let mut all_reports: Vec<BenchmarkReport> = Vec::new();
 
         for backend in &selection.backends {
@@ -83,10 +302,10 @@ fn run_interactive_mode() {
                 "Operations: {:?}",
                 backend_ops.iter().map(|o| o.name()).collect::<Vec<_>>()
             );
-            println!("Workgroup size: {}", selection.config.workgroup_size);
+            println!("Workgroup size: {}", config.workgroup_size);
             println!(
                 "Auto-calibrate: {}",
-                if selection.config.auto_calibrate {
+                if config.auto_calibrate {
                     "enabled"
                 } else {
                     "disabled"
@@ -95,7 +314,7 @@ fn run_interactive_mode() {
             println!();
 
             // Run benchmarks
-            let report = run_benchmarks(*backend, &backend_ops, &selection.config);
+            let report = run_benchmarks(*backend, &backend_ops, &config, &DeviceSelector::Default);
 
             // Print results
             reporter::print_results(&report);
@@ -104,7 +323,7 @@ fn run_interactive_mode() {
 
         // Print comparison if multiple backends
         if all_reports.len() > 1 {
-            reporter::print_comparison(&all_reports);
+            reporter::print_comparison(&all_reports, false, SignificanceTest::default());
         }
 
         // Ask to save
@@ -121,13 +340,21 @@ fn run_interactive_mode() {
 
 fn run_batch_mode(args: Args) {
     let backend = match args.backend.as_deref() {
-        Some("metal") => Backend::Metal,
-        Some("webgpu") => Backend::WebGPU,
-        Some(other) => {
-            eprintln!("Unknown backend: {}", other);
-            eprintln!("Available: metal, webgpu");
-            return;
-        }
+        Some(name) => match Backend::from_cli_name(name) {
+            Some(b) => b,
+            None => {
+                eprintln!("Unknown backend: {}", name);
+                eprintln!(
+                    "Available: {}",
+                    Backend::all()
+                        .iter()
+                        .map(|b| b.cli_name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                return;
+            }
+        },
         None => {
             // Use first available backend
             if let Some(b) = Backend::available().first() {
@@ -139,8 +366,12 @@ fn run_batch_mode(args: Args) {
         }
     };
 
-    if !backend.is_available() {
-        eprintln!("Backend {} is not available on this system", backend.name());
+    if let Err(reason) = backend.availability() {
+        eprintln!(
+            "Backend {} is not available on this system: {}",
+            backend.name(),
+            reason
+        );
         return;
     }
 
@@ -164,10 +395,87 @@ fn run_batch_mode(args: Args) {
         }
     };
 
+    let config = build_config(&args);
+
+    // One suite per buffer strategy, chain count and batch size, compared across them afterwards
+    let mut swept_reports = Vec::new();
+    for &strategy in &args.buffer_strategy {
+        if !strategy.applies_to(backend) {
+            println!(
+                "{}",
+                Style::new().dim().apply_to(format!(
+                    "Skipping buffer strategy {}: it does not apply to {}",
+                    strategy.name(),
+                    backend.name()
+                ))
+            );
+            continue;
+        }
+        for &chains in &args.independent_chains {
+            for &count in &args.dispatches_per_submit {
+                let config = config
+                    .clone()
+                    .with_buffer_strategy(strategy)
+                    .with_independent_chains(chains)
+                    .with_dispatches_per_submit(count, submit_batching(&args));
+                let reports = run_on_devices(backend, &operations, &config, &args);
+
+                for report in &reports {
+                    reporter::print_results(report);
+                }
+
+                if reports.len() > 1 {
+                    reporter::print_comparison(&reports, args.allow_software_ratios, args.test);
+                }
+                swept_reports.extend(reports);
+            }
+        }
+    }
+    let reports = swept_reports;
+
+    if args.independent_chains.len() > 1 {
+        reporter::print_chains(&reports);
+    }
+    if args.dispatches_per_submit.len() > 1 {
+        reporter::print_batching(&reports);
+    }
+    if args.buffer_strategy.len() > 1 {
+        reporter::print_buffer_strategies(&reports);
+    }
+
+    if let Some(output) = args.output {
+        let report = match reports.as_slice() {
+            [single] => single.clone(),
+            _ => reporter::merge_reports(&reports),
+        };
+        if output.ends_with(".csv") {
+            if let Err(e) = reporter::export_csv(&report, &output) {
+                eprintln!("Failed to save CSV: {}", e);
+            }
+        } else {
+            if let Err(e) = reporter::export_json(&report, &output) {
+                eprintln!("Failed to save JSON: {}", e);
+            }
+        }
+    }
+}
+
+/// How batched dispatches are recorded, from the command-line flags
+fn submit_batching(args: &Args) -> SubmitBatching {
+    if args.separate_command_buffers {
+        SubmitBatching::CommandBuffers
+    } else {
+        SubmitBatching::SinglePass
+    }
+}
+
+/// Build the benchmark config from the command-line flags
+fn build_config(args: &Args) -> BenchmarkConfig {
     let config = if args.full {
         // Full benchmark mode: high ops, many iterations, no auto-calibrate
         BenchmarkConfig::default()
             .with_workgroup_size(args.workgroup)
+            .with_num_workgroups(args.workgroups)
             .with_ops_per_thread(10_000)
             .with_iterations(100)
             .with_auto_calibrate(false)
@@ -175,27 +483,89 @@ fn run_batch_mode(args: Args) {
         // Default: use auto-calibration for fast benchmarks
         BenchmarkConfig::default()
             .with_workgroup_size(args.workgroup)
+            .with_num_workgroups(args.workgroups)
             .with_ops_per_thread(args.ops)
             .with_iterations(args.iterations)
     };
 
-    let report = run_benchmarks(backend, &operations, &config);
+    let config = config
+        .with_calibration_target(Duration::from_millis(args.calibration_target))
+        .with_calibration_cache(CalibrationCache::default_path())
+        .with_recalibrate(args.recalibrate)
+        .with_overhead_calibration(!args.no_overhead)
+        .with_startup_latency(!args.no_startup)
+        .with_drift_sentinel((!args.no_drift_check).then_some(Operation::U32Add))
+        .with_cooldown(args.cooldown)
+        .with_statistic(args.statistic)
+        .with_dispatches_per_submit(
+            args.dispatches_per_submit.first().copied().unwrap_or(1),
+            submit_batching(args),
+        )
+        .with_independent_chains(args.independent_chains.first().copied().unwrap_or(1))
+        .with_input_mode(args.input)
+        .with_verification(args.verify)
+        .with_buffer_strategy(args.buffer_strategy.first().copied().unwrap_or_default());
+
+    match args.target_ci {
+        Some(percent) => config.with_adaptive_sampling(AdaptiveSampling {
+            target_relative_ci: percent / 100.0,
+            time_budget: args.time_budget,
+            ..AdaptiveSampling::default()
+        }),
+        None => config,
+    }
+}
 
-    reporter::print_results(&report);
+fn parse_test(name: &str) -> Result<SignificanceTest, String> {
+    SignificanceTest::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = SignificanceTest::all().iter().map(|t| t.name()).collect();
+        format!("expected one of: {}", names.join(", "))
+    })
+}
 
-    if let Some(output) = args.output {
-        if output.ends_with(".csv") {
-            if let Err(e) = reporter::export_csv(&report, &output) {
-                eprintln!("Failed to save CSV: {}", e);
-            }
-        } else {
-            if let Err(e) = reporter::export_json(&report, &output) {
-                eprintln!("Failed to save JSON: {}", e);
-            }
-        }
+fn parse_buffer_strategy(name: &str) -> Result<BufferStrategy, String> {
+    BufferStrategy::from_name(name)
+}
+
+fn parse_input_mode(name: &str) -> Result<InputMode, String> {
+    InputMode::from_name(name).ok_or_else(|| {
+        let names: Vec<String> = InputMode::all()
+            .iter()
+            .map(|m| m.name().replace('_', "-"))
+            .collect();
+        format!("expected one of: {}", names.join(", "))
+    })
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value.parse().map_err(|e| format!("{}", e))?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| "expected a finite, non-negative number of seconds".to_string())
+}
+
+fn parse_time_budget(value: &str) -> Result<Duration, String> {
+    parse_seconds(value)
+        .ok()
+        .filter(|budget| !budget.is_zero())
+        .ok_or_else(|| "expected a positive, finite number of seconds".to_string())
+}
+
+fn parse_target_ci(value: &str) -> Result<f64, String> {
+    let percent: f64 = value.parse().map_err(|e| format!("{}", e))?;
+    if percent.is_finite() && percent > 0.0 {
+        Ok(percent)
+    } else {
+        Err("expected a positive percentage".to_string())
     }
 }
 
+fn parse_statistic(name: &str) -> Result<Statistic, String> {
+    Statistic::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Statistic::all().iter().map(|s| s.name()).collect();
+        format!("expected one of: {}", names.join(", "))
+    })
+}
+
 fn run_comparison_mode(args: Args) {
     let header_style = Style::new().bold().cyan();
 
@@ -212,20 +582,7 @@ fn run_comparison_mode(args: Args) {
         return;
     }
 
-    let config = if args.full {
-        // Full benchmark mode: high ops, many iterations, no auto-calibrate
-        BenchmarkConfig::default()
-            .with_workgroup_size(args.workgroup)
-            .with_ops_per_thread(10_000)
-            .with_iterations(100)
-            .with_auto_calibrate(false)
-    } else {
-        // Default: use auto-calibration for fast benchmarks
-        BenchmarkConfig::default()
-            .with_workgroup_size(args.workgroup)
-            .with_ops_per_thread(args.ops)
-            .with_iterations(args.iterations)
-    };
+    let config = build_config(&args);
 
     let mut all_reports: Vec<BenchmarkReport> = Vec::new();
 
@@ -256,14 +613,15 @@ fn run_comparison_mode(args: Args) {
             }
         };
 
-        let report = run_benchmarks(*backend, &operations, &config);
-        reporter::print_results(&report);
-        all_reports.push(report);
+        for report in run_on_devices(*backend, &operations, &config, &args) {
+            reporter::print_results(&report);
+            all_reports.push(report);
+        }
     }
 
     // Print comparison summary
     if all_reports.len() > 1 {
-        reporter::print_comparison(&all_reports);
+        reporter::print_comparison(&all_reports, args.allow_software_ratios, args.test);
     }
 
     // Export combined results if requested
@@ -285,136 +643,732 @@ fn run_comparison_mode(args: Args) {
     }
 }
 
-fn run_benchmarks(
-    backend: Backend,
-    operations: &[Operation],
-    config: &BenchmarkConfig,
-) -> BenchmarkReport {
-    match backend {
-        #[cfg(feature = "metal")]
-        Backend::Metal => run_metal_benchmarks(operations, config),
+fn run_list_devices() {
+    let header_style = Style::new().bold().cyan();
 
-        #[cfg(feature = "webgpu")]
-        Backend::WebGPU => run_webgpu_benchmarks(operations, config),
+    // Metal-WGSL runs on the same devices as Metal
+    for backend in Backend::available()
+        .into_iter()
+        .filter(|b| *b != Backend::MetalFromWgsl)
+    {
+        println!();
+        println!(
+            "{}",
+            header_style.apply_to(format!("=== {} Devices ===", backend.name()))
+        );
+        println!();
 
-        #[allow(unreachable_patterns)]
-        _ => {
-            eprintln!("Backend {} not compiled in", backend.name());
-            BenchmarkReport::new("Unknown".to_string(), "Unknown".to_string())
+        let devices = backend.enumerate_devices();
+        if devices.is_empty() {
+            println!("  No devices found");
+            println!();
         }
+        reporter::print_devices(&devices);
     }
 }
 
-#[cfg(feature = "metal")]
-fn run_metal_benchmarks(operations: &[Operation], config: &BenchmarkConfig) -> BenchmarkReport {
-    use field_ops_benchmarks::metal::MetalRunner;
+fn run_compare_reports(first: &str, second: &str, args: &Args) {
+    let mut reports = Vec::new();
+    for path in [first, second] {
+        match reporter::load_json(path) {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                eprintln!("Failed to load {}: {}", path, e);
+                return;
+            }
+        }
+    }
+
+    reporter::print_comparison(&reports, args.allow_software_ratios, args.test);
+}
 
+fn run_suite_file(path: &str, args: &Args) {
     let error_style = Style::new().red();
+    let header_style = Style::new().bold().cyan();
 
-    // Create Metal runner
-    let mut runner = match MetalRunner::new() {
-        Ok(r) => r,
+    let suite = match SuiteFile::load(Path::new(path)) {
+        Ok(suite) => suite,
         Err(e) => {
-            eprintln!(
+            eprintln!("{}", error_style.apply_to(e));
+            return;
+        }
+    };
+    let base = build_config(args);
+
+    let mut all_reports: Vec<BenchmarkReport> = Vec::new();
+    for entry in &suite.benchmarks {
+        let config = entry.config(&base);
+        let mut entry_reports = Vec::new();
+
+        for backend in entry.backends() {
+            if let Err(reason) = backend.availability() {
+                eprintln!(
+                    "{}",
+                    error_style.apply_to(format!(
+                        "{}: backend {} is not available: {}",
+                        entry.name,
+                        backend.name(),
+                        reason
+                    ))
+                );
+                continue;
+            }
+
+            println!();
+            println!(
                 "{}",
-                error_style.apply_to(format!("Failed to create Metal runner: {}", e))
+                header_style.apply_to(format!("=== {} ({}) ===", entry.name, backend.name()))
             );
-            return BenchmarkReport::new("Unknown".to_string(), "Metal".to_string());
+
+            let operations = entry.operations(backend);
+            for mut report in run_on_devices(backend, &operations, &config, args) {
+                report.results = report
+                    .results
+                    .into_iter()
+                    .map(|r| r.with_suite_entry(&entry.name))
+                    .collect();
+                reporter::print_results(&report);
+                entry_reports.push(report);
+            }
         }
-    };
 
-    let device_name = runner.device_name();
-    println!("Device: {}", device_name);
+        // Compare backends/devices within an entry, never across entries
+        if entry_reports.len() > 1 {
+            reporter::print_comparison(&entry_reports, args.allow_software_ratios, args.test);
+        }
+        if let Some(output) = &entry.output {
+            save_reports(&entry_reports, output, &entry.name);
+        }
+        all_reports.extend(entry_reports);
+    }
+
+    // --output overrides the suite's own output path
+    if let Some(output) = args.output.as_ref().or(suite.output.as_ref()) {
+        save_reports(&all_reports, output, path);
+    }
+}
 
-    // Load the embedded metallib
-    if let Err(e) = runner.load_library_data(METAL_LIB) {
+/// Merge and save `reports`, warning instead of writing an empty report when nothing ran
+fn save_reports(reports: &[BenchmarkReport], output: &str, label: &str) {
+    if reports.is_empty() {
         eprintln!(
             "{}",
-            error_style.apply_to(format!("Failed to load Metal library: {}", e))
+            Style::new()
+                .yellow()
+                .apply_to(format!("{}: no backend ran, not saving {}", label, output))
         );
-        return BenchmarkReport::new(device_name, "Metal".to_string());
+        return;
     }
+    save_report(&reporter::merge_reports(reports), output);
+}
 
-    let mut report = BenchmarkReport::new(device_name, "Metal".to_string());
-
-    // Run each benchmark with spinner
-    for op in operations {
-        // Create spinner for each operation
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg} [{elapsed_precise}]")
-                .unwrap(),
-        );
-        spinner.set_message(format!("Running {}...", op.name()));
-        spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+/// Write a report as CSV (`.csv`) or JSON
+fn save_report(report: &BenchmarkReport, output: &str) {
+    let saved = if output.ends_with(".csv") {
+        reporter::export_csv(report, output)
+    } else {
+        reporter::export_json(report, output)
+    };
+    match saved {
+        Ok(()) => println!("Results saved to {}", output),
+        Err(e) => eprintln!("Failed to save results: {}", e),
+    }
+}
 
-        // Get operation-specific config
-        let op_config = config.for_operation(*op);
+fn run_occupancy(search: OccupancySearch, args: &Args) {
+    let error_style = Style::new().red();
+    let header_style = Style::new().bold().cyan();
+    let info_style = Style::new().dim();
 
-        match runner.run_benchmark(*op, &op_config) {
-            Ok(result) => {
-                let time_ms = result.min_ns as f64 / 1_000_000.0;
-                spinner.finish_with_message(format!("✓ {} ({:.2}ms)", op.name(), time_ms));
-                report.add_result(result);
+    let backends = match args.backend.as_deref() {
+        Some(name) => match Backend::from_cli_name(name) {
+            Some(b) => vec![b],
+            None => {
+                eprintln!("Unknown backend: {}", name);
+                return;
             }
-            Err(e) => {
-                spinner.finish_with_message(format!("✗ {} failed: {}", op.name(), e));
+        },
+        None => Backend::available(),
+    };
+    let config = build_config(args);
+
+    let mut reports: Vec<BenchmarkReport> = Vec::new();
+    for backend in backends {
+        for selector in device_selectors(backend, args) {
+            let mut runner = match create_backend(backend, &selector) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        error_style.apply_to(format!(
+                            "Failed to create {} runner: {}",
+                            backend.name(),
+                            e
+                        ))
+                    );
+                    continue;
+                }
+            };
+
+            println!();
+            println!(
+                "{}",
+                header_style.apply_to(format!(
+                    "=== {} Backend: {} ===",
+                    backend.name(),
+                    runner.device_name()
+                ))
+            );
+
+            let mut report = BenchmarkReport::new(runner.device_name(), backend.name().to_string());
+            report.device_type = Some(runner.device_type());
+
+            for op in runner.supported_operations().into_iter().filter(|op| {
+                matches!(args.op.as_deref(), Some("all") | None)
+                    || args.op.as_deref() == Some(op.name())
+            }) {
+                let op_config = config.for_operation(op);
+                let found =
+                    occupancy::find_knee(runner.as_mut(), op, &op_config, &search, &mut |r| {
+                        println!(
+                            "{}",
+                            info_style.apply_to(format!(
+                                "  {} x{} workgroups: {:.3} GOP/s",
+                                r.operation,
+                                r.num_workgroups(),
+                                r.gops_per_second
+                            ))
+                        );
+                    });
+
+                match found {
+                    Ok((results, knee)) => {
+                        for result in results {
+                            report.add_result(result);
+                        }
+                        report.occupancy.extend(knee);
+                    }
+                    Err(e) => eprintln!(
+                        "{}",
+                        error_style.apply_to(format!("✗ {} failed: {}", op.name(), e))
+                    ),
+                }
             }
+
+            reporter::print_occupancy(&report);
+            reports.push(report);
         }
     }
 
-    report
+    if let Some(output) = &args.output {
+        save_report(&reporter::merge_reports(&reports), output);
+    }
 }
 
-#[cfg(feature = "webgpu")]
-fn run_webgpu_benchmarks(operations: &[Operation], config: &BenchmarkConfig) -> BenchmarkReport {
-    use field_ops_benchmarks::webgpu::WebGpuRunner;
+/// Resolve the `--streams` names, always keeping `copy` as the baseline
+fn parse_streams(names: Option<&[String]>) -> Result<Vec<StreamOperation>, String> {
+    let Some(names) = names else {
+        return Ok(StreamOperation::all());
+    };
+    let mut streams = vec![StreamOperation::Copy];
+    for name in names {
+        let op = StreamOperation::from_name(name.trim())
+            .ok_or_else(|| format!("--streams: unknown stream '{}'", name))?;
+        if !streams.contains(&op) {
+            streams.push(op);
+        }
+    }
+    Ok(streams)
+}
 
+fn run_stream(sizes: &str, streams: Option<&[String]>, args: &Args) {
     let error_style = Style::new().red();
+    let header_style = Style::new().bold().cyan();
+    let info_style = Style::new().dim();
 
-    // Create WebGPU runner
-    let runner = match WebGpuRunner::new() {
-        Ok(r) => r,
+    let sizes = match sweep::parse_values(sizes) {
+        Ok(sizes) => sizes,
         Err(e) => {
-            eprintln!(
+            eprintln!("{}", error_style.apply_to(format!("--sizes: {}", e)));
+            return;
+        }
+    };
+    let streams = match parse_streams(streams) {
+        Ok(streams) => streams,
+        Err(e) => {
+            eprintln!("{}", error_style.apply_to(e));
+            return;
+        }
+    };
+
+    let backends = match args.backend.as_deref() {
+        Some(name) => match Backend::from_cli_name(name) {
+            Some(b) => vec![b],
+            None => {
+                eprintln!("Unknown backend: {}", name);
+                return;
+            }
+        },
+        None => Backend::available(),
+    };
+    let config = build_config(args);
+
+    let mut reports: Vec<BenchmarkReport> = Vec::new();
+    for backend in backends {
+        for selector in device_selectors(backend, args) {
+            let mut runner = match create_backend(backend, &selector) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        error_style.apply_to(format!(
+                            "Failed to create {} runner: {}",
+                            backend.name(),
+                            e
+                        ))
+                    );
+                    continue;
+                }
+            };
+
+            println!();
+            println!(
                 "{}",
-                error_style.apply_to(format!("Failed to create WebGPU runner: {}", e))
+                header_style.apply_to(format!(
+                    "=== {} Backend: {} ===",
+                    backend.name(),
+                    runner.device_name()
+                ))
             );
-            return BenchmarkReport::new("Unknown".to_string(), "WebGPU".to_string());
+
+            let mut report = BenchmarkReport::new(runner.device_name(), backend.name().to_string());
+            report.device_type = Some(runner.device_type());
+
+            for &op in &streams {
+                for &size in &sizes {
+                    match stream::measure(runner.as_mut(), op, size as u64 * stream::MB, &config) {
+                        Ok(result) => {
+                            println!(
+                                "{}",
+                                info_style.apply_to(format!(
+                                    "  {} {} MB: {:.2} GB/s",
+                                    result.operation, size, result.gb_per_second
+                                ))
+                            );
+                            report.streams.push(result);
+                        }
+                        Err(e) => eprintln!(
+                            "{}",
+                            error_style.apply_to(format!("✗ {} {} MB failed: {}", op, size, e))
+                        ),
+                    }
+                }
+            }
+
+            stream::relate_to_copy(&mut report.streams);
+            reporter::print_streams(&report);
+            reports.push(report);
+        }
+    }
+
+    if let Some(output) = &args.output {
+        save_report(&reporter::merge_reports(&reports), output);
+    }
+}
+
+fn run_transfer(sizes: &str, methods: Option<&[String]>, args: &Args) {
+    let error_style = Style::new().red();
+    let header_style = Style::new().bold().cyan();
+    let info_style = Style::new().dim();
+
+    let sizes = match sweep::parse_values(sizes) {
+        Ok(sizes) => sizes,
+        Err(e) => {
+            eprintln!("{}", error_style.apply_to(format!("--sizes: {}", e)));
+            return;
         }
     };
+    let selected = match methods {
+        Some(names) => {
+            let mut selected = Vec::new();
+            for name in names {
+                match TransferMethod::from_name(name.trim()) {
+                    Some(method) => selected.push(method),
+                    None => {
+                        eprintln!(
+                            "{}",
+                            error_style.apply_to(format!("--methods: unknown method '{}'", name))
+                        );
+                        return;
+                    }
+                }
+            }
+            Some(selected)
+        }
+        None => None,
+    };
 
-    let device_name = runner.device_name();
-    println!("Device: {}", device_name);
+    let backends = match args.backend.as_deref() {
+        Some(name) => match Backend::from_cli_name(name) {
+            Some(b) => vec![b],
+            None => {
+                eprintln!("Unknown backend: {}", name);
+                return;
+            }
+        },
+        None => Backend::available(),
+    };
+    let config = build_config(args);
 
-    let mut report = BenchmarkReport::new(device_name, "WebGPU".to_string());
+    let mut reports: Vec<BenchmarkReport> = Vec::new();
+    for backend in backends {
+        let methods: Vec<TransferMethod> = TransferMethod::for_backend(backend)
+            .into_iter()
+            .filter(|m| selected.as_ref().is_none_or(|s| s.contains(m)))
+            .collect();
+        if methods.is_empty() {
+            println!(
+                "{}",
+                info_style.apply_to(format!(
+                    "Skipping {}: none of the selected methods belong to it",
+                    backend.name()
+                ))
+            );
+            continue;
+        }
 
-    // Run each benchmark with spinner
-    for op in operations {
-        // Create spinner for each operation
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg} [{elapsed_precise}]")
-                .unwrap(),
+        for selector in device_selectors(backend, args) {
+            let mut runner = match create_backend(backend, &selector) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        error_style.apply_to(format!(
+                            "Failed to create {} runner: {}",
+                            backend.name(),
+                            e
+                        ))
+                    );
+                    continue;
+                }
+            };
+
+            println!();
+            println!(
+                "{}",
+                header_style.apply_to(format!(
+                    "=== {} Backend: {} ===",
+                    backend.name(),
+                    runner.device_name()
+                ))
+            );
+
+            let mut report = BenchmarkReport::new(runner.device_name(), backend.name().to_string());
+            report.device_type = Some(runner.device_type());
+
+            for &method in &methods {
+                for &size in &sizes {
+                    match transfer::measure(
+                        runner.as_mut(),
+                        method,
+                        size as u64 * transfer::KB,
+                        &config,
+                    ) {
+                        Ok(result) => {
+                            println!(
+                                "{}",
+                                info_style.apply_to(format!(
+                                    "  {} {} KB: {:.1} µs, {:.2} GB/s",
+                                    result.method,
+                                    size,
+                                    result.time_ns / 1_000.0,
+                                    result.gb_per_second
+                                ))
+                            );
+                            report.transfers.push(result);
+                        }
+                        Err(e) => eprintln!(
+                            "{}",
+                            error_style.apply_to(format!("✗ {} {} KB failed: {}", method, size, e))
+                        ),
+                    }
+                }
+            }
+
+            reporter::print_transfers(&report);
+            reports.push(report);
+        }
+    }
+
+    if let Some(output) = &args.output {
+        save_report(&reporter::merge_reports(&reports), output);
+    }
+}
+
+/// Parse the `sweep` ranges, naming the offending flag on error
+fn parse_sweep_spec(
+    workgroup_sizes: &str,
+    num_workgroups: &str,
+    ops_per_thread: Option<&str>,
+) -> Result<SweepSpec, String> {
+    let parse = |name: &str, values: &str| {
+        sweep::parse_values(values).map_err(|e| format!("--{}: {}", name, e))
+    };
+    Ok(SweepSpec {
+        workgroup_sizes: parse("workgroup-sizes", workgroup_sizes)?,
+        num_workgroups: parse("num-workgroups", num_workgroups)?,
+        ops_per_thread: ops_per_thread
+            .map(|values| parse("ops-per-thread", values))
+            .transpose()?,
+    })
+}
+
+fn run_sweep(
+    workgroup_sizes: &str,
+    num_workgroups: &str,
+    ops_per_thread: Option<&str>,
+    args: &Args,
+) {
+    let error_style = Style::new().red();
+    let header_style = Style::new().bold().cyan();
+    let info_style = Style::new().dim();
+
+    let spec = match parse_sweep_spec(workgroup_sizes, num_workgroups, ops_per_thread) {
+        Ok(spec) => spec,
+        Err(e) => {
+            eprintln!("{}", error_style.apply_to(e));
+            return;
+        }
+    };
+
+    let backends = match args.backend.as_deref() {
+        Some(name) => match Backend::from_cli_name(name) {
+            Some(b) => vec![b],
+            None => {
+                eprintln!("Unknown backend: {}", name);
+                return;
+            }
+        },
+        None => Backend::available(),
+    };
+
+    // Startup latency and drift are per-suite measurements; a sweep runs many short suites
+    let base = build_config(args)
+        .with_startup_latency(false)
+        .with_drift_sentinel(None);
+
+    let mut reports: Vec<BenchmarkReport> = Vec::new();
+    for backend in backends {
+        if let Err(reason) = backend.availability() {
+            eprintln!(
+                "Backend {} is not available on this system: {}",
+                backend.name(),
+                reason
+            );
+            continue;
+        }
+
+        let operations: Vec<Operation> = Operation::available_for(backend)
+            .into_iter()
+            .filter(|op| {
+                matches!(args.op.as_deref(), Some("all") | None)
+                    || args.op.as_deref() == Some(op.name())
+            })
+            .collect();
+        if operations.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!(
+            "{}",
+            header_style.apply_to(format!(
+                "=== {} Backend: {} combinations ===",
+                backend.name(),
+                spec.len()
+            ))
         );
-        spinner.set_message(format!("Running {}...", op.name()));
-        spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
-        // Get operation-specific config
-        let op_config = config.for_operation(*op);
+        let mut backend_reports = Vec::new();
+        for config in spec.configs(&base) {
+            println!(
+                "{}",
+                info_style.apply_to(format!(
+                    "  workgroup_size={} num_workgroups={} ops_per_thread={}",
+                    config.workgroup_size,
+                    config.num_workgroups,
+                    if config.auto_calibrate {
+                        "calibrated".to_string()
+                    } else {
+                        config.ops_per_thread.to_string()
+                    }
+                ))
+            );
+            backend_reports.extend(run_on_devices(backend, &operations, &config, args));
+        }
+
+        reporter::print_results(&reporter::merge_reports(&backend_reports));
+        reports.extend(backend_reports);
+    }
+
+    if reports.iter().all(|r| r.results.is_empty()) {
+        eprintln!("{}", error_style.apply_to("No sweep results"));
+        return;
+    }
+
+    reporter::print_sweep_summary(&reports);
 
-        match runner.run_benchmark(*op, &op_config) {
-            Ok(result) => {
-                let time_ms = result.min_ns as f64 / 1_000_000.0;
-                spinner.finish_with_message(format!("✓ {} ({:.2}ms)", op.name(), time_ms));
-                report.add_result(result);
+    if let Some(output) = &args.output {
+        save_report(&reporter::merge_reports(&reports), output);
+    }
+}
+
+fn run_export_shaders(dir: &str) {
+    match translate::export_shaders(Path::new(dir)) {
+        Ok(paths) => {
+            for path in &paths {
+                println!("  {}", path.display());
             }
-            Err(e) => {
-                spinner.finish_with_message(format!("✗ {} failed: {}", op.name(), e));
+            println!("Exported {} shaders to {}", paths.len(), dir);
+        }
+        Err(e) => {
+            eprintln!(
+                "{}",
+                Style::new()
+                    .red()
+                    .apply_to(format!("Shader export failed: {}", e))
+            );
+        }
+    }
+}
+
+/// Run the operations on the device(s) selected by `--adapter` / `--all-adapters`
+fn run_on_devices(
+    backend: Backend,
+    operations: &[Operation],
+    config: &BenchmarkConfig,
+    args: &Args,
+) -> Vec<BenchmarkReport> {
+    if args.all_adapters {
+        backend
+            .enumerate_devices()
+            .iter()
+            .map(|device| {
+                println!();
+                println!(
+                    "--- {} #{}: {} ---",
+                    backend.name(),
+                    device.index,
+                    device.name
+                );
+                run_benchmarks(
+                    backend,
+                    operations,
+                    config,
+                    &DeviceSelector::Index(device.index),
+                )
+            })
+            .collect()
+    } else {
+        vec![run_benchmarks(
+            backend,
+            operations,
+            config,
+            &device_selector(args),
+        )]
+    }
+}
+
+/// Every adapter of `backend` with `--all-adapters`, otherwise the one [selected](device_selector)
+fn device_selectors(backend: Backend, args: &Args) -> Vec<DeviceSelector> {
+    if args.all_adapters {
+        backend
+            .enumerate_devices()
+            .iter()
+            .map(|device| DeviceSelector::Index(device.index))
+            .collect()
+    } else {
+        vec![device_selector(args)]
+    }
+}
+
+/// The device selected by `--software` / `--adapter`
+fn device_selector(args: &Args) -> DeviceSelector {
+    if args.software {
+        DeviceSelector::Software
+    } else {
+        args.adapter
+            .as_deref()
+            .map(DeviceSelector::parse)
+            .unwrap_or_default()
+    }
+}
+
+fn run_benchmarks(
+    backend: Backend,
+    operations: &[Operation],
+    config: &BenchmarkConfig,
+    selector: &DeviceSelector,
+) -> BenchmarkReport {
+    let error_style = Style::new().red();
+
+    // Create the backend runner
+    let mut runner = match create_backend(backend, selector) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                error_style.apply_to(format!("Failed to create {} runner: {}", backend.name(), e))
+            );
+            return BenchmarkReport::new("Unknown".to_string(), backend.name().to_string());
+        }
+    };
+
+    println!("Device: {}", runner.device_name());
+
+    // Run each benchmark with spinner
+    let mut spinner: Option<ProgressBar> = None;
+    let mut report = run_suite_with_progress(runner.as_mut(), operations, config, &mut |event| {
+        match event {
+            SuiteEvent::Started(op) => {
+                // Create spinner for each operation
+                let bar = ProgressBar::new_spinner();
+                bar.set_style(
+                    ProgressStyle::default_spinner()
+                        .template("{spinner:.green} {msg} [{elapsed_precise}]")
+                        .unwrap(),
+                );
+                bar.set_message(format!("Running {}...", op.name()));
+                bar.enable_steady_tick(std::time::Duration::from_millis(100));
+                spinner = Some(bar);
+            }
+            SuiteEvent::Finished(op, result) => {
+                if let Some(bar) = spinner.take() {
+                    let time_ms = result.min_ns as f64 / 1_000_000.0;
+                    bar.finish_with_message(format!("✓ {} ({:.2}ms)", op.name(), time_ms));
+                }
+            }
+            SuiteEvent::Failed(op, e) => {
+                let message = format!("✗ {} failed: {}", op.name(), e);
+                match spinner.take() {
+                    // Spinners are hidden when stdout is not a terminal
+                    Some(bar) if !bar.is_hidden() => bar.finish_with_message(message),
+                    _ => eprintln!("{}", error_style.apply_to(message)),
+                }
+            }
+            SuiteEvent::Sentinel(op) => {
+                println!(
+                    "{}",
+                    Style::new()
+                        .dim()
+                        .apply_to(format!("  Running drift sentinel {}...", op.name()))
+                );
             }
         }
+    });
+
+    if let DeviceSelector::Index(index) = selector {
+        report.device_index = Some(*index);
     }
 
     report
@@ -427,8 +1381,6 @@ fn run_placeholder_benchmarks(
     operations: &[Operation],
     config: &BenchmarkConfig,
 ) -> BenchmarkReport {
-    use std::time::Duration;
-
     let info_style = Style::new().dim();
 
     let mut report = BenchmarkReport::new(
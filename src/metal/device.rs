@@ -1,7 +1,8 @@
 //! Metal device and queue management
 
-use crate::BenchmarkError;
-use metal::{CommandQueue, Device, Library};
+use crate::device::{DeviceInfo, DeviceSelector, DeviceType};
+use crate::{Backend, BenchmarkError};
+use metal::{CommandQueue, CompileOptions, Device, Library};
 use std::path::Path;
 
 /// Metal GPU context
@@ -14,7 +15,25 @@ pub struct MetalContext {
 impl MetalContext {
     /// Create a new Metal context with the default GPU
     pub fn new() -> Result<Self, BenchmarkError> {
-        let device = Device::system_default().ok_or(BenchmarkError::NoDevice)?;
+        Self::with_selector(&DeviceSelector::Default)
+    }
+
+    /// Create a new Metal context on the selected GPU
+    pub fn with_selector(selector: &DeviceSelector) -> Result<Self, BenchmarkError> {
+        let device = match selector {
+            DeviceSelector::Default => Device::system_default().ok_or(BenchmarkError::NoDevice)?,
+            DeviceSelector::Software => {
+                return Err(BenchmarkError::BackendNotAvailable(
+                    "Metal has no software fallback device".to_string(),
+                ))
+            }
+            _ => Device::all()
+                .into_iter()
+                .enumerate()
+                .find(|(i, d)| selector.matches(*i, d.name()))
+                .map(|(_, d)| d)
+                .ok_or_else(|| BenchmarkError::DeviceNotFound(selector.to_string()))?,
+        };
 
         let command_queue = device.new_command_queue();
 
@@ -47,6 +66,27 @@ impl MetalContext {
         Ok(())
     }
 
+    /// Describe every Metal device, in `Device::all()` order
+    pub fn enumerate_devices() -> Vec<DeviceInfo> {
+        Device::all()
+            .iter()
+            .enumerate()
+            .map(|(index, device)| describe_device(index, device))
+            .collect()
+    }
+
+    /// Compile a Metal library from MSL source at runtime
+    pub fn compile_library(&self, source: &str) -> Result<Library, BenchmarkError> {
+        self.device
+            .new_library_with_source(source, &CompileOptions::new())
+            .map_err(BenchmarkError::ShaderCompilation)
+    }
+
+    /// Get the device class
+    pub fn device_type(&self) -> DeviceType {
+        metal_device_type(&self.device)
+    }
+
     /// Get device name
     pub fn device_name(&self) -> String {
         self.device.name().to_string()
@@ -59,3 +99,43 @@ impl MetalContext {
         true // Simplified for now
     }
 }
+
+/// Collect device properties and limits into a `DeviceInfo`
+fn describe_device(index: usize, device: &Device) -> DeviceInfo {
+    let max_threads = device.max_threads_per_threadgroup();
+
+    let mut features = Vec::new();
+    if device.has_unified_memory() {
+        features.push("UNIFIED_MEMORY".to_string());
+    }
+    if device.is_headless() {
+        features.push("HEADLESS".to_string());
+    }
+
+    DeviceInfo {
+        backend: Backend::Metal,
+        index,
+        name: device.name().to_string(),
+        device_type: metal_device_type(device),
+        driver: format!("Metal (registry id {:#x})", device.registry_id()),
+        limits: vec![
+            // Threads along x, the only dimension the kernels use
+            ("max_threads_per_threadgroup", max_threads.width),
+            ("max_buffer_length", device.max_buffer_length()),
+            (
+                "recommended_max_working_set_size",
+                device.recommended_max_working_set_size(),
+            ),
+        ],
+        features,
+    }
+}
+
+/// Classify a Metal device; Apple Silicon GPUs share memory with the CPU
+fn metal_device_type(device: &Device) -> DeviceType {
+    if device.has_unified_memory() || device.is_low_power() {
+        DeviceType::IntegratedGpu
+    } else {
+        DeviceType::DiscreteGpu
+    }
+}
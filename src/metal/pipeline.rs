@@ -2,12 +2,17 @@
 
 use crate::BenchmarkError;
 use metal::{ComputePipelineState, Device, Library, MTLSize};
+use std::time::{Duration, Instant};
 
 /// Metal compute pipeline for a benchmark kernel
 pub struct MetalPipeline {
     pub pipeline_state: ComputePipelineState,
     pub function_name: String,
     pub threads_per_threadgroup: MTLSize,
+    /// Time spent loading the kernel function (plus library compilation, if any)
+    pub shader_module_time: Duration,
+    /// Time spent creating the compute pipeline state
+    pub pipeline_time: Duration,
 }
 
 impl MetalPipeline {
@@ -18,16 +23,20 @@ impl MetalPipeline {
         function_name: &str,
         workgroup_size: u32,
     ) -> Result<Self, BenchmarkError> {
+        let start = Instant::now();
         let function = library.get_function(function_name, None).map_err(|_| {
             BenchmarkError::ShaderCompilation(format!(
                 "Function '{}' not found in library",
                 function_name
             ))
         })?;
+        let shader_module_time = start.elapsed();
 
+        let start = Instant::now();
         let pipeline_state = device
             .new_compute_pipeline_state_with_function(&function)
             .map_err(|e| BenchmarkError::PipelineCreation(format!("{:?}", e)))?;
+        let pipeline_time = start.elapsed();
 
         let threads_per_threadgroup = MTLSize::new(workgroup_size as u64, 1, 1);
 
@@ -35,6 +44,8 @@ impl MetalPipeline {
             pipeline_state,
             function_name: function_name.to_string(),
             threads_per_threadgroup,
+            shader_module_time,
+            pipeline_time,
         })
     }
 
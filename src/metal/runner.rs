@@ -1,104 +1,157 @@
 //! Metal benchmark execution
 
-use crate::config::BenchmarkConfig;
-use crate::results::BenchmarkResult;
+use crate::backend::{BackendCapabilities, BenchmarkBackend, DispatchGrid};
+use crate::config::{BenchmarkConfig, InputMode, OutputStorage, ParamsBinding, SubmitBatching};
+use crate::dataset;
+use crate::device::{DeviceSelector, DeviceType};
+use crate::results::{BenchmarkResult, StageLatency, StartupLatency};
+use crate::sampling;
+use crate::shaders;
+use crate::stream::{StreamLayout, StreamOperation, StreamTimings};
+use crate::transfer::{self, TransferMethod};
+use crate::translate;
+use crate::verify::{self, Verification};
 use crate::{Backend, BenchmarkError, Operation};
-use metal::{Buffer, MTLResourceOptions, MTLSize};
-use std::time::Instant;
+use metal::{Buffer, CommandBuffer, CommandBufferRef, MTLResourceOptions, MTLSize};
+use objc::{msg_send, sel, sel_impl};
+use std::time::{Duration, Instant};
 
 use super::{MetalContext, MetalPipeline};
 
+// Embedded metallib (compiled at build time)
+const METAL_LIB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/benchmarks.metallib"));
+
+/// Kernel parameters, laid out as the MSL `BenchParams`
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct BenchParams {
+    iterations: u32,
+    seed: u32,
+    dataset: u32,
+    threads: u32,
+}
+
+impl BenchParams {
+    fn from_config(config: &BenchmarkConfig) -> Self {
+        Self {
+            iterations: config.ops_per_thread,
+            seed: config.seed,
+            dataset: (config.input_mode == InputMode::Dataset) as u32,
+            threads: config.total_threads() as u32,
+        }
+    }
+}
+
+/// Params bound at slot 2: a buffer, or bytes copied in with `set_bytes`
+enum Params {
+    Buffer(Buffer),
+    Bytes(BenchParams),
+}
+
+/// Pipeline and buffers prepared for one operation
+struct PreparedBenchmark {
+    operation: Operation,
+    config: BenchmarkConfig,
+    pipeline: MetalPipeline,
+    grid: DispatchGrid,
+    input_buffer: Buffer,
+    output_buffer: Buffer,
+    params: Params,
+    /// Zero-iteration params, bound in place of `params` for overhead calibration
+    empty_params: Params,
+    /// Array sizes buffer and its slot, required by naga-generated MSL
+    sizes_buffer: Option<(u64, Buffer)>,
+}
+
 /// Benchmark runner for Metal
 pub struct MetalRunner {
     ctx: MetalContext,
+    backend: Backend,
+    prepared: Option<PreparedBenchmark>,
 }
 
 impl MetalRunner {
     pub fn new() -> Result<Self, BenchmarkError> {
-        let ctx = MetalContext::new()?;
-        Ok(Self { ctx })
+        Self::with_selector(&DeviceSelector::Default)
     }
 
-    pub fn device_name(&self) -> String {
-        self.ctx.device_name()
+    /// Create a runner on the selected device using the embedded metallib
+    pub fn with_selector(selector: &DeviceSelector) -> Result<Self, BenchmarkError> {
+        let mut ctx = MetalContext::with_selector(selector)?;
+        ctx.load_library_data(METAL_LIB)?;
+        Ok(Self {
+            ctx,
+            backend: Backend::Metal,
+            prepared: None,
+        })
+    }
+
+    /// Create a runner that compiles naga-translated WGSL kernels instead of the metallib
+    pub fn from_wgsl(selector: &DeviceSelector) -> Result<Self, BenchmarkError> {
+        let ctx = MetalContext::with_selector(selector)?;
+        Ok(Self {
+            ctx,
+            backend: Backend::MetalFromWgsl,
+            prepared: None,
+        })
     }
 
-    /// Load metallib from embedded bytes
+    /// Replace the shader library with a metallib from embedded bytes
     pub fn load_library_data(&mut self, data: &[u8]) -> Result<(), BenchmarkError> {
         self.ctx.load_library_data(data)
     }
 
-    /// Run a benchmark with the given configuration
-    pub fn run_benchmark(
+    /// Create the pipeline for an operation from the metallib or translated WGSL
+    fn create_pipeline(
         &self,
         operation: Operation,
         config: &BenchmarkConfig,
-    ) -> Result<BenchmarkResult, BenchmarkError> {
-        // Get the kernel function name for this operation
-        let function_name = operation_to_function_name(operation);
+    ) -> Result<(MetalPipeline, Option<u64>), BenchmarkError> {
+        if self.backend == Backend::MetalFromWgsl {
+            let start = Instant::now();
+            let kernel =
+                translate::to_msl_specialized(operation, shaders::KernelSpec::from_config(config))?;
+            let library = self.ctx.compile_library(&kernel.source)?;
+            let compile_time = start.elapsed();
+
+            let mut pipeline = MetalPipeline::new(
+                &self.ctx.device,
+                &library,
+                &kernel.entry_point,
+                config.workgroup_size,
+            )?;
+            pipeline.shader_module_time += compile_time;
+            return Ok((pipeline, Some(kernel.sizes_buffer)));
+        }
+
+        // Get the kernel function name for this operation and chain count
+        shaders::validate_chains(config.independent_chains)?;
+        let function_name = match config.independent_chains {
+            1 => operation_to_function_name(operation),
+            chains => format!("{}_chains{}", operation_to_function_name(operation), chains),
+        };
 
         // Check if we have a library loaded
         let library = self.ctx.library.as_ref().ok_or_else(|| {
             BenchmarkError::ShaderCompilation("No shader library loaded".to_string())
         })?;
 
-        // Create the compute pipeline
         let pipeline = MetalPipeline::new(
             &self.ctx.device,
             library,
             &function_name,
             config.workgroup_size,
         )?;
-
-        // Create buffers
-        let total_threads = config.total_threads() as usize;
-        let input_buffer = self.create_input_buffer(config.seed)?;
-        let output_buffer = self.create_output_buffer(total_threads)?;
-        let params_buffer = self.create_params_buffer(config)?;
-
-        // Warmup runs
-        for _ in 0..config.warmup_iterations {
-            self.dispatch(
-                &pipeline,
-                &input_buffer,
-                &output_buffer,
-                &params_buffer,
-                config,
-            )?;
-        }
-
-        // Timed runs
-        let mut timings = Vec::with_capacity(config.measurement_iterations as usize);
-
-        for _ in 0..config.measurement_iterations {
-            let start = Instant::now();
-            self.dispatch(
-                &pipeline,
-                &input_buffer,
-                &output_buffer,
-                &params_buffer,
-                config,
-            )?;
-            timings.push(start.elapsed());
-        }
-
-        // Create result
-        Ok(BenchmarkResult::from_timings(
-            Backend::Metal,
-            operation,
-            config.workgroup_size,
-            config.total_threads(),
-            config.ops_per_thread,
-            &timings,
-        ))
+        Ok((pipeline, None))
     }
 
-    /// Create input buffer with random data (16 values, matching shader access pattern)
-    fn create_input_buffer(&self, seed: u32) -> Result<Buffer, BenchmarkError> {
-        // Shaders only access input[(tid + i) % 16], so we only need 16 values
-        let data: Vec<u32> = (0..16u32)
-            .map(|i| seed.wrapping_add(i).wrapping_mul(0x9E3779B9))
-            .collect();
+    /// Create input buffer with the operation's seeded data
+    fn create_input_buffer(
+        &self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<Buffer, BenchmarkError> {
+        let data = dataset::generate(operation, config);
 
         let buffer = self.ctx.device.new_buffer_with_data(
             data.as_ptr() as *const _,
@@ -109,70 +162,586 @@ impl MetalRunner {
         Ok(buffer)
     }
 
-    /// Create output buffer (StorageModePrivate for GPU-only access, matching wgpu behavior)
-    fn create_output_buffer(&self, count: usize) -> Result<Buffer, BenchmarkError> {
-        let buffer = self.ctx.device.new_buffer(
-            (count * std::mem::size_of::<u32>()) as u64,
-            MTLResourceOptions::StorageModePrivate,
-        );
+    /// Create output buffer (StorageModePrivate by default for GPU-only access, matching wgpu)
+    fn create_output_buffer(
+        &self,
+        words: usize,
+        storage: OutputStorage,
+    ) -> Result<Buffer, BenchmarkError> {
+        let options = match storage {
+            OutputStorage::Private => MTLResourceOptions::StorageModePrivate,
+            OutputStorage::Shared => MTLResourceOptions::StorageModeShared,
+        };
+        let buffer = self
+            .ctx
+            .device
+            .new_buffer((words * std::mem::size_of::<u32>()) as u64, options);
 
         Ok(buffer)
     }
 
-    /// Create parameters buffer
-    fn create_params_buffer(&self, config: &BenchmarkConfig) -> Result<Buffer, BenchmarkError> {
-        #[repr(C)]
-        struct BenchParams {
-            iterations: u32,
-            seed: u32,
-            _pad0: u32,
-            _pad1: u32,
+    /// Create parameters, in a buffer unless the strategy pushes them with `set_bytes`
+    fn create_params(&self, config: &BenchmarkConfig) -> Result<Params, BenchmarkError> {
+        let params = BenchParams::from_config(config);
+        if config.buffer_strategy.params == ParamsBinding::PushConstants {
+            return Ok(Params::Bytes(params));
         }
 
-        let params = BenchParams {
-            iterations: config.ops_per_thread,
-            seed: config.seed,
-            _pad0: 0,
-            _pad1: 0,
-        };
-
         let buffer = self.ctx.device.new_buffer_with_data(
             &params as *const _ as *const _,
             std::mem::size_of::<BenchParams>() as u64,
             MTLResourceOptions::StorageModeShared,
         );
 
-        Ok(buffer)
+        Ok(Params::Buffer(buffer))
     }
 
-    /// Dispatch the compute kernel
+    /// Create the runtime-array sizes buffer (byte length of each bound array)
+    fn create_sizes_buffer(&self, buffers: &[&Buffer]) -> Buffer {
+        let sizes: Vec<u32> = buffers.iter().map(|b| b.length() as u32).collect();
+
+        self.ctx.device.new_buffer_with_data(
+            sizes.as_ptr() as *const _,
+            (sizes.len() * std::mem::size_of::<u32>()) as u64,
+            MTLResourceOptions::StorageModeShared,
+        )
+    }
+
+    /// Commit `dispatches_per_submit` dispatches with the given params and wait,
+    /// returning the GPU execution time if reported
     fn dispatch(
         &self,
-        pipeline: &MetalPipeline,
-        input_buffer: &Buffer,
-        output_buffer: &Buffer,
-        params_buffer: &Buffer,
-        config: &BenchmarkConfig,
-    ) -> Result<(), BenchmarkError> {
-        let command_buffer = self.ctx.command_queue.new_command_buffer();
+        prepared: &PreparedBenchmark,
+        params: &Params,
+    ) -> Result<Option<Duration>, BenchmarkError> {
+        let count = prepared.config.dispatches_per_submit.max(1);
+
+        let command_buffers: Vec<CommandBuffer> = match prepared.config.submit_batching {
+            SubmitBatching::SinglePass => vec![self.encode(prepared, params, count)],
+            SubmitBatching::CommandBuffers => (0..count)
+                .map(|_| self.encode(prepared, params, 1))
+                .collect(),
+        };
+
+        for command_buffer in &command_buffers {
+            command_buffer.commit();
+        }
+        // The queue runs command buffers in commit order, and count >= 1
+        let first: &CommandBufferRef = &command_buffers[0];
+        let last: &CommandBufferRef = &command_buffers[command_buffers.len() - 1];
+        last.wait_until_completed();
+
+        // GPUStartTime/GPUEndTime are host-time seconds; not wrapped by the metal crate
+        let (gpu_start, gpu_end): (f64, f64) =
+            unsafe { (msg_send![first, GPUStartTime], msg_send![last, GPUEndTime]) };
+
+        if gpu_end > gpu_start {
+            Ok(Some(Duration::from_secs_f64(gpu_end - gpu_start)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Encode `count` dispatches into one compute encoder of a new command buffer
+    fn encode(&self, prepared: &PreparedBenchmark, params: &Params, count: u32) -> CommandBuffer {
+        let command_buffer = self.ctx.command_queue.new_command_buffer().to_owned();
         let encoder = command_buffer.new_compute_command_encoder();
 
-        encoder.set_compute_pipeline_state(&pipeline.pipeline_state);
-        encoder.set_buffer(0, Some(input_buffer), 0);
-        encoder.set_buffer(1, Some(output_buffer), 0);
-        encoder.set_buffer(2, Some(params_buffer), 0);
+        encoder.set_compute_pipeline_state(&prepared.pipeline.pipeline_state);
+        encoder.set_buffer(0, Some(&prepared.input_buffer), 0);
+        encoder.set_buffer(1, Some(&prepared.output_buffer), 0);
+        if let Some((slot, buffer)) = &prepared.sizes_buffer {
+            encoder.set_buffer(*slot, Some(buffer), 0);
+        }
 
-        let threadgroups = MTLSize::new(config.num_workgroups as u64, 1, 1);
-        let threads_per_threadgroup = pipeline.threads_per_threadgroup;
+        let threadgroups = MTLSize::new(prepared.grid.x as u64, prepared.grid.y as u64, 1);
+        let threads_per_threadgroup = prepared.pipeline.threads_per_threadgroup;
 
-        encoder.dispatch_thread_groups(threadgroups, threads_per_threadgroup);
+        // Params are bound per dispatch, as successive kernels with their own params would be
+        for _ in 0..count {
+            match params {
+                Params::Buffer(buffer) => encoder.set_buffer(2, Some(buffer), 0),
+                Params::Bytes(bytes) => encoder.set_bytes(
+                    2,
+                    std::mem::size_of::<BenchParams>() as u64,
+                    bytes as *const _ as *const _,
+                ),
+            }
+            encoder.dispatch_thread_groups(threadgroups, threads_per_threadgroup);
+        }
         encoder.end_encoding();
 
+        command_buffer
+    }
+
+    /// Create the pipeline for a streaming kernel from the metallib or translated WGSL
+    fn create_stream_pipeline(
+        &self,
+        operation: StreamOperation,
+        workgroup_size: u32,
+    ) -> Result<(MetalPipeline, Option<u64>), BenchmarkError> {
+        if self.backend == Backend::MetalFromWgsl {
+            let kernel = translate::stream_to_msl(operation, workgroup_size)?;
+            let library = self.ctx.compile_library(&kernel.source)?;
+            let pipeline = MetalPipeline::new(
+                &self.ctx.device,
+                &library,
+                &kernel.entry_point,
+                workgroup_size,
+            )?;
+            return Ok((pipeline, Some(kernel.sizes_buffer)));
+        }
+
+        let library = self.ctx.library.as_ref().ok_or_else(|| {
+            BenchmarkError::ShaderCompilation("No shader library loaded".to_string())
+        })?;
+        let pipeline = MetalPipeline::new(
+            &self.ctx.device,
+            library,
+            operation.entry_point(),
+            workgroup_size,
+        )?;
+        Ok((pipeline, None))
+    }
+
+    /// Time a streaming kernel with its own pipeline and buffers (see [`BenchmarkBackend::run_stream`])
+    fn time_stream(
+        &self,
+        operation: StreamOperation,
+        layout: &StreamLayout,
+        config: &BenchmarkConfig,
+    ) -> Result<StreamTimings, BenchmarkError> {
+        #[repr(C)]
+        struct StreamParams {
+            count: u32,
+            _pad: [u32; 3],
+        }
+
+        let (pipeline, sizes_slot) =
+            self.create_stream_pipeline(operation, layout.workgroup_size)?;
+        let max_threads = pipeline.max_threads_per_threadgroup();
+        if layout.workgroup_size as u64 > max_threads {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "max_total_threads_per_threadgroup",
+                requested: layout.workgroup_size as u64,
+                max: max_threads,
+            });
+        }
+
+        // Reduced operands for the stream's field; the copy only reads `a`
+        let reads = operation.buffers_read() as usize;
+        let data = dataset::random_elements(
+            operation.field_operation(),
+            layout.elements as usize * reads,
+            config.seed,
+        );
+        let words = data.len() / reads;
+        let create_input = |contents: &[u32]| {
+            self.ctx.device.new_buffer_with_data(
+                contents.as_ptr() as *const _,
+                std::mem::size_of_val(contents) as u64,
+                MTLResourceOptions::StorageModeShared,
+            )
+        };
+        let a_buffer = create_input(&data[..words]);
+        let b_buffer = (reads > 1).then(|| create_input(&data[words..]));
+        drop(data);
+
+        let out_buffer = self
+            .ctx
+            .device
+            .new_buffer(layout.buffer_bytes, MTLResourceOptions::StorageModePrivate);
+        let params = StreamParams {
+            count: layout.elements as u32,
+            _pad: [0; 3],
+        };
+        let params_buffer = self.ctx.device.new_buffer_with_data(
+            &params as *const _ as *const _,
+            std::mem::size_of::<StreamParams>() as u64,
+            MTLResourceOptions::StorageModeShared,
+        );
+        let b_buffer = b_buffer.as_ref().unwrap_or(&a_buffer);
+        let sizes_buffer = sizes_slot.map(|slot| {
+            (
+                slot,
+                self.create_sizes_buffer(&[&a_buffer, b_buffer, &out_buffer]),
+            )
+        });
+
+        let threadgroups = MTLSize::new(layout.grid.x as u64, layout.grid.y as u64, 1);
+        let dispatch = || {
+            let command_buffer = self.ctx.command_queue.new_command_buffer();
+            let encoder = command_buffer.new_compute_command_encoder();
+            encoder.set_compute_pipeline_state(&pipeline.pipeline_state);
+            encoder.set_buffer(0, Some(&a_buffer), 0);
+            encoder.set_buffer(1, Some(b_buffer), 0);
+            encoder.set_buffer(2, Some(&out_buffer), 0);
+            encoder.set_buffer(3, Some(&params_buffer), 0);
+            if let Some((slot, buffer)) = &sizes_buffer {
+                encoder.set_buffer(*slot, Some(buffer), 0);
+            }
+            encoder.dispatch_thread_groups(threadgroups, pipeline.threads_per_threadgroup);
+            encoder.end_encoding();
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+
+            let (gpu_start, gpu_end): (f64, f64) = unsafe {
+                (
+                    msg_send![command_buffer, GPUStartTime],
+                    msg_send![command_buffer, GPUEndTime],
+                )
+            };
+            (gpu_end > gpu_start).then(|| Duration::from_secs_f64(gpu_end - gpu_start))
+        };
+
+        for _ in 0..config.warmup_iterations {
+            dispatch();
+        }
+
+        let mut timings = StreamTimings::default();
+        for _ in 0..config.measurement_iterations {
+            let start = Instant::now();
+            let gpu_time = dispatch();
+            timings.wall.push(start.elapsed());
+            timings.gpu.extend(gpu_time);
+        }
+        Ok(timings)
+    }
+
+    /// Time one host-device transfer path (see [`BenchmarkBackend::run_transfer`])
+    fn time_transfer(
+        &self,
+        method: TransferMethod,
+        bytes: u64,
+        config: &BenchmarkConfig,
+    ) -> Result<Vec<Duration>, BenchmarkError> {
+        let words = dataset::random_elements(Operation::U32Add, (bytes / 4) as usize, config.seed);
+        let data: &[u8] = bytemuck::cast_slice(&words);
+        let len = data.len();
+
+        let new_buffer = |options| self.ctx.device.new_buffer(bytes, options);
+        let new_shared_with_data = || {
+            self.ctx.device.new_buffer_with_data(
+                data.as_ptr() as *const _,
+                bytes,
+                MTLResourceOptions::StorageModeShared,
+            )
+        };
+        let blit = |source: &Buffer, destination: &Buffer| {
+            let command_buffer = self.ctx.command_queue.new_command_buffer();
+            let encoder = command_buffer.new_blit_command_encoder();
+            encoder.copy_from_buffer(source, 0, destination, 0, bytes);
+            encoder.end_encoding();
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+        };
+
+        match method {
+            TransferMethod::SharedUpload => {
+                // Unified memory: the GPU sees the shared contents without a copy
+                let buffer = new_buffer(MTLResourceOptions::StorageModeShared);
+                transfer::time_iterations(config, || {
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            data.as_ptr(),
+                            buffer.contents() as *mut u8,
+                            len,
+                        );
+                    }
+                    Ok(())
+                })
+            }
+            TransferMethod::PrivateBlitUpload => {
+                let staging_buffer = new_buffer(MTLResourceOptions::StorageModeShared);
+                let private_buffer = new_buffer(MTLResourceOptions::StorageModePrivate);
+                transfer::time_iterations(config, || {
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            data.as_ptr(),
+                            staging_buffer.contents() as *mut u8,
+                            len,
+                        );
+                    }
+                    blit(&staging_buffer, &private_buffer);
+                    Ok(())
+                })
+            }
+            TransferMethod::SharedReadback => {
+                let buffer = new_shared_with_data();
+                let mut host = vec![0u8; len];
+                let samples = transfer::time_iterations(config, || {
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            buffer.contents() as *const u8,
+                            host.as_mut_ptr(),
+                            len,
+                        );
+                    }
+                    Ok(())
+                })?;
+                transfer::check_download(data, &host)?;
+                Ok(samples)
+            }
+            TransferMethod::PrivateBlitReadback => {
+                let private_buffer = new_buffer(MTLResourceOptions::StorageModePrivate);
+                blit(&new_shared_with_data(), &private_buffer);
+                let staging_buffer = new_buffer(MTLResourceOptions::StorageModeShared);
+                let mut host = vec![0u8; len];
+                let samples = transfer::time_iterations(config, || {
+                    blit(&private_buffer, &staging_buffer);
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            staging_buffer.contents() as *const u8,
+                            host.as_mut_ptr(),
+                            len,
+                        );
+                    }
+                    Ok(())
+                })?;
+                transfer::check_download(data, &host)?;
+                Ok(samples)
+            }
+            _ => Err(BenchmarkError::BackendNotAvailable(format!(
+                "{} has no {} transfer",
+                self.backend.name(),
+                method
+            ))),
+        }
+    }
+
+    /// Run one full dispatch, read the output back and check it against the CPU reference
+    fn verify_output(&self, prepared: &PreparedBenchmark) -> Result<Verification, BenchmarkError> {
+        self.dispatch(prepared, &prepared.params)?;
+
+        // Blit the output (private unless the strategy says shared) into a buffer the host can read
+        let length = prepared.output_buffer.length();
+        let readback_buffer = self
+            .ctx
+            .device
+            .new_buffer(length, MTLResourceOptions::StorageModeShared);
+        let command_buffer = self.ctx.command_queue.new_command_buffer();
+        let blit = command_buffer.new_blit_command_encoder();
+        blit.copy_from_buffer(&prepared.output_buffer, 0, &readback_buffer, 0, length);
+        blit.end_encoding();
         command_buffer.commit();
         command_buffer.wait_until_completed();
 
+        let output = unsafe {
+            std::slice::from_raw_parts(
+                readback_buffer.contents() as *const u32,
+                length as usize / std::mem::size_of::<u32>(),
+            )
+        };
+        Ok(verify::verify(prepared.operation, &prepared.config, output))
+    }
+
+    /// Prepare, time the first dispatch and tear down, returning each stage's latency
+    fn time_startup(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<StageLatency, BenchmarkError> {
+        self.prepare(operation, config)?;
+        let first_dispatch = self.dispatch_once();
+        let stages = self
+            .prepared
+            .as_ref()
+            .map(|p| (p.pipeline.shader_module_time, p.pipeline.pipeline_time));
+        self.teardown();
+
+        let (shader_module, pipeline) =
+            stages.ok_or_else(|| BenchmarkError::Execution("No benchmark prepared".to_string()))?;
+        Ok(StageLatency::new(shader_module, pipeline, first_dispatch?))
+    }
+}
+
+impl BenchmarkBackend for MetalRunner {
+    fn name(&self) -> &str {
+        self.backend.name()
+    }
+
+    fn device_name(&self) -> String {
+        self.ctx.device_name()
+    }
+
+    fn device_type(&self) -> DeviceType {
+        self.ctx.device_type()
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            // Translated WGSL kernels emulate u64 even though the device has it
+            native_u64: self.backend.has_native_u64() && self.ctx.supports_native_u64(),
+            timestamp_queries: true,
+            max_workgroup_size: self.ctx.device.max_threads_per_threadgroup().width as u32,
+            max_workgroups_per_dimension: u32::MAX,
+            max_buffer_size: self.ctx.device.max_buffer_length(),
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<(), BenchmarkError> {
+        let grid = self.capabilities().validate(operation, config)?;
+        config.buffer_strategy.check(self.backend)?;
+        let (pipeline, sizes_slot) = self.create_pipeline(operation, config)?;
+
+        // Register-heavy kernels can fit fewer threads than the device maximum
+        let max_threads = pipeline.max_threads_per_threadgroup();
+        if config.workgroup_size as u64 > max_threads {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "max_total_threads_per_threadgroup",
+                requested: config.workgroup_size as u64,
+                max: max_threads,
+            });
+        }
+
+        // Create buffers
+        let total_threads = config.total_threads() as usize;
+        let input_buffer = self.create_input_buffer(operation, config)?;
+        let output_buffer = self.create_output_buffer(
+            total_threads * dataset::output_words(operation),
+            config.buffer_strategy.metal_output,
+        )?;
+        let params = self.create_params(config)?;
+        let empty_params = self.create_params(&config.clone().with_ops_per_thread(0))?;
+        let sizes_buffer = sizes_slot.map(|slot| {
+            (
+                slot,
+                self.create_sizes_buffer(&[&input_buffer, &output_buffer]),
+            )
+        });
+
+        self.prepared = Some(PreparedBenchmark {
+            operation,
+            config: config.clone(),
+            pipeline,
+            grid,
+            input_buffer,
+            output_buffer,
+            params,
+            empty_params,
+            sizes_buffer,
+        });
+
         Ok(())
     }
+
+    fn run(&mut self) -> Result<BenchmarkResult, BenchmarkError> {
+        let prepared = self
+            .prepared
+            .as_ref()
+            .ok_or_else(|| BenchmarkError::Execution("No benchmark prepared".to_string()))?;
+        let config = &prepared.config;
+
+        // Warmup runs
+        for _ in 0..config.warmup_iterations {
+            self.dispatch(prepared, &prepared.params)?;
+        }
+
+        // Timed runs
+        let mut gpu_timings = Vec::with_capacity(config.measurement_iterations as usize);
+
+        let samples = sampling::collect_samples(config, || {
+            let start = Instant::now();
+            let gpu_time = self.dispatch(prepared, &prepared.params)?;
+            let elapsed = start.elapsed();
+            gpu_timings.extend(gpu_time);
+            Ok(elapsed)
+        })?;
+
+        // Fall back to wall-clock only if any sample lacked a GPU time
+        if gpu_timings.len() != samples.timings.len() {
+            gpu_timings.clear();
+        }
+
+        // Empty-kernel runs: same pipeline and buffers, zero iterations
+        let mut overhead_timings = Vec::new();
+        if config.measure_overhead {
+            self.dispatch(prepared, &prepared.empty_params)?;
+            for _ in 0..config.measurement_iterations {
+                let start = Instant::now();
+                self.dispatch(prepared, &prepared.empty_params)?;
+                overhead_timings.push(start.elapsed());
+            }
+        }
+
+        // Verification runs after timing so the readback never lands in a sample
+        let verification = if config.verify {
+            Some(self.verify_output(prepared)?)
+        } else {
+            None
+        };
+
+        // Create result
+        Ok(BenchmarkResult::unsummarized(
+            self.name(),
+            prepared.operation,
+            config.workgroup_size,
+            config.total_threads(),
+            config.ops_per_thread,
+            &samples.timings,
+        )
+        .with_independent_chains(config.independent_chains)
+        .with_dispatches_per_submit(config.dispatches_per_submit, config.submit_batching)
+        .with_statistic(config.statistic)
+        .with_sample_offsets(&samples.offsets)
+        .with_convergence(samples.convergence)
+        .with_gpu_timings(&gpu_timings)
+        .with_dispatch_overhead(&overhead_timings)
+        .with_verification(config.input_mode, verification)
+        .with_buffer_strategy(config.buffer_strategy))
+    }
+
+    fn dispatch_once(&mut self) -> Result<Duration, BenchmarkError> {
+        let prepared = self
+            .prepared
+            .as_ref()
+            .ok_or_else(|| BenchmarkError::Execution("No benchmark prepared".to_string()))?;
+
+        let start = Instant::now();
+        self.dispatch(prepared, &prepared.params)?;
+        Ok(start.elapsed())
+    }
+
+    fn teardown(&mut self) {
+        self.prepared = None;
+    }
+
+    fn run_stream(
+        &mut self,
+        operation: StreamOperation,
+        layout: &StreamLayout,
+        config: &BenchmarkConfig,
+    ) -> Result<StreamTimings, BenchmarkError> {
+        self.time_stream(operation, layout, config)
+    }
+
+    fn run_transfer(
+        &mut self,
+        method: TransferMethod,
+        bytes: u64,
+        config: &BenchmarkConfig,
+    ) -> Result<Vec<Duration>, BenchmarkError> {
+        self.time_transfer(method, bytes, config)
+    }
+
+    fn measure_startup(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<Option<StartupLatency>, BenchmarkError> {
+        let cold = self.time_startup(operation, config)?;
+        let warm = self.time_startup(operation, config)?;
+
+        Ok(Some(StartupLatency {
+            cold,
+            warm,
+            pipeline_cache: None,
+        }))
+    }
 }
 
 /// Map operation to Metal kernel function name
@@ -0,0 +1,207 @@
+//! Occupancy saturation: the smallest grid that saturates the GPU
+//!
+//! The number of workgroups is doubled until throughput stops improving;
+//! the knee is the first grid within `tolerance` of the peak.
+
+use crate::backend::{self, BenchmarkBackend};
+use crate::config::BenchmarkConfig;
+use crate::results::BenchmarkResult;
+use crate::{BenchmarkError, Operation};
+use serde::{Deserialize, Serialize};
+
+/// How the workgroup count is grown and when throughput counts as saturated
+#[derive(Debug, Clone)]
+pub struct OccupancySearch {
+    /// First number of workgroups
+    pub start_workgroups: u32,
+
+    /// Largest number of workgroups tried (also capped by the device limits)
+    pub max_workgroups: u32,
+
+    /// Growth factor between steps
+    pub factor: u32,
+
+    /// Relative gain below which a step counts as no improvement
+    pub tolerance: f64,
+
+    /// Consecutive steps without improvement that end the search
+    pub patience: u32,
+}
+
+impl Default for OccupancySearch {
+    fn default() -> Self {
+        Self {
+            start_workgroups: 1,
+            max_workgroups: 65_536,
+            factor: 2,
+            tolerance: 0.05,
+            patience: 2,
+        }
+    }
+}
+
+/// Throughput at one grid size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OccupancyPoint {
+    pub num_workgroups: u32,
+    pub total_threads: u64,
+    pub gops_per_second: f64,
+}
+
+/// Throughput curve of one operation and its knee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Occupancy {
+    pub backend: String,
+    pub operation: String,
+    pub workgroup_size: u32,
+
+    /// Every grid tried, in increasing order
+    pub points: Vec<OccupancyPoint>,
+
+    /// Highest throughput seen
+    pub peak_gops: f64,
+
+    /// Smallest grid within `tolerance` of the peak
+    pub knee_workgroups: u32,
+    pub knee_threads: u64,
+
+    /// False if the search hit `max_workgroups` while throughput was still rising
+    pub saturated: bool,
+}
+
+impl Occupancy {
+    /// Build the curve and knee from per-grid results
+    fn from_results(results: &[BenchmarkResult], tolerance: f64, saturated: bool) -> Option<Self> {
+        let first = results.first()?;
+        let points: Vec<OccupancyPoint> = results
+            .iter()
+            .map(|r| OccupancyPoint {
+                num_workgroups: r.num_workgroups() as u32,
+                total_threads: r.total_threads,
+                gops_per_second: r.gops_per_second,
+            })
+            .collect();
+
+        let peak_gops = points.iter().map(|p| p.gops_per_second).fold(0.0, f64::max);
+        let knee = points
+            .iter()
+            .find(|p| p.gops_per_second >= peak_gops * (1.0 - tolerance))?;
+
+        Some(Self {
+            backend: first.backend.clone(),
+            operation: first.operation.clone(),
+            workgroup_size: first.workgroup_size,
+            knee_workgroups: knee.num_workgroups,
+            knee_threads: knee.total_threads,
+            points,
+            peak_gops,
+            saturated,
+        })
+    }
+}
+
+/// Grow the grid for one operation until throughput plateaus
+///
+/// Returns the result of every step (calibrated per step if `config.auto_calibrate`)
+/// alongside the curve, which is None if no grid fits under `max_workgroups`.
+pub fn find_knee(
+    backend: &mut dyn BenchmarkBackend,
+    operation: Operation,
+    config: &BenchmarkConfig,
+    search: &OccupancySearch,
+    on_step: &mut dyn FnMut(&BenchmarkResult),
+) -> Result<(Vec<BenchmarkResult>, Option<Occupancy>), BenchmarkError> {
+    let capabilities = backend.capabilities();
+    let factor = search.factor.max(2);
+
+    let mut results: Vec<BenchmarkResult> = Vec::new();
+    let mut best_gops = 0.0;
+    let mut stale = 0;
+    let mut saturated = false;
+    let mut num_workgroups = search.start_workgroups.max(1);
+
+    while num_workgroups <= search.max_workgroups {
+        // Grids beyond the device limits end the search rather than fail it
+        let step_config = config.clone().with_num_workgroups(num_workgroups);
+        if results.is_empty() {
+            capabilities.validate(operation, &step_config)?;
+        } else if capabilities.validate(operation, &step_config).is_err() {
+            break;
+        }
+
+        let result = backend::run_operation(backend, operation, &step_config)?;
+        on_step(&result);
+
+        if result.gops_per_second > best_gops * (1.0 + search.tolerance) {
+            stale = 0;
+        } else {
+            stale += 1;
+        }
+        best_gops = f64::max(best_gops, result.gops_per_second);
+        results.push(result);
+
+        if stale >= search.patience {
+            saturated = true;
+            break;
+        }
+        num_workgroups = match num_workgroups.checked_mul(factor) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    let occupancy = Occupancy::from_results(&results, search.tolerance, saturated);
+    Ok((results, occupancy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Backend;
+    use std::time::Duration;
+
+    /// One result per grid; 64 threads per workgroup, one op per thread
+    fn curve(points: &[(u32, u64)]) -> Vec<BenchmarkResult> {
+        points
+            .iter()
+            .map(|&(num_workgroups, ns)| {
+                BenchmarkResult::from_timings(
+                    Backend::WebGPU,
+                    Operation::U32Add,
+                    64,
+                    num_workgroups as u64 * 64,
+                    1,
+                    &[Duration::from_nanos(ns)],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn knee_is_the_first_grid_near_the_peak() {
+        // Throughput doubles up to 4 workgroups, then levels off
+        let results = curve(&[(1, 64), (2, 64), (4, 64), (8, 124), (16, 256)]);
+        let occupancy = Occupancy::from_results(&results, 0.05, true).unwrap();
+
+        assert_eq!(occupancy.points.len(), 5);
+        assert_eq!(occupancy.workgroup_size, 64);
+        assert!((occupancy.peak_gops - 512.0 / 124.0).abs() < 1e-9);
+        assert_eq!(occupancy.knee_workgroups, 4);
+        assert_eq!(occupancy.knee_threads, 256);
+        assert!(occupancy.saturated);
+    }
+
+    #[test]
+    fn tolerance_moves_the_knee() {
+        let results = curve(&[(1, 100), (2, 100), (4, 100), (8, 160)]);
+        let strict = Occupancy::from_results(&results, 0.0, false).unwrap();
+        assert_eq!(strict.knee_workgroups, 8);
+        let loose = Occupancy::from_results(&results, 0.25, false).unwrap();
+        assert_eq!(loose.knee_workgroups, 4);
+    }
+
+    #[test]
+    fn no_results_no_curve() {
+        assert!(Occupancy::from_results(&[], 0.05, false).is_none());
+    }
+}
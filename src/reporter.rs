@@ -1,4 +1,8 @@
+use crate::compare::{self, SignificanceTest};
+use crate::config::InputMode;
+use crate::device::DeviceInfo;
 use crate::results::{BenchmarkReport, BenchmarkResult};
+use crate::sweep;
 use console::Style;
 use std::io::Write;
 
@@ -20,6 +24,27 @@ fn get_display_name(op: &str) -> &str {
     }
 }
 
+/// Find an operation's result in a report, matching equivalent names
+fn find_result<'a>(report: &'a BenchmarkReport, op: &str) -> Option<&'a BenchmarkResult> {
+    let equivalents = get_equivalent_ops(op);
+    report
+        .results
+        .iter()
+        .find(|r| r.operation == op || equivalents.contains(&r.operation.as_str()))
+}
+
+/// Operation name with non-default chains and batching, e.g. "m31_field_mul 4ch x16"
+fn display_name(operation: &str, chains: u32, dispatches_per_submit: u32) -> String {
+    let mut name = operation.to_string();
+    if chains > 1 {
+        name.push_str(&format!(" {}ch", chains));
+    }
+    if dispatches_per_submit > 1 {
+        name.push_str(&format!(" x{}", dispatches_per_submit));
+    }
+    name
+}
+
 /// Print benchmark results to console
 pub fn print_results(report: &BenchmarkReport) {
     let header_style = Style::new().bold().cyan();
@@ -51,30 +76,213 @@ pub fn print_results(report: &BenchmarkReport) {
         value_style.apply_to(&report.device_name),
         &report.device_vendor
     );
+    print_drift_warnings(report);
+    if report.is_software() {
+        println!(
+            "{}",
+            Style::new().bold().red().apply_to(
+                "SOFTWARE ADAPTER: functional run only, NOT representative of GPU performance"
+            )
+        );
+    }
     println!();
 
     // Table header
     println!(
-        "{:<25} {:>10} {:>12} {:>12}",
+        "{:<25} {:>10} {:>12} {:>12} {:>12} {:>12} {:>19} {:>7} {:>12}",
         label_style.apply_to("Benchmark"),
         label_style.apply_to("WG Size"),
         label_style.apply_to("Min (ms)"),
+        label_style.apply_to("Median (ms)"),
+        label_style.apply_to("GPU (ms)"),
         label_style.apply_to("GOP/s"),
+        label_style.apply_to("GOP/s 95% CI"),
+        label_style.apply_to("CV %"),
+        label_style.apply_to("Net GOP/s"),
     );
-    println!("{}", "-".repeat(65));
+    println!("{}", "-".repeat(132));
 
     // Results
     for result in &report.results {
+        print_result_line(result);
+    }
+
+    println!();
+
+    if let Some(result) = report.results.first() {
+        let outliers: usize = report.results.iter().map(|r| r.outliers_rejected).sum();
         println!(
-            "{:<25} {:>10} {:>12.3} {:>12.2}",
-            result.operation,
-            result.workgroup_size,
-            result.min_ms(),
-            result.gops_per_second,
+            "{}",
+            Style::new().dim().apply_to(format!(
+                "GOP/s from {} time; median, CI and CV exclude {} MAD outlier(s)",
+                result.statistic, outliers
+            ))
         );
     }
 
-    println!();
+    if let Some(drift) = &report.drift {
+        println!(
+            "{}",
+            Style::new().dim().apply_to(format!(
+                "Drift ({} sentinel, start vs end of suite): {:+.1}% (p={:.3})",
+                drift.operation,
+                drift.relative_change * 100.0,
+                drift.p_value
+            ))
+        );
+    }
+
+    let calibrated: Vec<_> = report
+        .results
+        .iter()
+        .filter_map(|r| r.calibration.as_ref().map(|c| (r, c)))
+        .collect();
+    if let Some((_, first)) = calibrated.first() {
+        println!(
+            "{}",
+            Style::new().dim().apply_to(format!(
+                "ops_per_thread calibrated to ~{:.0} ms per dispatch:",
+                first.target_ms
+            ))
+        );
+        for (result, c) in &calibrated {
+            println!(
+                "{}",
+                Style::new().dim().apply_to(format!(
+                    "  {:<23} {:>9} ops/thread ({:.1} ms{})",
+                    result.operation,
+                    c.ops_per_thread,
+                    c.measured_ms,
+                    if c.cached { ", cached" } else { "" }
+                ))
+            );
+        }
+    }
+
+    let adaptive: Vec<_> = report
+        .results
+        .iter()
+        .filter_map(|r| r.convergence.as_ref().map(|c| (r, c)))
+        .collect();
+    if let Some((_, first)) = adaptive.first() {
+        println!(
+            "{}",
+            Style::new().dim().apply_to(format!(
+                "Adaptive sampling to ±{:.1}% CI: {}/{} converged",
+                first.target_relative_ci * 100.0,
+                adaptive.iter().filter(|(_, c)| c.converged).count(),
+                adaptive.len()
+            ))
+        );
+        for (result, c) in &adaptive {
+            let line = format!(
+                "  {:<23} {:>5} samples, ±{:.2}% in {:.0} ms",
+                result.operation,
+                c.samples,
+                c.relative_ci * 100.0,
+                c.elapsed_ms
+            );
+            if c.converged {
+                println!("{}", Style::new().dim().apply_to(line));
+            } else {
+                println!("{}", Style::new().yellow().apply_to(line));
+            }
+        }
+    }
+
+    if let Some(overhead) = report
+        .results
+        .iter()
+        .find_map(|r| r.dispatch_overhead.as_ref())
+    {
+        println!(
+            "{}",
+            Style::new().dim().apply_to(format!(
+                "Net GOP/s subtracts the empty-kernel dispatch overhead (~{:.3} ms)",
+                overhead.min_ns as f64 / 1e6
+            ))
+        );
+        println!();
+    }
+
+    let startup: Vec<_> = report
+        .results
+        .iter()
+        .filter_map(|r| r.startup.as_ref().map(|s| (r, s)))
+        .collect();
+    if !startup.is_empty() {
+        println!(
+            "{}",
+            Style::new()
+                .dim()
+                .apply_to("Startup (ms): shader module + pipeline + first dispatch")
+        );
+        for (result, s) in &startup {
+            let cached = match &s.pipeline_cache {
+                Some(c) => format!(", cached {:.1}", c.total_ms()),
+                None => String::new(),
+            };
+            println!(
+                "{}",
+                Style::new().dim().apply_to(format!(
+                    "  {:<23} cold {:.1} + {:.1} + {:.1} = {:.1}, warm {:.1}{}",
+                    result.operation,
+                    s.cold.shader_module_ms,
+                    s.cold.pipeline_ms,
+                    s.cold.first_dispatch_ms,
+                    s.cold.total_ms(),
+                    s.warm.total_ms(),
+                    cached
+                ))
+            );
+        }
+        println!();
+    }
+
+    if let Some(result) = report
+        .results
+        .iter()
+        .find(|r| r.input_mode == InputMode::Dataset)
+    {
+        println!(
+            "{}",
+            Style::new().dim().apply_to(format!(
+                "Input: one random operand pair per thread and chain ({})",
+                result.input_mode.name()
+            ))
+        );
+    }
+
+    let verified: Vec<_> = report
+        .results
+        .iter()
+        .filter_map(|r| r.verification.as_ref().map(|v| (r, v)))
+        .collect();
+    if !verified.is_empty() {
+        println!(
+            "{}",
+            Style::new()
+                .dim()
+                .apply_to("Output verification: sampled threads vs CPU reference")
+        );
+        for (result, v) in &verified {
+            let line = format!(
+                "  {:<23} {}/{} threads match{}",
+                result.operation,
+                v.threads_checked - v.mismatches,
+                v.threads_checked,
+                v.first_mismatch
+                    .map(|tid| format!(" (first mismatch: thread {})", tid))
+                    .unwrap_or_default()
+            );
+            if v.passed() {
+                println!("{}", Style::new().dim().apply_to(line));
+            } else {
+                println!("{}", Style::new().bold().red().apply_to(line));
+            }
+        }
+        println!();
+    }
 
     // Overhead analysis
     if let Some(overhead) = report.u64_overhead() {
@@ -91,17 +299,113 @@ pub fn print_results(report: &BenchmarkReport) {
     println!();
 }
 
+/// Warn about sentinel drift and per-operation slowdown trends
+fn print_drift_warnings(report: &BenchmarkReport) {
+    let warn_style = Style::new().bold().yellow();
+
+    if let Some(drift) = report.drift.as_ref().filter(|d| d.warning) {
+        println!(
+            "{}",
+            warn_style.apply_to(format!(
+                "DRIFT: {} went from {:.2} to {:.2} GOP/s ({:+.1}%, p={:.3}) over the suite; \
+                 results may be biased by throttling or clock changes",
+                drift.operation,
+                drift.start_gops,
+                drift.end_gops,
+                drift.relative_change * 100.0,
+                drift.p_value
+            ))
+        );
+    }
+
+    for result in &report.results {
+        if let Some(trend) = result.trend.as_ref().filter(|t| t.throttling) {
+            println!(
+                "{}",
+                warn_style.apply_to(format!(
+                    "THROTTLING: {} samples slowed by {:.1}% during the run (tau={:.2}, p={:.3})",
+                    result.operation,
+                    trend.relative_change * 100.0,
+                    trend.kendall_tau,
+                    trend.p_value
+                ))
+            );
+        }
+    }
+}
+
+/// Print enumerated devices with their limits and features
+pub fn print_devices(devices: &[DeviceInfo]) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+    let value_style = Style::new().green();
+    let info_style = Style::new().dim();
+
+    for device in devices {
+        println!(
+            "{} {}: {} ({})",
+            header_style.apply_to(format!("[{} #{}]", device.backend.name(), device.index)),
+            label_style.apply_to("Device"),
+            value_style.apply_to(&device.name),
+            device.device_type
+        );
+        println!("  {}: {}", label_style.apply_to("Driver"), device.driver);
+
+        println!("  {}:", label_style.apply_to("Limits"));
+        for (name, value) in &device.limits {
+            println!("    {:<40} {:>14}", name, value);
+        }
+
+        if device.features.is_empty() {
+            println!("  {}: none", label_style.apply_to("Features"));
+        } else {
+            println!("  {}:", label_style.apply_to("Features"));
+            for feature in &device.features {
+                println!("    {}", info_style.apply_to(feature));
+            }
+        }
+        println!();
+    }
+}
+
 /// Print a single result line (for live updates)
 pub fn print_result_line(result: &BenchmarkResult) {
+    let gpu_ms = match &result.gpu_timing {
+        Some(gpu) => format!("{:.3}", gpu.min_ms()),
+        None => "-".to_string(),
+    };
+    let net_gops = match result.net_gops_per_second() {
+        Some(gops) => format!("{:.2}", gops),
+        None => "-".to_string(),
+    };
+
+    let ci = format!("[{:.2}, {:.2}]", result.gops_ci_low, result.gops_ci_high);
+    let name = display_name(
+        &result.operation,
+        result.independent_chains,
+        result.dispatches_per_submit,
+    );
+
     println!(
-        "{:<25} {:>10} {:>12.3} {:>12.2}",
-        result.operation,
+        "{:<25} {:>10} {:>12.3} {:>12.3} {:>12} {:>12.2} {:>19} {:>7.2} {:>12}",
+        name,
         result.workgroup_size,
         result.min_ms(),
+        result.median_ns / 1e6,
+        gpu_ms,
         result.gops_per_second,
+        ci,
+        result.cv * 100.0,
+        net_gops,
     );
 }
 
+/// Load a report previously exported with [`export_json`]
+pub fn load_json(path: &str) -> std::io::Result<BenchmarkReport> {
+    let json = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
 /// Export results to JSON file
 pub fn export_json(report: &BenchmarkReport, path: &str) -> std::io::Result<()> {
     let json = serde_json::to_string_pretty(report)?;
@@ -109,7 +413,14 @@ pub fn export_json(report: &BenchmarkReport, path: &str) -> std::io::Result<()>
 }
 
 /// Print comparison between multiple backend reports
-pub fn print_comparison(reports: &[BenchmarkReport]) {
+///
+/// Ratios between a software (CPU) adapter and a hardware device are
+/// suppressed unless `allow_software_ratios` is set.
+pub fn print_comparison(
+    reports: &[BenchmarkReport],
+    allow_software_ratios: bool,
+    test: SignificanceTest,
+) {
     let header_style = Style::new().bold().cyan();
     let label_style = Style::new().bold();
     let good_style = Style::new().green();
@@ -159,56 +470,83 @@ pub fn print_comparison(reports: &[BenchmarkReport]) {
     }
     all_ops.sort_by_key(|op| get_operation_order(op));
 
+    let mixed_software =
+        reports.iter().any(|r| r.is_software()) && reports.iter().any(|r| !r.is_software());
+    let show_ratio = reports.len() == 2 && (!mixed_software || allow_software_ratios);
+
     // Print header with backend names
     print!("{:<20}", label_style.apply_to("Operation"));
     for report in reports {
-        print!(" {:>15}", label_style.apply_to(&report.device_vendor));
+        print!(" {:>15}", label_style.apply_to(report.label()));
     }
-    if reports.len() == 2 {
+    if show_ratio {
         print!(" {:>12}", label_style.apply_to("Ratio"));
+        print!(" {:>16}", label_style.apply_to("95% CI"));
+        print!(" {:>8}", label_style.apply_to("p"));
+        print!(" {:>13}", label_style.apply_to("Verdict"));
+        print!(" {:>12}", label_style.apply_to("Net Ratio"));
     }
     println!();
     println!(
         "{}",
-        "-".repeat(20 + reports.len() * 16 + if reports.len() == 2 { 13 } else { 0 })
+        "-".repeat(20 + reports.len() * 16 + if show_ratio { 66 } else { 0 })
     );
 
     // Print comparison for each operation
     for op in &all_ops {
         print!("{:<20}", op);
 
-        let mut gops_values: Vec<Option<f64>> = Vec::new();
-
-        // Get equivalent operation names for matching
-        let equivalents = get_equivalent_ops(op);
+        let mut results: Vec<Option<&BenchmarkResult>> = Vec::new();
+        let mut net_values: Vec<Option<f64>> = Vec::new();
 
         for report in reports {
             // Search for the operation or any equivalent
-            let result = report
-                .results
-                .iter()
-                .find(|r| &r.operation == op || equivalents.contains(&r.operation.as_str()));
+            let result = find_result(report, op);
 
             if let Some(result) = result {
                 print!(" {:>12.2} GOP/s", result.gops_per_second);
-                gops_values.push(Some(result.gops_per_second));
+                results.push(Some(result));
+                net_values.push(result.net_gops_per_second());
             } else {
                 print!(" {:>15}", "-");
-                gops_values.push(None);
+                results.push(None);
+                net_values.push(None);
             }
         }
 
-        // Calculate ratio if we have exactly 2 backends with values
-        if reports.len() == 2 {
-            if let (Some(Some(v1)), Some(Some(v2))) = (gops_values.get(0), gops_values.get(1)) {
-                if *v2 > 0.0 {
-                    let ratio = v1 / v2;
-                    if ratio > 1.0 {
-                        print!(" {}", good_style.apply_to(format!("{:>10.2}x", ratio)));
-                    } else {
-                        print!(" {}", warn_style.apply_to(format!("{:>10.2}x", ratio)));
+        // Calculate ratios if we have exactly 2 backends with values
+        if show_ratio {
+            let print_ratio = |ratio: f64| {
+                if ratio > 1.0 {
+                    print!(" {}", good_style.apply_to(format!("{:>11.2}x", ratio)));
+                } else {
+                    print!(" {}", warn_style.apply_to(format!("{:>11.2}x", ratio)));
+                }
+            };
+
+            match (results.first(), results.get(1)) {
+                (Some(Some(r1)), Some(Some(r2))) if r2.gops_per_second > 0.0 => {
+                    print_ratio(r1.gops_per_second / r2.gops_per_second);
+
+                    match compare::compare(r1, r2, test, compare::DEFAULT_ALPHA) {
+                        Some(c) => {
+                            let ci = format!("[{:.2}, {:.2}]", c.ratio_ci_low, c.ratio_ci_high);
+                            print!(" {:>16} {:>8.3}", ci, c.p_value);
+                            if c.significant {
+                                print!(" {}", good_style.apply_to(format!("{:>13}", c.verdict())));
+                            } else {
+                                print!(" {}", warn_style.apply_to(format!("{:>13}", c.verdict())));
+                            }
+                        }
+                        None => print!(" {:>16} {:>8} {:>13}", "-", "-", "-"),
                     }
                 }
+                _ => print!(" {:>12} {:>16} {:>8} {:>13}", "-", "-", "-", "-"),
+            }
+
+            match (net_values.first(), net_values.get(1)) {
+                (Some(Some(v1)), Some(Some(v2))) if *v2 > 0.0 => print_ratio(v1 / v2),
+                _ => print!(" {:>12}", "-"),
             }
         }
         println!();
@@ -216,13 +554,43 @@ pub fn print_comparison(reports: &[BenchmarkReport]) {
 
     println!();
 
+    print_startup_comparison(reports, &all_ops);
+
+    if mixed_software && !allow_software_ratios {
+        println!(
+            "{}",
+            warn_style.apply_to(
+                "Ratios omitted: software (CPU) adapter results are not comparable with hardware"
+            )
+        );
+    }
+
     // Print legend
-    if reports.len() == 2 {
+    if show_ratio {
         println!(
             "{}",
             label_style
                 .apply_to("Ratio: First backend / Second backend (higher = first is faster)")
         );
+        println!(
+            "{}",
+            label_style.apply_to(format!(
+                "95% CI: bootstrap interval on the ratio; p: {} test on per-sample GOP/s",
+                test
+            ))
+        );
+        println!(
+            "{}",
+            label_style.apply_to(format!(
+                "Verdict: significant if p < {} and the CI excludes 1.00x",
+                compare::DEFAULT_ALPHA
+            ))
+        );
+        println!(
+            "{}",
+            label_style
+                .apply_to("Net Ratio: same, after subtracting each backend's dispatch overhead")
+        );
     }
 
     println!(
@@ -234,17 +602,570 @@ pub fn print_comparison(reports: &[BenchmarkReport]) {
     println!();
 }
 
+/// Print cold and warm startup latency of each operation side by side, if measured
+fn print_startup_comparison(reports: &[BenchmarkReport], ops: &[String]) {
+    let label_style = Style::new().bold();
+
+    if !reports
+        .iter()
+        .any(|r| r.results.iter().any(|res| res.startup.is_some()))
+    {
+        return;
+    }
+
+    print!("{:<20}", label_style.apply_to("Startup (ms)"));
+    for report in reports {
+        print!(" {:>15}", label_style.apply_to(report.label()));
+    }
+    println!();
+    println!("{}", "-".repeat(20 + reports.len() * 16));
+
+    for op in ops {
+        print!("{:<20}", op);
+        for report in reports {
+            match find_result(report, op).and_then(|r| r.startup.as_ref()) {
+                Some(s) => print!(
+                    " {:>15}",
+                    format!("{:.1} / {:.1}", s.cold.total_ms(), s.warm.total_ms())
+                ),
+                None => print!(" {:>15}", "-"),
+            }
+        }
+        println!();
+    }
+
+    println!();
+    println!(
+        "{}",
+        label_style
+            .apply_to("Startup: cold / warm shader module + pipeline creation + first dispatch")
+    );
+    println!();
+}
+
+/// A table with one column per value of a swept parameter
+struct Sweep<'a> {
+    title: &'a str,
+    /// Column header prefix, e.g. "K="
+    column: &'a str,
+    /// Swept parameter of a result
+    key: fn(&BenchmarkResult) -> u32,
+    /// Row name: the operation plus any parameters that are not swept
+    row: fn(&BenchmarkResult) -> String,
+    /// Value shown in each cell
+    value: fn(&BenchmarkResult) -> f64,
+    /// Last column header and its value from the first and last cells of a row
+    summary: (&'a str, fn(f64, f64) -> String),
+}
+
+/// Print one row per backend/device and operation, one column per swept value
+fn print_sweep(reports: &[BenchmarkReport], sweep: &Sweep) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+
+    let mut keys: Vec<u32> = reports
+        .iter()
+        .flat_map(|r| r.results.iter().map(sweep.key))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    // Rows keyed by device/backend label and row name, in first-seen order
+    let mut rows: Vec<(String, String)> = Vec::new();
+    for report in reports {
+        for result in &report.results {
+            let row = (report.label(), (sweep.row)(result));
+            if !rows.contains(&row) {
+                rows.push(row);
+            }
+        }
+    }
+
+    println!();
+    println!("{}", header_style.apply_to(sweep.title));
+    println!();
+
+    print!("{:<40}", label_style.apply_to("Benchmark"));
+    for key in &keys {
+        print!(
+            " {:>10}",
+            label_style.apply_to(format!("{}{}", sweep.column, key))
+        );
+    }
+    print!(" {:>12}", label_style.apply_to(sweep.summary.0));
+    println!();
+    println!("{}", "-".repeat(40 + keys.len() * 11 + 13));
+
+    for (label, name) in &rows {
+        print!("{:<40}", format!("{} {}", name, label));
+
+        let values: Vec<Option<f64>> = keys
+            .iter()
+            .map(|key| {
+                reports
+                    .iter()
+                    .filter(|r| &r.label() == label)
+                    .flat_map(|r| &r.results)
+                    .find(|res| &(sweep.row)(res) == name && (sweep.key)(res) == *key)
+                    .map(sweep.value)
+            })
+            .collect();
+
+        for value in &values {
+            match value {
+                Some(v) => print!(" {:>10.3}", v),
+                None => print!(" {:>10}", "-"),
+            }
+        }
+
+        match (values.first(), values.last()) {
+            (Some(Some(first)), Some(Some(last))) if keys.len() > 1 => {
+                print!(" {:>12}", (sweep.summary.1)(*first, *last))
+            }
+            _ => print!(" {:>12}", "-"),
+        }
+        println!();
+    }
+    println!();
+}
+
+/// Print per-dispatch time of each operation against dispatches per submission
+///
+/// Expects one report per batch size, e.g. from a `--dispatches-per-submit 1,4,16` run.
+pub fn print_batching(reports: &[BenchmarkReport]) {
+    print_sweep(
+        reports,
+        &Sweep {
+            title: "              PER-DISPATCH TIME (ms) vs DISPATCHES PER SUBMIT",
+            column: "K=",
+            key: |r| r.dispatches_per_submit,
+            row: |r| display_name(&r.operation, r.independent_chains, 1),
+            value: |r| r.per_dispatch_ns() / 1e6,
+            summary: ("Saved/disp", |first, last| format!("{:.3}", first - last)),
+        },
+    );
+    println!(
+        "{}",
+        Style::new().bold().apply_to(
+            "Saved/disp: per-dispatch time (ms) amortized by batching, smallest vs largest K"
+        )
+    );
+    println!();
+}
+
+/// Print GOP/s of each operation against independent chains per thread
+///
+/// Expects one report per chain count, e.g. from a `--independent-chains 1,2,4,8` run.
+pub fn print_chains(reports: &[BenchmarkReport]) {
+    print_sweep(
+        reports,
+        &Sweep {
+            title: "                    GOP/s vs INDEPENDENT CHAINS PER THREAD",
+            column: "chains=",
+            key: |r| r.independent_chains,
+            row: |r| display_name(&r.operation, 1, r.dispatches_per_submit),
+            value: |r| r.gops_per_second,
+            summary: ("Speedup", |first, last| format!("{:.2}x", last / first)),
+        },
+    );
+    println!(
+        "{}",
+        Style::new().bold().apply_to(
+            "Speedup: most vs fewest chains; near 1x means issue-rate bound, large means latency bound"
+        )
+    );
+    println!();
+}
+
+/// Print GOP/s of each operation under each buffer strategy
+///
+/// Expects one report per strategy, e.g. from a `--buffer-strategy default,shared_output` run.
+pub fn print_buffer_strategies(reports: &[BenchmarkReport]) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+
+    let mut strategies: Vec<String> = Vec::new();
+    let mut rows: Vec<(String, String)> = Vec::new();
+    for report in reports {
+        for result in &report.results {
+            let strategy = result.buffer_strategy.name();
+            if !strategies.contains(&strategy) {
+                strategies.push(strategy);
+            }
+            let row = (
+                report.label(),
+                display_name(
+                    &result.operation,
+                    result.independent_chains,
+                    result.dispatches_per_submit,
+                ),
+            );
+            if !rows.contains(&row) {
+                rows.push(row);
+            }
+        }
+    }
+    let widths: Vec<usize> = strategies.iter().map(|s| s.len().max(10)).collect();
+
+    println!();
+    println!(
+        "{}",
+        header_style.apply_to("                    GOP/s vs BUFFER STRATEGY")
+    );
+    println!();
+
+    print!("{:<40}", label_style.apply_to("Benchmark"));
+    for (strategy, width) in strategies.iter().zip(&widths) {
+        print!(" {:>width$}", label_style.apply_to(strategy), width = width);
+    }
+    print!("  {}", label_style.apply_to("Best vs Def"));
+    println!();
+    println!(
+        "{}",
+        "-".repeat(40 + widths.iter().map(|w| w + 1).sum::<usize>() + 24)
+    );
+
+    for (label, name) in &rows {
+        print!("{:<40}", format!("{} {}", name, label));
+
+        let values: Vec<Option<f64>> = strategies
+            .iter()
+            .map(|strategy| {
+                reports
+                    .iter()
+                    .filter(|r| &r.label() == label)
+                    .flat_map(|r| &r.results)
+                    .find(|res| {
+                        &display_name(
+                            &res.operation,
+                            res.independent_chains,
+                            res.dispatches_per_submit,
+                        ) == name
+                            && &res.buffer_strategy.name() == strategy
+                    })
+                    .map(|res| res.gops_per_second)
+            })
+            .collect();
+
+        for (value, width) in values.iter().zip(&widths) {
+            match value {
+                Some(v) => print!(" {:>width$.3}", v, width = width),
+                None => print!(" {:>width$}", "-", width = width),
+            }
+        }
+
+        // Best strategy's gain over the default, when the default was run
+        let default = strategies
+            .iter()
+            .position(|s| s == "default")
+            .and_then(|i| values[i]);
+        let best = values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.map(|v| (i, v)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match (default, best) {
+            (Some(default), Some((i, best))) if default > 0.0 => {
+                print!(
+                    "  {} {:+.1}%",
+                    strategies[i],
+                    100.0 * (best / default - 1.0)
+                )
+            }
+            _ => print!("  -"),
+        }
+        println!();
+    }
+    println!();
+    println!(
+        "{}",
+        label_style.apply_to("Best vs Def: fastest strategy and its GOP/s change over default")
+    );
+    println!();
+}
+
+/// Print the highest-throughput configuration of each operation in a parameter sweep
+pub fn print_sweep_summary(reports: &[BenchmarkReport]) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+
+    println!();
+    println!(
+        "{}",
+        header_style.apply_to("                    BEST CONFIGURATION PER OPERATION")
+    );
+    println!();
+    println!(
+        "{:<40} {:>8} {:>11} {:>11} {:>12} {:>9}",
+        label_style.apply_to("Benchmark"),
+        label_style.apply_to("WG Size"),
+        label_style.apply_to("Workgroups"),
+        label_style.apply_to("Ops/thread"),
+        label_style.apply_to("GOP/s"),
+        label_style.apply_to("vs Worst"),
+    );
+    println!("{}", "-".repeat(96));
+
+    for (label, best) in sweep::best_per_operation(reports) {
+        // Slowest configuration of the same operation on the same device/backend
+        let worst = reports
+            .iter()
+            .filter(|r| r.label() == label)
+            .flat_map(|r| &r.results)
+            .filter(|r| r.operation == best.operation)
+            .map(|r| r.gops_per_second)
+            .fold(f64::INFINITY, f64::min);
+
+        println!(
+            "{:<40} {:>8} {:>11} {:>11} {:>12.3} {:>9}",
+            format!(
+                "{} {}",
+                display_name(
+                    &best.operation,
+                    best.independent_chains,
+                    best.dispatches_per_submit
+                ),
+                label
+            ),
+            best.workgroup_size,
+            best.num_workgroups(),
+            best.ops_per_thread,
+            best.gops_per_second,
+            if worst > 0.0 {
+                format!("{:.2}x", best.gops_per_second / worst)
+            } else {
+                "-".to_string()
+            },
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        label_style.apply_to("vs Worst: best over slowest swept configuration of the operation")
+    );
+    println!();
+}
+
+/// Print the occupancy knee and throughput curve of each operation
+pub fn print_occupancy(report: &BenchmarkReport) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+    let info_style = Style::new().dim();
+    let warn_style = Style::new().bold().yellow();
+
+    println!();
+    println!(
+        "{}",
+        header_style.apply_to("                    OCCUPANCY: SMALLEST SATURATING GRID")
+    );
+    println!();
+    println!("Device: {} ({})", report.device_name, report.label());
+    println!();
+    println!(
+        "{:<28} {:>8} {:>11} {:>14} {:>12}",
+        label_style.apply_to("Benchmark"),
+        label_style.apply_to("WG Size"),
+        label_style.apply_to("Knee WGs"),
+        label_style.apply_to("Knee Threads"),
+        label_style.apply_to("Peak GOP/s"),
+    );
+    println!("{}", "-".repeat(77));
+
+    for occupancy in &report.occupancy {
+        println!(
+            "{:<28} {:>8} {:>11} {:>14} {:>12.3}",
+            occupancy.operation,
+            occupancy.workgroup_size,
+            occupancy.knee_workgroups,
+            occupancy.knee_threads,
+            occupancy.peak_gops,
+        );
+
+        // GOP/s at each grid as a share of the peak
+        let curve: Vec<String> = occupancy
+            .points
+            .iter()
+            .map(|p| {
+                format!(
+                    "{}:{:.0}%",
+                    p.num_workgroups,
+                    100.0 * p.gops_per_second / occupancy.peak_gops
+                )
+            })
+            .collect();
+        println!("{}", info_style.apply_to(format!("  {}", curve.join(" "))));
+
+        if !occupancy.saturated {
+            println!(
+                "{}",
+                warn_style.apply_to(
+                    "  Still rising at the largest grid; the knee may be higher (--max-workgroups)"
+                )
+            );
+        }
+    }
+    println!();
+    println!(
+        "{}",
+        label_style.apply_to(
+            "Knee: fewest workgroups within tolerance of peak; curve is workgroups:% of peak"
+        )
+    );
+    println!();
+}
+
+/// Print bandwidth and throughput of each stream, relative to the copy baseline
+pub fn print_streams(report: &BenchmarkReport) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+    let info_style = Style::new().dim();
+
+    println!();
+    println!(
+        "{}",
+        header_style.apply_to("                    STREAMING: MEMORY BANDWIDTH")
+    );
+    println!();
+    println!("Device: {} ({})", report.device_name, report.label());
+    println!();
+    println!(
+        "{:<16} {:>10} {:>12} {:>12} {:>10} {:>10} {:>10} {:>8}",
+        label_style.apply_to("Stream"),
+        label_style.apply_to("Buffer MB"),
+        label_style.apply_to("Elements"),
+        label_style.apply_to("Time (ms)"),
+        label_style.apply_to("GB/s"),
+        label_style.apply_to("GOP/s"),
+        label_style.apply_to("% Copy"),
+        label_style.apply_to("Bound"),
+    );
+    println!("{}", "-".repeat(95));
+
+    for stream in &report.streams {
+        let (copy_share, bound) = match (stream.copy_fraction, stream.memory_bound()) {
+            (Some(fraction), Some(memory)) => (
+                format!("{:.1}%", 100.0 * fraction),
+                if memory { "memory" } else { "compute" },
+            ),
+            _ => ("-".to_string(), "-"),
+        };
+        println!(
+            "{:<16} {:>10.1} {:>12} {:>12.3} {:>10.2} {:>10.3} {:>10} {:>8}",
+            stream.operation,
+            stream.buffer_bytes as f64 / crate::stream::MB as f64,
+            stream.elements,
+            stream.time_ns / 1_000_000.0,
+            stream.gb_per_second,
+            stream.gops_per_second,
+            copy_share,
+            bound,
+        );
+    }
+
+    if report.streams.iter().any(|s| !s.gpu_timed) {
+        println!();
+        println!(
+            "{}",
+            info_style.apply_to(
+                "Some streams were timed by wall clock (no GPU timestamps); small buffers include submission latency"
+            )
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        label_style.apply_to(format!(
+            "GB/s counts every buffer read and written; memory-bound at ≥{:.0}% of copy bandwidth",
+            100.0 * crate::stream::MEMORY_BOUND_FRACTION
+        ))
+    );
+    println!();
+}
+
+/// Print latency and bandwidth of each transfer method by size
+pub fn print_transfers(report: &BenchmarkReport) {
+    let header_style = Style::new().bold().cyan();
+    let label_style = Style::new().bold();
+
+    println!();
+    println!(
+        "{}",
+        header_style.apply_to("                    HOST-DEVICE TRANSFERS")
+    );
+    println!();
+    println!("Device: {} ({})", report.device_name, report.label());
+    println!();
+    println!(
+        "{:<24} {:<10} {:>12} {:>14} {:>10}",
+        label_style.apply_to("Method"),
+        label_style.apply_to("Direction"),
+        label_style.apply_to("Size"),
+        label_style.apply_to("Latency (µs)"),
+        label_style.apply_to("GB/s"),
+    );
+    println!("{}", "-".repeat(74));
+
+    for transfer in &report.transfers {
+        println!(
+            "{:<24} {:<10} {:>12} {:>14.1} {:>10.2}",
+            transfer.method,
+            transfer.direction.name(),
+            format_bytes(transfer.bytes),
+            transfer.time_ns / 1_000.0,
+            transfer.gb_per_second,
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        label_style.apply_to(
+            "Latency: host time from the first call until the data is usable on the other side"
+        )
+    );
+    println!();
+}
+
+/// Format a byte count in the largest whole binary unit
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.0} {}", value, UNITS[unit])
+}
+
 /// Merge multiple reports into a single combined report
 pub fn merge_reports(reports: &[BenchmarkReport]) -> BenchmarkReport {
-    let device_names: Vec<&str> = reports.iter().map(|r| r.device_name.as_str()).collect();
-    let vendors: Vec<&str> = reports.iter().map(|r| r.device_vendor.as_str()).collect();
+    // Each device and backend once, e.g. when merging the reports of a sweep
+    let mut device_names: Vec<&str> = Vec::new();
+    let mut vendors: Vec<&str> = Vec::new();
+    for report in reports {
+        if !device_names.contains(&report.device_name.as_str()) {
+            device_names.push(&report.device_name);
+        }
+        if !vendors.contains(&report.device_vendor.as_str()) {
+            vendors.push(&report.device_vendor);
+        }
+    }
 
     let mut combined = BenchmarkReport::new(device_names.join(" + "), vendors.join(" + "));
 
+    // Keep the device class only if every report agrees on it
+    let first_type = reports.first().and_then(|r| r.device_type);
+    if reports.iter().all(|r| r.device_type == first_type) {
+        combined.device_type = first_type;
+    }
+
     for report in reports {
         for result in &report.results {
             combined.add_result(result.clone());
         }
+        combined.occupancy.extend(report.occupancy.iter().cloned());
+        combined.streams.extend(report.streams.iter().cloned());
+        combined.transfers.extend(report.transfers.iter().cloned());
     }
 
     combined
@@ -252,20 +1173,74 @@ pub fn merge_reports(reports: &[BenchmarkReport]) -> BenchmarkReport {
 
 /// Export results to CSV file
 pub fn export_csv(report: &BenchmarkReport, path: &str) -> std::io::Result<()> {
+    // `stream` and `transfer` runs have no per-operation results, only their own table
+    if report.results.is_empty() && !report.streams.is_empty() {
+        return export_streams_csv(report, path);
+    }
+    if report.results.is_empty() && !report.transfers.is_empty() {
+        return export_transfers_csv(report, path);
+    }
+
     let mut file = std::fs::File::create(path)?;
 
     // Header
     writeln!(
         file,
-        "backend,operation,workgroup_size,total_threads,ops_per_thread,total_operations,min_ns,max_ns,mean_ns,std_dev_ns,gops_per_second"
+        "backend,device_name,device_type,operation,workgroup_size,total_threads,ops_per_thread,total_operations,min_ns,max_ns,mean_ns,std_dev_ns,median_ns,p5_ns,p95_ns,cv,outliers_rejected,statistic,gops_per_second,gops_ci_low,gops_ci_high,samples,converged,trend_change,trend_p,gpu_min_ns,gpu_mean_ns,gpu_gops_per_second,overhead_min_ns,net_ns,net_gops_per_second,cold_shader_module_ms,cold_pipeline_ms,cold_first_dispatch_ms,warm_startup_ms,cached_startup_ms,independent_chains,dispatches_per_submit,submit_batching,per_dispatch_ns,num_workgroups,suite_entry,input_mode,verified_threads,verify_mismatches,buffer_strategy"
     )?;
 
     // Data
     for r in &report.results {
+        // GPU columns are left empty when timestamps were unavailable
+        let (gpu_min, gpu_mean, gpu_gops) = match &r.gpu_timing {
+            Some(gpu) => (
+                gpu.min_ns.to_string(),
+                format!("{:.2}", gpu.mean_ns),
+                format!("{:.4}", gpu.gops_per_second),
+            ),
+            None => Default::default(),
+        };
+        let (trend_change, trend_p) = match &r.trend {
+            Some(t) => (
+                format!("{:.4}", t.relative_change),
+                format!("{:.4}", t.p_value),
+            ),
+            None => Default::default(),
+        };
+        let (overhead_min, net, net_gops) = match &r.dispatch_overhead {
+            Some(o) => (
+                o.min_ns.to_string(),
+                o.net_ns.to_string(),
+                format!("{:.4}", o.net_gops_per_second),
+            ),
+            None => Default::default(),
+        };
+        let (cold_shader, cold_pipeline, cold_dispatch, warm_startup, cached_startup) =
+            match &r.startup {
+                Some(s) => (
+                    format!("{:.3}", s.cold.shader_module_ms),
+                    format!("{:.3}", s.cold.pipeline_ms),
+                    format!("{:.3}", s.cold.first_dispatch_ms),
+                    format!("{:.3}", s.warm.total_ms()),
+                    s.pipeline_cache
+                        .as_ref()
+                        .map(|c| format!("{:.3}", c.total_ms()))
+                        .unwrap_or_default(),
+                ),
+                None => Default::default(),
+            };
+
+        let (verified, mismatches) = match &r.verification {
+            Some(v) => (v.threads_checked.to_string(), v.mismatches.to_string()),
+            None => Default::default(),
+        };
+
         writeln!(
             file,
-            "{},{},{},{},{},{},{},{},{:.2},{:.2},{:.4}",
+            "{},{},{},{},{},{},{},{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.4},{},{},{:.4},{:.4},{:.4},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{:.2},{},{},{},{},{},{}",
             r.backend,
+            csv_quote(&r.device_name),
+            r.device_type.map(|t| t.name()).unwrap_or(""),
             r.operation,
             r.workgroup_size,
             r.total_threads,
@@ -275,9 +1250,221 @@ pub fn export_csv(report: &BenchmarkReport, path: &str) -> std::io::Result<()> {
             r.max_ns,
             r.mean_ns,
             r.std_dev_ns,
+            r.median_ns,
+            r.p5_ns,
+            r.p95_ns,
+            r.cv,
+            r.outliers_rejected,
+            r.statistic,
             r.gops_per_second,
+            r.gops_ci_low,
+            r.gops_ci_high,
+            r.samples_ns.len(),
+            r.convergence
+                .as_ref()
+                .map(|c| c.converged.to_string())
+                .unwrap_or_default(),
+            trend_change,
+            trend_p,
+            gpu_min,
+            gpu_mean,
+            gpu_gops,
+            overhead_min,
+            net,
+            net_gops,
+            cold_shader,
+            cold_pipeline,
+            cold_dispatch,
+            warm_startup,
+            cached_startup,
+            r.independent_chains,
+            r.dispatches_per_submit,
+            r.submit_batching.name(),
+            r.per_dispatch_ns(),
+            r.num_workgroups(),
+            csv_quote(r.suite_entry.as_deref().unwrap_or("")),
+            r.input_mode.name(),
+            verified,
+            mismatches,
+            r.buffer_strategy.name(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Quote a free-text CSV field, doubling any quotes inside it
+fn csv_quote(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+/// Export the streaming results of a report to CSV file
+fn export_streams_csv(report: &BenchmarkReport, path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+
+    writeln!(
+        file,
+        "backend,device_name,operation,workgroup_size,elements,buffer_bytes,bytes_moved,statistic,time_ns,min_ns,median_ns,gpu_timed,gb_per_second,gops_per_second,copy_fraction"
+    )?;
+
+    for s in &report.streams {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{},{:.2},{},{:.2},{},{:.4},{:.4},{}",
+            s.backend,
+            csv_quote(&s.device_name),
+            s.operation,
+            s.workgroup_size,
+            s.elements,
+            s.buffer_bytes,
+            s.bytes_moved,
+            s.statistic,
+            s.time_ns,
+            s.min_ns,
+            s.median_ns,
+            s.gpu_timed,
+            s.gb_per_second,
+            s.gops_per_second,
+            s.copy_fraction
+                .map(|f| format!("{:.4}", f))
+                .unwrap_or_default(),
         )?;
     }
 
     Ok(())
 }
+
+/// Export the transfer results of a report to CSV file
+fn export_transfers_csv(report: &BenchmarkReport, path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+
+    writeln!(
+        file,
+        "backend,device_name,method,direction,bytes,statistic,time_ns,min_ns,median_ns,gb_per_second"
+    )?;
+
+    for t in &report.transfers {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{:.2},{},{:.2},{:.4}",
+            t.backend,
+            csv_quote(&t.device_name),
+            t.method,
+            t.direction.name(),
+            t.bytes,
+            t.statistic,
+            t.time_ns,
+            t.min_ns,
+            t.median_ns,
+            t.gb_per_second,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::Statistic;
+    use crate::stream::StreamResult;
+    use crate::transfer::{TransferDirection, TransferResult};
+
+    const DEVICE: &str = "GPU, \"Pro\" rev 2";
+
+    /// Split a CSV line into fields, honouring quotes and doubled quotes
+    fn split_csv(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    fields.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(String::new()),
+                _ => fields.last_mut().unwrap().push(c),
+            }
+        }
+        fields
+    }
+
+    fn export(report: &BenchmarkReport, name: &str) -> Vec<Vec<String>> {
+        let path = std::env::temp_dir().join(format!(
+            "field-ops-{}-test-{}.csv",
+            name,
+            std::process::id()
+        ));
+        export_csv(report, path.to_str().unwrap()).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        text.lines().map(split_csv).collect()
+    }
+
+    fn report() -> BenchmarkReport {
+        BenchmarkReport::new(DEVICE.to_string(), "Vendor".to_string())
+    }
+
+    #[test]
+    fn csv_quote_doubles_quotes() {
+        assert_eq!(csv_quote("plain"), "\"plain\"");
+        assert_eq!(csv_quote("a, b"), "\"a, b\"");
+        assert_eq!(csv_quote("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(split_csv(&csv_quote(DEVICE)), vec![DEVICE.to_string()]);
+    }
+
+    #[test]
+    fn stream_csv_quotes_device_name() {
+        let mut report = report();
+        report.streams.push(StreamResult {
+            backend: "WebGPU".to_string(),
+            device_name: DEVICE.to_string(),
+            operation: "copy".to_string(),
+            workgroup_size: 64,
+            elements: 1024,
+            buffer_bytes: 4096,
+            bytes_moved: 8192,
+            statistic: Statistic::Min,
+            time_ns: 1000.0,
+            min_ns: 1000,
+            median_ns: 1100.0,
+            gpu_timed: true,
+            gb_per_second: 8.192,
+            gops_per_second: 1.024,
+            copy_fraction: Some(1.0),
+        });
+
+        let rows = export(&report, "streams");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].len(), rows[0].len());
+        assert_eq!(rows[0][1], "device_name");
+        assert_eq!(rows[1][1], DEVICE);
+        assert_eq!(rows[1][2], "copy");
+    }
+
+    #[test]
+    fn transfer_csv_quotes_device_name() {
+        let mut report = report();
+        report.transfers.push(TransferResult {
+            backend: "WebGPU".to_string(),
+            device_name: DEVICE.to_string(),
+            method: "write_buffer".to_string(),
+            direction: TransferDirection::Upload,
+            bytes: 4096,
+            statistic: Statistic::Median,
+            time_ns: 2000.0,
+            min_ns: 1800,
+            median_ns: 2000.0,
+            gb_per_second: 2.048,
+        });
+
+        let rows = export(&report, "transfers");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].len(), rows[0].len());
+        assert_eq!(rows[0][1], "device_name");
+        assert_eq!(rows[1][1], DEVICE);
+        assert_eq!(rows[1][2], "write_buffer");
+    }
+}
@@ -1,3 +1,12 @@
+use crate::calibration::Calibration;
+use crate::compare::{self, SignificanceTest};
+use crate::config::{BufferStrategy, InputMode, SubmitBatching};
+use crate::device::DeviceType;
+use crate::occupancy::Occupancy;
+use crate::stats::{self, Statistic};
+use crate::stream::StreamResult;
+use crate::transfer::TransferResult;
+use crate::verify::Verification;
 use crate::{Backend, Operation};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -8,6 +17,14 @@ pub struct BenchmarkResult {
     /// Backend used
     pub backend: String,
 
+    /// Device the result was measured on (filled in when added to a report)
+    #[serde(default)]
+    pub device_name: String,
+
+    /// Device class; `Cpu` marks software-adapter results
+    #[serde(default)]
+    pub device_type: Option<DeviceType>,
+
     /// Operation benchmarked
     pub operation: String,
 
@@ -20,17 +37,324 @@ pub struct BenchmarkResult {
     /// Operations per thread
     pub ops_per_thread: u32,
 
-    /// Total operations executed
+    /// Total operations executed per sample (all dispatches of a submission)
     pub total_operations: u64,
 
+    /// Independent accumulator chains per thread, each running ops_per_thread operations
+    #[serde(default = "default_count")]
+    pub independent_chains: u32,
+
+    /// Dispatches recorded in each timed submission
+    #[serde(default = "default_count")]
+    pub dispatches_per_submit: u32,
+
+    /// How the dispatches of a submission were recorded
+    #[serde(default)]
+    pub submit_batching: SubmitBatching,
+
     /// Timing statistics (in nanoseconds)
     pub min_ns: u64,
     pub max_ns: u64,
     pub mean_ns: f64,
     pub std_dev_ns: f64,
 
+    /// Robust statistics over the samples left after MAD outlier rejection (in nanoseconds)
+    #[serde(default)]
+    pub median_ns: f64,
+    #[serde(default)]
+    pub p5_ns: f64,
+    #[serde(default)]
+    pub p95_ns: f64,
+
+    /// Coefficient of variation (std dev / mean) after outlier rejection
+    #[serde(default)]
+    pub cv: f64,
+
+    /// Number of samples rejected as outliers
+    #[serde(default)]
+    pub outliers_rejected: usize,
+
     /// Derived metrics
     pub gops_per_second: f64,
+
+    /// Timing statistic that `gops_per_second` is derived from
+    #[serde(default)]
+    pub statistic: Statistic,
+
+    /// Bootstrap 95% confidence interval on `gops_per_second`
+    #[serde(default)]
+    pub gops_ci_low: f64,
+    #[serde(default)]
+    pub gops_ci_high: f64,
+
+    /// Raw wall-clock samples (in nanoseconds)
+    #[serde(default)]
+    pub samples_ns: Vec<u64>,
+
+    /// Start of each sample relative to the first (in nanoseconds)
+    #[serde(default)]
+    pub sample_offsets_ns: Vec<u64>,
+
+    /// Slowdown trend across the samples (e.g. thermal throttling)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+
+    /// How ops_per_thread was calibrated, when auto-calibration is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calibration: Option<Calibration>,
+
+    /// How adaptive sampling ended, when enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub convergence: Option<Convergence>,
+
+    /// GPU-side kernel timing, when timestamp queries are supported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_timing: Option<GpuTiming>,
+
+    /// Empty-kernel dispatch overhead and overhead-subtracted metrics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dispatch_overhead: Option<DispatchOverhead>,
+
+    /// Shader, pipeline and first-dispatch latency, when measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup: Option<StartupLatency>,
+
+    /// Where the kernel read its operands from
+    #[serde(default)]
+    pub input_mode: InputMode,
+
+    /// Storage modes and usages of the output and params buffers
+    #[serde(default)]
+    pub buffer_strategy: BufferStrategy,
+
+    /// Sampled threads checked against the CPU reference, when enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<Verification>,
+
+    /// Name of the suite-file entry that produced this result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite_entry: Option<String>,
+}
+
+/// Monotonic trend of sample times over the course of one result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trend {
+    /// Change of the fitted sample time from first to last sample (0.1 = 10% slower)
+    pub relative_change: f64,
+
+    /// Mann-Kendall tau (positive when samples get slower) and its p-value
+    pub kendall_tau: f64,
+    pub p_value: f64,
+
+    /// Samples slow down significantly by more than [`Trend::THROTTLING_THRESHOLD`]
+    pub throttling: bool,
+}
+
+impl Trend {
+    /// Slowdown over a run above which a significant trend is flagged
+    pub const THROTTLING_THRESHOLD: f64 = 0.05;
+
+    /// Detect a trend in sample times (in nanoseconds) started at `offsets` (in nanoseconds)
+    pub fn detect(samples_ns: &[f64], offsets_ns: &[f64]) -> Option<Self> {
+        if samples_ns.len() < 3 || samples_ns.len() != offsets_ns.len() {
+            return None;
+        }
+
+        let (intercept, slope) = stats::linear_fit(offsets_ns, samples_ns);
+        let first = intercept + slope * offsets_ns[0];
+        let last = intercept + slope * offsets_ns[offsets_ns.len() - 1];
+        let relative_change = if first > 0.0 { last / first - 1.0 } else { 0.0 };
+        let (kendall_tau, p_value) = stats::mann_kendall(samples_ns);
+
+        Some(Self {
+            relative_change,
+            kendall_tau,
+            p_value,
+            throttling: p_value < 0.05
+                && kendall_tau > 0.0
+                && relative_change > Self::THROTTLING_THRESHOLD,
+        })
+    }
+}
+
+/// Change in throughput of a sentinel operation between the start and end of a suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Drift {
+    /// Sentinel operation
+    pub operation: String,
+
+    /// Sentinel throughput before and after the suite
+    pub start_gops: f64,
+    pub end_gops: f64,
+
+    /// `end / start - 1` (negative when the device slowed down)
+    pub relative_change: f64,
+
+    /// Welch's t-test p-value between the two sentinel runs
+    pub p_value: f64,
+
+    /// The change is significant and larger than [`Drift::WARN_THRESHOLD`]
+    pub warning: bool,
+}
+
+impl Drift {
+    /// Relative throughput change above which a significant drift is warned about
+    pub const WARN_THRESHOLD: f64 = 0.05;
+
+    /// Compare the sentinel runs from the start and end of a suite
+    pub fn from_sentinels(start: &BenchmarkResult, end: &BenchmarkResult) -> Option<Self> {
+        let comparison =
+            compare::compare(end, start, SignificanceTest::Welch, compare::DEFAULT_ALPHA)?;
+        let relative_change = comparison.ratio - 1.0;
+
+        Some(Self {
+            operation: start.operation.clone(),
+            start_gops: start.gops_per_second,
+            end_gops: end.gops_per_second,
+            relative_change,
+            p_value: comparison.p_value,
+            warning: comparison.significant && relative_change.abs() > Self::WARN_THRESHOLD,
+        })
+    }
+}
+
+/// Outcome of adaptive sampling for one result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Convergence {
+    /// Requested relative half-width of the 95% throughput CI
+    pub target_relative_ci: f64,
+
+    /// Relative half-width reached when sampling stopped
+    pub relative_ci: f64,
+
+    /// Whether the target was reached before the time budget or sample cap
+    pub converged: bool,
+
+    /// Number of samples needed
+    pub samples: usize,
+
+    /// Time spent sampling (in milliseconds)
+    pub elapsed_ms: f64,
+}
+
+/// Fixed submit/synchronize cost measured with a zero-iteration dispatch of the same kernel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatchOverhead {
+    /// Empty-kernel timing statistics (in nanoseconds)
+    pub min_ns: u64,
+    pub mean_ns: f64,
+
+    /// Time of the throughput statistic with the overhead subtracted
+    pub net_ns: u64,
+
+    /// Throughput derived from `net_ns`
+    pub net_gops_per_second: f64,
+}
+
+/// Time to get an operation ready to run, split by stage (in milliseconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageLatency {
+    /// Shader module creation (MSL compilation and function lookup on Metal)
+    pub shader_module_ms: f64,
+
+    /// Compute pipeline creation
+    pub pipeline_ms: f64,
+
+    /// First dispatch after the pipeline and buffers are created
+    pub first_dispatch_ms: f64,
+}
+
+impl StageLatency {
+    pub fn new(shader_module: Duration, pipeline: Duration, first_dispatch: Duration) -> Self {
+        Self {
+            shader_module_ms: shader_module.as_secs_f64() * 1e3,
+            pipeline_ms: pipeline.as_secs_f64() * 1e3,
+            first_dispatch_ms: first_dispatch.as_secs_f64() * 1e3,
+        }
+    }
+
+    /// Sum of the three stages
+    pub fn total_ms(&self) -> f64 {
+        self.shader_module_ms + self.pipeline_ms + self.first_dispatch_ms
+    }
+}
+
+/// Startup latency of an operation, cold and warm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupLatency {
+    /// First creation in the process (driver on-disk shader caches may still apply)
+    pub cold: StageLatency,
+
+    /// Second creation of the same pipeline
+    pub warm: StageLatency,
+
+    /// Creation through a populated wgpu pipeline cache, when the device supports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline_cache: Option<StageLatency>,
+}
+
+/// Kernel execution time measured on the GPU (excludes submission and wake-up latency)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuTiming {
+    /// Timing statistics (in nanoseconds)
+    pub min_ns: u64,
+    pub max_ns: u64,
+    pub mean_ns: f64,
+    pub std_dev_ns: f64,
+
+    /// Throughput derived from the minimum GPU time
+    pub gops_per_second: f64,
+}
+
+impl GpuTiming {
+    /// Summarize GPU timings for a kernel that executed `total_operations`
+    pub fn from_timings(total_operations: u64, timings: &[Duration]) -> Self {
+        let (min_ns, max_ns, mean_ns, std_dev_ns) = timing_stats(timings);
+
+        Self {
+            min_ns,
+            max_ns,
+            mean_ns,
+            std_dev_ns,
+            gops_per_second: gops(total_operations, min_ns),
+        }
+    }
+
+    /// Get minimum GPU time in milliseconds
+    pub fn min_ms(&self) -> f64 {
+        self.min_ns as f64 / 1e6
+    }
+}
+
+/// Min, max, mean and population standard deviation of timings in nanoseconds
+fn timing_stats(timings: &[Duration]) -> (u64, u64, f64, f64) {
+    let timings_ns: Vec<u64> = timings.iter().map(|d| d.as_nanos() as u64).collect();
+
+    let min_ns = *timings_ns.iter().min().unwrap_or(&0);
+    let max_ns = *timings_ns.iter().max().unwrap_or(&0);
+    let sum: u64 = timings_ns.iter().sum();
+    let mean_ns = sum as f64 / timings_ns.len().max(1) as f64;
+
+    let variance: f64 = timings_ns
+        .iter()
+        .map(|&t| (t as f64 - mean_ns).powi(2))
+        .sum::<f64>()
+        / timings_ns.len().max(1) as f64;
+
+    (min_ns, max_ns, mean_ns, variance.sqrt())
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+/// Giga-operations per second for `total_operations` completed in `ns`
+fn gops(total_operations: u64, ns: u64) -> f64 {
+    if ns > 0 {
+        (total_operations as f64) / (ns as f64 / 1e9) / 1e9
+    } else {
+        0.0
+    }
 }
 
 impl BenchmarkResult {
@@ -43,31 +367,59 @@ impl BenchmarkResult {
         ops_per_thread: u32,
         timings: &[Duration],
     ) -> Self {
-        let timings_ns: Vec<u64> = timings.iter().map(|d| d.as_nanos() as u64).collect();
+        Self::from_timings_named(
+            backend.name(),
+            operation,
+            workgroup_size,
+            total_threads,
+            ops_per_thread,
+            timings,
+        )
+    }
 
-        let min_ns = *timings_ns.iter().min().unwrap_or(&0);
-        let max_ns = *timings_ns.iter().max().unwrap_or(&0);
-        let sum: u64 = timings_ns.iter().sum();
-        let mean_ns = sum as f64 / timings_ns.len().max(1) as f64;
+    /// Create a new result labelled with any backend name, e.g. a [`BenchmarkBackend::name`]
+    ///
+    /// [`BenchmarkBackend::name`]: crate::backend::BenchmarkBackend::name
+    pub fn from_timings_named(
+        backend: &str,
+        operation: Operation,
+        workgroup_size: u32,
+        total_threads: u64,
+        ops_per_thread: u32,
+        timings: &[Duration],
+    ) -> Self {
+        // Calculate GOP/s using minimum time (best case) unless overridden
+        Self::unsummarized(
+            backend,
+            operation,
+            workgroup_size,
+            total_threads,
+            ops_per_thread,
+            timings,
+        )
+        .with_statistic(Statistic::default())
+    }
 
-        let variance: f64 = timings_ns
-            .iter()
-            .map(|&t| (t as f64 - mean_ns).powi(2))
-            .sum::<f64>()
-            / timings_ns.len().max(1) as f64;
-        let std_dev_ns = variance.sqrt();
+    /// A result without the throughput statistics, which cost a bootstrap
+    ///
+    /// Runners finish it with [`with_statistic`](Self::with_statistic) after
+    /// the builders that rescale the operation count, so the bootstrap runs once.
+    pub(crate) fn unsummarized(
+        backend: &str,
+        operation: Operation,
+        workgroup_size: u32,
+        total_threads: u64,
+        ops_per_thread: u32,
+        timings: &[Duration],
+    ) -> Self {
+        let (min_ns, max_ns, mean_ns, std_dev_ns) = timing_stats(timings);
 
         let total_operations = total_threads * ops_per_thread as u64;
 
-        // Calculate GOP/s using minimum time (best case)
-        let gops_per_second = if min_ns > 0 {
-            (total_operations as f64) / (min_ns as f64 / 1e9) / 1e9
-        } else {
-            0.0
-        };
-
         Self {
-            backend: backend.name().to_string(),
+            backend: backend.to_string(),
+            device_name: String::new(),
+            device_type: None,
             operation: operation.name().to_string(),
             workgroup_size,
             total_threads,
@@ -77,8 +429,201 @@ impl BenchmarkResult {
             max_ns,
             mean_ns,
             std_dev_ns,
-            gops_per_second,
+            median_ns: 0.0,
+            p5_ns: 0.0,
+            p95_ns: 0.0,
+            cv: 0.0,
+            outliers_rejected: 0,
+            gops_per_second: 0.0,
+            statistic: Statistic::default(),
+            gops_ci_low: 0.0,
+            gops_ci_high: 0.0,
+            samples_ns: timings.iter().map(|d| d.as_nanos() as u64).collect(),
+            calibration: None,
+            sample_offsets_ns: Vec::new(),
+            trend: None,
+            convergence: None,
+            gpu_timing: None,
+            dispatch_overhead: None,
+            independent_chains: 1,
+            dispatches_per_submit: 1,
+            submit_batching: SubmitBatching::default(),
+            startup: None,
+            input_mode: InputMode::default(),
+            buffer_strategy: BufferStrategy::default(),
+            verification: None,
+            suite_entry: None,
+        }
+    }
+
+    /// Derive the robust statistics and drive `gops_per_second` from `statistic`
+    ///
+    /// Bootstraps the GOP/s CI, so call it once the operation count is final.
+    pub fn with_statistic(mut self, statistic: Statistic) -> Self {
+        let sorted = self.sorted_samples();
+        let (kept, rejected) = stats::reject_outliers(&sorted);
+
+        self.median_ns = stats::median(&kept);
+        self.p5_ns = stats::percentile(&kept, 5.0);
+        self.p95_ns = stats::percentile(&kept, 95.0);
+        self.cv = stats::coefficient_of_variation(&kept);
+        self.outliers_rejected = rejected;
+        self.statistic = statistic;
+
+        // The minimum is taken over all samples; the other statistics exclude outliers
+        let samples = stats::samples_for(&sorted, statistic);
+        let total_operations = self.total_operations;
+        self.gops_per_second = gops(total_operations, statistic.evaluate(&samples) as u64);
+        (self.gops_ci_low, self.gops_ci_high) =
+            stats::bootstrap_ci(&samples, statistic, 0.95, |ns| {
+                gops(total_operations, ns as u64)
+            });
+
+        self
+    }
+
+    /// Time (in nanoseconds) of the statistic that drives `gops_per_second`
+    pub fn statistic_ns(&self) -> f64 {
+        let sorted = self.sorted_samples();
+        if sorted.is_empty() {
+            return self.min_ns as f64;
+        }
+        self.statistic
+            .evaluate(&stats::samples_for(&sorted, self.statistic))
+    }
+
+    /// Time of one dispatch by the throughput statistic (in nanoseconds)
+    pub fn per_dispatch_ns(&self) -> f64 {
+        self.statistic_ns() / self.dispatches_per_submit.max(1) as f64
+    }
+
+    /// Workgroups dispatched (total threads over workgroup size)
+    pub fn num_workgroups(&self) -> u64 {
+        self.total_threads / self.workgroup_size.max(1) as u64
+    }
+
+    /// Raw samples in ascending order
+    fn sorted_samples(&self) -> Vec<f64> {
+        let mut sorted: Vec<f64> = self.samples_ns.iter().map(|&t| t as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+
+    /// Record that each sample timed `count` dispatches, scaling the operation count
+    pub fn with_dispatches_per_submit(mut self, count: u32, batching: SubmitBatching) -> Self {
+        self.dispatches_per_submit = count.max(1);
+        self.submit_batching = batching;
+        self.rescale_operations()
+    }
+
+    /// Record that each thread ran `chains` independent chains, scaling the operation count
+    pub fn with_independent_chains(mut self, chains: u32) -> Self {
+        self.independent_chains = chains.max(1);
+        self.rescale_operations()
+    }
+
+    /// Recompute total_operations and scale the throughput derived from it
+    ///
+    /// GOP/s is linear in the operation count, so the CI scales with it and
+    /// needs no new bootstrap.
+    fn rescale_operations(mut self) -> Self {
+        let previous = self.total_operations;
+        self.total_operations = self.total_threads
+            * self.ops_per_thread as u64
+            * self.independent_chains as u64
+            * self.dispatches_per_submit as u64;
+
+        let scale = if previous > 0 {
+            self.total_operations as f64 / previous as f64
+        } else {
+            0.0
+        };
+        self.gops_per_second *= scale;
+        self.gops_ci_low *= scale;
+        self.gops_ci_high *= scale;
+        self
+    }
+
+    /// Record the calibration that chose ops_per_thread
+    pub fn with_calibration(mut self, calibration: Option<Calibration>) -> Self {
+        self.calibration = calibration;
+        self
+    }
+
+    /// Record when each sample started and detect slowdown trends
+    pub fn with_sample_offsets(mut self, offsets: &[Duration]) -> Self {
+        self.sample_offsets_ns = offsets.iter().map(|d| d.as_nanos() as u64).collect();
+
+        let samples: Vec<f64> = self.samples_ns.iter().map(|&t| t as f64).collect();
+        let offsets: Vec<f64> = self.sample_offsets_ns.iter().map(|&t| t as f64).collect();
+        self.trend = Trend::detect(&samples, &offsets);
+        self
+    }
+
+    /// Record how adaptive sampling ended
+    pub fn with_convergence(mut self, convergence: Option<Convergence>) -> Self {
+        self.convergence = convergence;
+        self
+    }
+
+    /// Attach GPU-side timings; ignored if empty (queries unsupported)
+    pub fn with_gpu_timings(mut self, timings: &[Duration]) -> Self {
+        if !timings.is_empty() {
+            self.gpu_timing = Some(GpuTiming::from_timings(self.total_operations, timings));
         }
+        self
+    }
+
+    /// Attach empty-kernel timings and derive overhead-subtracted metrics; ignored if empty
+    pub fn with_dispatch_overhead(mut self, timings: &[Duration]) -> Self {
+        if !timings.is_empty() {
+            let (min_ns, _, mean_ns, _) = timing_stats(timings);
+            let net_ns = (self.statistic_ns() as u64).saturating_sub(min_ns);
+
+            self.dispatch_overhead = Some(DispatchOverhead {
+                min_ns,
+                mean_ns,
+                net_ns,
+                net_gops_per_second: gops(self.total_operations, net_ns),
+            });
+        }
+        self
+    }
+
+    /// Record the operation's startup latency
+    pub fn with_startup_latency(mut self, startup: Option<StartupLatency>) -> Self {
+        self.startup = startup;
+        self
+    }
+
+    /// Record the input mode and the output verification, if any
+    pub fn with_verification(
+        mut self,
+        input_mode: InputMode,
+        verification: Option<Verification>,
+    ) -> Self {
+        self.input_mode = input_mode;
+        self.verification = verification;
+        self
+    }
+
+    /// Record the buffer strategy the benchmark ran with
+    pub fn with_buffer_strategy(mut self, strategy: BufferStrategy) -> Self {
+        self.buffer_strategy = strategy;
+        self
+    }
+
+    /// Tag the result with the suite-file entry that produced it
+    pub fn with_suite_entry(mut self, name: &str) -> Self {
+        self.suite_entry = Some(name.to_string());
+        self
+    }
+
+    /// Throughput with dispatch overhead subtracted, if it was measured
+    pub fn net_gops_per_second(&self) -> Option<f64> {
+        self.dispatch_overhead
+            .as_ref()
+            .map(|o| o.net_gops_per_second)
     }
 
     /// Get minimum time in milliseconds
@@ -99,9 +644,33 @@ pub struct BenchmarkReport {
     pub device_name: String,
     pub device_vendor: String,
 
+    /// Adapter index within the backend, when a specific device was selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_index: Option<usize>,
+
+    /// Device class of the device the report was measured on
+    #[serde(default)]
+    pub device_type: Option<DeviceType>,
+
+    /// Sentinel drift between the start and end of the suite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift: Option<Drift>,
+
     /// All benchmark results
     pub results: Vec<BenchmarkResult>,
 
+    /// Occupancy knee of each operation, from an `occupancy` run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub occupancy: Vec<Occupancy>,
+
+    /// Streaming bandwidth of each kernel and buffer size, from a `stream` run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub streams: Vec<StreamResult>,
+
+    /// Host-device transfer bandwidth and latency, from a `transfer` run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transfers: Vec<TransferResult>,
+
     /// Timestamp of the report
     pub timestamp: String,
 }
@@ -111,15 +680,49 @@ impl BenchmarkReport {
         Self {
             device_name,
             device_vendor,
+            device_index: None,
+            device_type: None,
+            drift: None,
             results: Vec::new(),
+            occupancy: Vec::new(),
+            streams: Vec::new(),
+            transfers: Vec::new(),
             timestamp: chrono_lite_timestamp(),
         }
     }
 
-    pub fn add_result(&mut self, result: BenchmarkResult) {
+    pub fn add_result(&mut self, mut result: BenchmarkResult) {
+        if result.device_name.is_empty() {
+            result.device_name = self.device_name.clone();
+        }
+        if result.device_type.is_none() {
+            result.device_type = self.device_type;
+        }
         self.results.push(result);
     }
 
+    /// Short label identifying this report's backend and device
+    pub fn label(&self) -> String {
+        let label = match self.device_index {
+            Some(index) => format!("{} #{}", self.device_vendor, index),
+            None => self.device_vendor.clone(),
+        };
+        if self.is_software() {
+            format!("{} [CPU]", label)
+        } else {
+            label
+        }
+    }
+
+    /// Returns true if any result came from a software (CPU) adapter
+    pub fn is_software(&self) -> bool {
+        self.device_type.is_some_and(|t| t.is_software())
+            || self
+                .results
+                .iter()
+                .any(|r| r.device_type.is_some_and(|t| t.is_software()))
+    }
+
     /// Calculate overhead of emulated vs native u64 addition
     pub fn u64_overhead(&self) -> Option<f64> {
         let native = self
@@ -147,3 +750,169 @@ fn chrono_lite_timestamp() -> String {
         .unwrap_or_default();
     format!("{}", now.as_secs())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(timings_ns: &[u64]) -> BenchmarkResult {
+        let timings: Vec<Duration> = timings_ns
+            .iter()
+            .map(|&ns| Duration::from_nanos(ns))
+            .collect();
+        // 1000 threads x 10 ops
+        BenchmarkResult::from_timings(Backend::WebGPU, Operation::U32Add, 100, 1000, 10, &timings)
+    }
+
+    fn offsets(n: usize) -> Vec<f64> {
+        (0..n).map(|i| i as f64 * 1e6).collect()
+    }
+
+    #[test]
+    fn trend_flags_a_steady_slowdown_as_throttling() {
+        // 20% slower from first to last sample
+        let samples: Vec<f64> = (0..20)
+            .map(|i| 1e6 * (1.0 + 0.2 * i as f64 / 19.0))
+            .collect();
+        let trend = Trend::detect(&samples, &offsets(20)).unwrap();
+        assert!((trend.relative_change - 0.2).abs() < 1e-9);
+        assert!(trend.kendall_tau > 0.99);
+        assert!(trend.throttling);
+    }
+
+    #[test]
+    fn trend_ignores_small_or_improving_changes() {
+        let small: Vec<f64> = (0..20)
+            .map(|i| 1e6 * (1.0 + 0.01 * i as f64 / 19.0))
+            .collect();
+        assert!(!Trend::detect(&small, &offsets(20)).unwrap().throttling);
+
+        let faster: Vec<f64> = (0..20)
+            .map(|i| 1e6 * (1.2 - 0.2 * i as f64 / 19.0))
+            .collect();
+        let trend = Trend::detect(&faster, &offsets(20)).unwrap();
+        assert!(trend.kendall_tau < 0.0);
+        assert!(!trend.throttling);
+    }
+
+    #[test]
+    fn trend_needs_three_aligned_samples() {
+        assert!(Trend::detect(&[1.0, 2.0], &offsets(2)).is_none());
+        assert!(Trend::detect(&[1.0, 2.0, 3.0], &offsets(4)).is_none());
+    }
+
+    #[test]
+    fn dispatch_overhead_is_subtracted_from_the_statistic() {
+        let result = result(&[10_000, 12_000])
+            .with_dispatch_overhead(&[Duration::from_nanos(2_000), Duration::from_nanos(4_000)]);
+        let overhead = result.dispatch_overhead.unwrap();
+        assert_eq!(overhead.min_ns, 2_000);
+        assert_eq!(overhead.mean_ns, 3_000.0);
+        assert_eq!(overhead.net_ns, 8_000);
+        assert!((overhead.net_gops_per_second - 10_000.0 / 8_000.0).abs() < 1e-12);
+        assert!((result.gops_per_second - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn dispatch_overhead_never_goes_negative() {
+        let overhead = result(&[1_000])
+            .with_dispatch_overhead(&[Duration::from_nanos(5_000)])
+            .dispatch_overhead
+            .unwrap();
+        assert_eq!(overhead.net_ns, 0);
+        assert_eq!(overhead.net_gops_per_second, 0.0);
+
+        assert!(result(&[1_000])
+            .with_dispatch_overhead(&[])
+            .dispatch_overhead
+            .is_none());
+    }
+
+    #[test]
+    fn software_reports_are_labelled_cpu() {
+        let mut report = BenchmarkReport::new("llvmpipe".to_string(), "WebGPU".to_string());
+        report.device_index = Some(1);
+        report.add_result(result(&[1_000]));
+        assert!(!report.is_software());
+        assert_eq!(report.label(), "WebGPU #1");
+
+        report.device_type = Some(DeviceType::Cpu);
+        assert!(report.is_software());
+        assert_eq!(report.label(), "WebGPU #1 [CPU]");
+    }
+
+    #[test]
+    fn results_inherit_the_report_device() {
+        let mut report = BenchmarkReport::new("llvmpipe".to_string(), "WebGPU".to_string());
+        report.device_type = Some(DeviceType::Cpu);
+        report.add_result(result(&[1_000]));
+        assert_eq!(report.results[0].device_name, "llvmpipe");
+        assert_eq!(report.results[0].device_type, Some(DeviceType::Cpu));
+
+        // A software result marks the whole report, whatever the report's own type
+        let mut mixed = BenchmarkReport::new("GPU".to_string(), "WebGPU".to_string());
+        mixed.device_type = Some(DeviceType::DiscreteGpu);
+        mixed.results.push(report.results[0].clone());
+        assert!(mixed.is_software());
+    }
+
+    #[test]
+    fn gpu_timing_uses_the_fastest_sample() {
+        let timings = [2_000, 4_000, 6_000].map(Duration::from_nanos);
+        let gpu = GpuTiming::from_timings(8_000, &timings);
+        assert_eq!((gpu.min_ns, gpu.max_ns), (2_000, 6_000));
+        assert_eq!(gpu.mean_ns, 4_000.0);
+        assert!((gpu.std_dev_ns - (8_000_000.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!(gpu.gops_per_second, 4.0);
+        assert_eq!(gpu.min_ms(), 0.002);
+
+        let empty = GpuTiming::from_timings(8_000, &[]);
+        assert_eq!(empty.min_ns, 0);
+        assert_eq!(empty.gops_per_second, 0.0);
+    }
+
+    #[test]
+    fn stage_latency_is_in_milliseconds() {
+        let stages = StageLatency::new(
+            Duration::from_micros(1_500),
+            Duration::from_millis(3),
+            Duration::from_micros(250),
+        );
+        assert_eq!(stages.shader_module_ms, 1.5);
+        assert_eq!(stages.pipeline_ms, 3.0);
+        assert_eq!(stages.first_dispatch_ms, 0.25);
+        assert_eq!(stages.total_ms(), 4.75);
+    }
+
+    #[test]
+    fn batched_dispatches_scale_operations_not_time() {
+        let single = result(&[10_000]);
+        let batched = result(&[10_000]).with_dispatches_per_submit(4, SubmitBatching::SinglePass);
+        assert_eq!(batched.total_operations, 4 * single.total_operations);
+        assert!((batched.gops_per_second - 4.0 * single.gops_per_second).abs() < 1e-12);
+        assert_eq!(batched.per_dispatch_ns(), 2_500.0);
+        assert_eq!(single.per_dispatch_ns(), 10_000.0);
+
+        // Zero is treated as one dispatch
+        let zero = result(&[10_000]).with_dispatches_per_submit(0, SubmitBatching::SinglePass);
+        assert_eq!(zero.dispatches_per_submit, 1);
+        assert_eq!(zero.total_operations, single.total_operations);
+    }
+
+    #[test]
+    fn rescaling_matches_a_fresh_summary() {
+        let timings: Vec<u64> = (0..20).map(|i| 10_000 + (i % 5) * 250).collect();
+        let rescaled = result(&timings)
+            .with_statistic(Statistic::Median)
+            .with_independent_chains(2)
+            .with_dispatches_per_submit(4, SubmitBatching::SinglePass);
+        // with_statistic reruns the bootstrap on the rescaled operation count
+        let fresh = rescaled.clone().with_statistic(Statistic::Median);
+
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * b.abs();
+        assert!(close(rescaled.gops_per_second, fresh.gops_per_second));
+        assert!(close(rescaled.gops_ci_low, fresh.gops_ci_low));
+        assert!(close(rescaled.gops_ci_high, fresh.gops_ci_high));
+        assert!(rescaled.gops_ci_low < rescaled.gops_ci_high);
+    }
+}
@@ -0,0 +1,157 @@
+//! Fixed-count and adaptive collection of timing samples
+
+use crate::config::BenchmarkConfig;
+use crate::results::Convergence;
+use crate::stats;
+use crate::BenchmarkError;
+use std::time::{Duration, Instant};
+
+/// Fewest samples before the CI is first checked
+const MIN_ADAPTIVE_SAMPLES: usize = 5;
+
+/// Timed samples of one operation
+#[derive(Debug, Default)]
+pub struct Samples {
+    /// Duration of each sample
+    pub timings: Vec<Duration>,
+
+    /// When each sample started, relative to the first
+    pub offsets: Vec<Duration>,
+
+    /// How adaptive sampling ended, when enabled
+    pub convergence: Option<Convergence>,
+}
+
+/// Collect timed samples by calling `sample` until the config's stopping rule is met
+///
+/// Without adaptive sampling this takes exactly `measurement_iterations` samples.
+/// Otherwise it takes at least that many and stops once the relative 95% CI on
+/// throughput reaches the target, the time budget runs out or the sample cap is hit.
+pub fn collect_samples(
+    config: &BenchmarkConfig,
+    mut sample: impl FnMut() -> Result<Duration, BenchmarkError>,
+) -> Result<Samples, BenchmarkError> {
+    let start = Instant::now();
+    let mut offsets = Vec::new();
+    let mut sample = || {
+        offsets.push(start.elapsed());
+        sample()
+    };
+
+    let Some(adaptive) = &config.adaptive else {
+        let timings = (0..config.measurement_iterations)
+            .map(|_| sample())
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(Samples {
+            timings,
+            offsets,
+            convergence: None,
+        });
+    };
+
+    let max_samples = (adaptive.max_samples as usize).max(1);
+    let mut timings = Vec::new();
+    let mut next_check = (config.measurement_iterations as usize).max(MIN_ADAPTIVE_SAMPLES);
+    let mut relative_ci = f64::INFINITY;
+
+    loop {
+        timings.push(sample()?);
+
+        let exhausted = timings.len() >= max_samples || start.elapsed() >= adaptive.time_budget;
+        if timings.len() >= next_check || exhausted {
+            let samples_ns: Vec<f64> = timings.iter().map(|d| d.as_nanos() as f64).collect();
+            relative_ci = stats::relative_ci_half_width(&samples_ns, config.statistic);
+
+            // Bootstrapping is not free, so re-check after ~10% more samples
+            next_check = timings.len() + (timings.len() / 10).max(1);
+        }
+
+        if relative_ci <= adaptive.target_relative_ci || exhausted {
+            break;
+        }
+    }
+
+    let convergence = Convergence {
+        target_relative_ci: adaptive.target_relative_ci,
+        relative_ci,
+        converged: relative_ci <= adaptive.target_relative_ci,
+        samples: timings.len(),
+        elapsed_ms: start.elapsed().as_secs_f64() * 1e3,
+    };
+
+    Ok(Samples {
+        timings,
+        offsets,
+        convergence: Some(convergence),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AdaptiveSampling;
+    use crate::stats::Statistic;
+
+    fn adaptive(max_samples: u32) -> BenchmarkConfig {
+        BenchmarkConfig::default()
+            .with_iterations(3)
+            .with_adaptive_sampling(AdaptiveSampling {
+                target_relative_ci: 0.01,
+                time_budget: Duration::from_secs(60),
+                max_samples,
+            })
+    }
+
+    /// Alternates between 1 and 3 microseconds, so the median's CI never narrows to 1%
+    fn noisy() -> impl FnMut() -> Result<Duration, BenchmarkError> {
+        let mut i = 0u64;
+        move || {
+            i += 1;
+            Ok(Duration::from_micros(1 + 2 * (i % 2)))
+        }
+    }
+
+    #[test]
+    fn fixed_count_without_adaptive_sampling() {
+        let config = BenchmarkConfig::default().with_iterations(7);
+        let samples = collect_samples(&config, || Ok(Duration::from_micros(5))).unwrap();
+        assert_eq!(samples.timings.len(), 7);
+        assert_eq!(samples.offsets.len(), 7);
+        assert!(samples.offsets.windows(2).all(|w| w[0] <= w[1]));
+        assert!(samples.convergence.is_none());
+    }
+
+    #[test]
+    fn steady_samples_converge_at_the_first_check() {
+        let samples = collect_samples(&adaptive(1000), || Ok(Duration::from_micros(5))).unwrap();
+        let convergence = samples.convergence.unwrap();
+        assert!(convergence.converged);
+        assert_eq!(convergence.samples, MIN_ADAPTIVE_SAMPLES);
+        assert_eq!(samples.timings.len(), MIN_ADAPTIVE_SAMPLES);
+    }
+
+    #[test]
+    fn noisy_samples_stop_at_the_cap() {
+        let config = adaptive(40).with_statistic(Statistic::Median);
+        let samples = collect_samples(&config, noisy()).unwrap();
+        let convergence = samples.convergence.unwrap();
+        assert!(!convergence.converged);
+        assert_eq!(convergence.samples, 40);
+        assert!(convergence.relative_ci > convergence.target_relative_ci);
+    }
+
+    #[test]
+    fn sample_errors_are_returned() {
+        let mut calls = 0;
+        let result = collect_samples(&adaptive(1000), || {
+            calls += 1;
+            if calls == 3 {
+                Err(BenchmarkError::Execution("lost device".to_string()))
+            } else {
+                Ok(Duration::from_micros(5))
+            }
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+}
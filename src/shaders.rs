@@ -0,0 +1,273 @@
+//! Embedded WGSL benchmark kernels, shared by the WebGPU runner and naga translation
+
+use crate::config::{BenchmarkConfig, ParamsBinding};
+use crate::stream::StreamOperation;
+use crate::{BenchmarkError, Operation};
+
+/// Supported numbers of independent accumulator chains per thread
+pub const CHAIN_COUNTS: [u32; 4] = [1, 2, 4, 8];
+
+/// Declarations every WGSL kernel starts from (64 threads, one chain)
+const WORKGROUP_SIZE_DECLARATION: &str = "const WORKGROUP_SIZE: u32 = 64u;";
+const CHAINS_DECLARATION: &str = "const CHAINS: u32 = 1u;";
+const PARAMS_DECLARATION: &str = "@group(0) @binding(2) var<uniform> params: BenchParams;";
+
+/// Compile-time parameters substituted into a WGSL kernel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KernelSpec {
+    /// Threads per workgroup (`@workgroup_size`)
+    pub workgroup_size: u32,
+
+    /// Independent accumulator chains per thread
+    pub independent_chains: u32,
+
+    /// Address space of the `params` binding
+    pub params: ParamsBinding,
+}
+
+impl Default for KernelSpec {
+    fn default() -> Self {
+        Self {
+            workgroup_size: 64,
+            independent_chains: 1,
+            params: ParamsBinding::Uniform,
+        }
+    }
+}
+
+impl KernelSpec {
+    pub fn from_config(config: &BenchmarkConfig) -> Self {
+        Self {
+            workgroup_size: config.workgroup_size,
+            independent_chains: config.independent_chains,
+            params: config.buffer_strategy.params,
+        }
+    }
+
+    /// Entry point of an operation's kernel for this spec
+    ///
+    /// One chain runs the scalar kernel; more run its `_chains` variant, whose
+    /// accumulators are arrays of `CHAINS`.
+    pub fn entry_point(&self, operation: Operation) -> String {
+        match self.independent_chains {
+            1 => wgsl_entry_point(operation).to_string(),
+            _ => format!("{}_chains", wgsl_entry_point(operation)),
+        }
+    }
+}
+
+/// WGSL source for an operation's benchmark kernel, if one exists
+pub fn wgsl_source(operation: Operation) -> Option<&'static str> {
+    match operation {
+        Operation::U32Add => Some(include_str!("../shaders/wgsl/bench_u32_add.wgsl")),
+        Operation::U64AddNative => None, // WGSL has no native u64
+        Operation::U64AddEmulated => Some(include_str!("../shaders/wgsl/bench_u64_add.wgsl")),
+        Operation::Bn254FieldMul => {
+            Some(include_str!("../shaders/wgsl/bench_bn254_field_mul.wgsl"))
+        }
+        Operation::Bn254FieldAdd => {
+            Some(include_str!("../shaders/wgsl/bench_bn254_field_add.wgsl"))
+        }
+        Operation::MersenneFieldAdd => {
+            Some(include_str!("../shaders/wgsl/bench_m31_field_add.wgsl"))
+        }
+        Operation::MersenneFieldMul => {
+            Some(include_str!("../shaders/wgsl/bench_m31_field_mul.wgsl"))
+        }
+    }
+}
+
+/// WGSL source for a streaming kernel (entry point [`StreamOperation::entry_point`])
+pub fn stream_wgsl_source(operation: StreamOperation) -> &'static str {
+    match operation {
+        StreamOperation::Copy => include_str!("../shaders/wgsl/stream_copy.wgsl"),
+        StreamOperation::M31VecAdd => include_str!("../shaders/wgsl/stream_m31_vec_add.wgsl"),
+        StreamOperation::M31VecMul => include_str!("../shaders/wgsl/stream_m31_vec_mul.wgsl"),
+        StreamOperation::Bn254VecAdd => include_str!("../shaders/wgsl/stream_bn254_vec_add.wgsl"),
+        StreamOperation::Bn254VecMul => include_str!("../shaders/wgsl/stream_bn254_vec_mul.wgsl"),
+    }
+}
+
+/// Map operation to WGSL entry point name
+pub fn wgsl_entry_point(operation: Operation) -> &'static str {
+    match operation {
+        Operation::U32Add => "bench_u32_add",
+        Operation::U64AddNative => "bench_u64_add", // Not available in WebGPU
+        Operation::U64AddEmulated => "bench_u64_add",
+        Operation::Bn254FieldMul => "bench_bn254_field_mul",
+        Operation::Bn254FieldAdd => "bench_bn254_field_add",
+        Operation::MersenneFieldAdd => "bench_m31_field_add",
+        Operation::MersenneFieldMul => "bench_m31_field_mul",
+    }
+}
+
+/// Check that `chains` is one of [`CHAIN_COUNTS`]
+pub fn validate_chains(chains: u32) -> Result<(), BenchmarkError> {
+    if CHAIN_COUNTS.contains(&chains) {
+        Ok(())
+    } else {
+        Err(BenchmarkError::ShaderCompilation(format!(
+            "independent_chains must be one of {:?}, got {}",
+            CHAIN_COUNTS, chains
+        )))
+    }
+}
+
+/// Specialize a WGSL kernel to `spec`'s workgroup size, chain count and params binding
+pub fn specialize(source: &str, spec: KernelSpec) -> Result<String, BenchmarkError> {
+    validate_chains(spec.independent_chains)?;
+    if !source.contains(CHAINS_DECLARATION) {
+        return Err(BenchmarkError::ShaderCompilation(
+            "Kernel does not declare CHAINS".to_string(),
+        ));
+    }
+    if !source.contains(PARAMS_DECLARATION) {
+        return Err(BenchmarkError::ShaderCompilation(
+            "Kernel does not declare params as a uniform at binding 2".to_string(),
+        ));
+    }
+    let source = specialize_workgroup_size(source, spec.workgroup_size)?.replace(
+        CHAINS_DECLARATION,
+        &format!("const CHAINS: u32 = {}u;", spec.independent_chains),
+    );
+    Ok(match spec.params {
+        ParamsBinding::Uniform => source,
+        ParamsBinding::Storage => source.replace(
+            PARAMS_DECLARATION,
+            "@group(0) @binding(2) var<storage, read> params: BenchParams;",
+        ),
+        ParamsBinding::PushConstants => source.replace(
+            PARAMS_DECLARATION,
+            "var<push_constant> params: BenchParams;",
+        ),
+    })
+}
+
+/// Specialize a WGSL kernel's `WORKGROUP_SIZE` (streaming kernels have no chains)
+pub fn specialize_workgroup_size(
+    source: &str,
+    workgroup_size: u32,
+) -> Result<String, BenchmarkError> {
+    if workgroup_size == 0 {
+        return Err(BenchmarkError::ShaderCompilation(
+            "workgroup_size must be at least 1".to_string(),
+        ));
+    }
+    if !source.contains(WORKGROUP_SIZE_DECLARATION) {
+        return Err(BenchmarkError::ShaderCompilation(
+            "Kernel does not declare WORKGROUP_SIZE".to_string(),
+        ));
+    }
+    Ok(source.replace(
+        WORKGROUP_SIZE_DECLARATION,
+        &format!("const WORKGROUP_SIZE: u32 = {}u;", workgroup_size),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kernels() -> Vec<&'static str> {
+        let mut sources: Vec<&str> = Operation::all()
+            .into_iter()
+            .filter_map(wgsl_source)
+            .collect();
+        sources.extend(StreamOperation::all().into_iter().map(stream_wgsl_source));
+        sources
+    }
+
+    #[test]
+    fn every_kernel_declares_its_workgroup_size() {
+        for source in kernels() {
+            let specialized = specialize_workgroup_size(source, 256).unwrap();
+            assert!(specialized.contains("const WORKGROUP_SIZE: u32 = 256u;"));
+            assert!(!specialized.contains(WORKGROUP_SIZE_DECLARATION));
+            assert!(specialized.contains("@workgroup_size(WORKGROUP_SIZE)"));
+        }
+    }
+
+    #[test]
+    fn workgroup_size_must_be_positive_and_declared() {
+        let source = wgsl_source(Operation::U32Add).unwrap();
+        assert!(specialize_workgroup_size(source, 0).is_err());
+        assert!(
+            specialize_workgroup_size("@compute @workgroup_size(64) fn main() {}", 128).is_err()
+        );
+    }
+
+    #[test]
+    fn default_spec_leaves_the_source_unchanged() {
+        for operation in Operation::all() {
+            if let Some(source) = wgsl_source(operation) {
+                assert_eq!(specialize(source, KernelSpec::default()).unwrap(), source);
+            }
+        }
+    }
+
+    #[test]
+    fn chain_counts_are_validated() {
+        for chains in CHAIN_COUNTS {
+            assert!(validate_chains(chains).is_ok());
+        }
+        assert!(validate_chains(0).is_err());
+        assert!(validate_chains(3).is_err());
+        assert!(validate_chains(16).is_err());
+    }
+
+    #[test]
+    fn one_chain_runs_the_scalar_entry_point() {
+        let spec = |independent_chains| KernelSpec {
+            independent_chains,
+            ..KernelSpec::default()
+        };
+        assert_eq!(spec(1).entry_point(Operation::U32Add), "bench_u32_add");
+        assert_eq!(
+            spec(4).entry_point(Operation::Bn254FieldMul),
+            "bench_bn254_field_mul_chains"
+        );
+    }
+
+    #[test]
+    fn chains_are_substituted_into_every_kernel() {
+        for operation in Operation::all() {
+            let Some(source) = wgsl_source(operation) else {
+                continue;
+            };
+            let spec = KernelSpec {
+                independent_chains: 8,
+                ..KernelSpec::default()
+            };
+            let specialized = specialize(source, spec).unwrap();
+            assert!(specialized.contains("const CHAINS: u32 = 8u;"));
+            assert!(specialized.contains(&format!("fn {}(", spec.entry_point(operation))));
+            assert!(specialized.contains(&format!("fn {}(", wgsl_entry_point(operation))));
+        }
+    }
+
+    #[test]
+    fn storage_params_bind_a_read_only_buffer() {
+        let source = wgsl_source(Operation::U32Add).unwrap();
+        let spec = KernelSpec {
+            params: ParamsBinding::Storage,
+            ..KernelSpec::default()
+        };
+        let specialized = specialize(source, spec).unwrap();
+        assert!(
+            specialized.contains("@group(0) @binding(2) var<storage, read> params: BenchParams;")
+        );
+        assert!(!specialized.contains("var<uniform>"));
+    }
+
+    #[test]
+    fn push_constant_params_replace_the_binding() {
+        let source = wgsl_source(Operation::MersenneFieldAdd).unwrap();
+        let spec = KernelSpec {
+            params: ParamsBinding::PushConstants,
+            ..KernelSpec::default()
+        };
+        let specialized = specialize(source, spec).unwrap();
+        assert!(specialized.contains("var<push_constant> params: BenchParams;"));
+        assert!(!specialized.contains("@binding(2)"));
+    }
+}
@@ -0,0 +1,633 @@
+//! Robust summary statistics for timing samples
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Modified z-score above which a sample is rejected as an outlier (Iglewicz & Hoaglin)
+pub const MAD_OUTLIER_THRESHOLD: f64 = 3.5;
+
+/// Scale factor making the MAD a consistent estimator of the standard deviation
+const MAD_SCALE: f64 = 1.4826;
+
+/// Number of bootstrap resamples used for confidence intervals
+pub const BOOTSTRAP_RESAMPLES: usize = 2000;
+
+/// Fixed seed so confidence intervals are reproducible for the same samples
+const BOOTSTRAP_SEED: u64 = 0x5EED_CAFE;
+
+/// Statistic of the timing samples that drives the reported throughput
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Statistic {
+    /// Fastest sample (best case)
+    #[default]
+    Min,
+    /// Median of the samples after outlier rejection
+    Median,
+    /// Mean of the samples after outlier rejection
+    Mean,
+}
+
+impl Statistic {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Statistic::Min => "min",
+            Statistic::Median => "median",
+            Statistic::Mean => "mean",
+        }
+    }
+
+    /// Look up a statistic by its `--statistic` name
+    pub fn from_name(name: &str) -> Option<Statistic> {
+        Self::all().into_iter().find(|s| s.name() == name)
+    }
+
+    pub fn all() -> Vec<Statistic> {
+        vec![Statistic::Min, Statistic::Median, Statistic::Mean]
+    }
+
+    /// Evaluate this statistic on samples sorted in ascending order
+    pub fn evaluate(&self, sorted: &[f64]) -> f64 {
+        match self {
+            Statistic::Min => sorted.first().copied().unwrap_or(0.0),
+            Statistic::Median => median(sorted),
+            Statistic::Mean => mean(sorted),
+        }
+    }
+}
+
+impl std::fmt::Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Arithmetic mean (0 for no samples)
+pub fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len().max(1) as f64
+}
+
+/// Population standard deviation
+pub fn std_dev(samples: &[f64]) -> f64 {
+    let m = mean(samples);
+    let variance =
+        samples.iter().map(|x| (x - m).powi(2)).sum::<f64>() / samples.len().max(1) as f64;
+    variance.sqrt()
+}
+
+/// Median of samples sorted in ascending order
+pub fn median(sorted: &[f64]) -> f64 {
+    percentile(sorted, 50.0)
+}
+
+/// Linearly interpolated percentile (0-100) of samples sorted in ascending order
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        n => {
+            let rank = (p / 100.0).clamp(0.0, 1.0) * (n - 1) as f64;
+            let lo = rank.floor() as usize;
+            let hi = rank.ceil() as usize;
+            sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+        }
+    }
+}
+
+/// Median absolute deviation of samples sorted in ascending order
+pub fn mad(sorted: &[f64]) -> f64 {
+    let m = median(sorted);
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - m).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    median(&deviations)
+}
+
+/// Split sorted samples into those kept and the number rejected by the MAD rule
+///
+/// Nothing is rejected when the MAD is zero (e.g. most samples identical).
+pub fn reject_outliers(sorted: &[f64]) -> (Vec<f64>, usize) {
+    let m = median(sorted);
+    let scaled_mad = MAD_SCALE * mad(sorted);
+    if scaled_mad <= 0.0 {
+        return (sorted.to_vec(), 0);
+    }
+
+    let kept: Vec<f64> = sorted
+        .iter()
+        .copied()
+        .filter(|x| (x - m).abs() / scaled_mad <= MAD_OUTLIER_THRESHOLD)
+        .collect();
+    let rejected = sorted.len() - kept.len();
+    (kept, rejected)
+}
+
+/// Coefficient of variation (standard deviation / mean)
+pub fn coefficient_of_variation(samples: &[f64]) -> f64 {
+    let m = mean(samples);
+    if m > 0.0 {
+        std_dev(samples) / m
+    } else {
+        0.0
+    }
+}
+
+/// Percentile bootstrap confidence interval of `f(statistic(resample))`
+///
+/// `f` maps the statistic to the reported quantity (e.g. time to throughput), so the
+/// interval bounds are ordered even when `f` is decreasing.
+pub fn bootstrap_ci(
+    samples: &[f64],
+    statistic: Statistic,
+    confidence: f64,
+    f: impl Fn(f64) -> f64,
+) -> (f64, f64) {
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+    let mut resample = vec![0.0; samples.len()];
+    let mut estimates: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+        .map(|_| {
+            for x in resample.iter_mut() {
+                *x = samples[rng.gen_range(0..samples.len())];
+            }
+            resample.sort_by(f64::total_cmp);
+            f(statistic.evaluate(&resample))
+        })
+        .collect();
+    estimates.sort_by(f64::total_cmp);
+
+    let tail = (1.0 - confidence) / 2.0 * 100.0;
+    (
+        percentile(&estimates, tail),
+        percentile(&estimates, 100.0 - tail),
+    )
+}
+
+/// Samples a statistic is evaluated over: all of them for `Min`, inliers otherwise
+pub fn samples_for(sorted: &[f64], statistic: Statistic) -> Vec<f64> {
+    match statistic {
+        Statistic::Min => sorted.to_vec(),
+        _ => reject_outliers(sorted).0,
+    }
+}
+
+/// Half-width of the 95% throughput confidence interval relative to the point estimate
+///
+/// Throughput is proportional to 1/time, so the result does not depend on the
+/// operation count. Returns infinity when there are too few samples to tell.
+pub fn relative_ci_half_width(samples_ns: &[f64], statistic: Statistic) -> f64 {
+    let mut sorted = samples_ns.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let samples = samples_for(&sorted, statistic);
+
+    let point = statistic.evaluate(&samples);
+    if samples.len() < 2 || point <= 0.0 {
+        return f64::INFINITY;
+    }
+
+    let (lo, hi) = bootstrap_ci(&samples, statistic, 0.95, |ns| 1.0 / ns.max(1.0));
+    (hi - lo) / 2.0 * point
+}
+
+/// Bootstrap confidence interval of `statistic(b) / statistic(a)`
+///
+/// Both sample sets are resampled independently with the same fixed seed as
+/// [`bootstrap_ci`], so intervals are reproducible.
+pub fn bootstrap_ratio_ci(
+    a: &[f64],
+    b: &[f64],
+    statistic: Statistic,
+    confidence: f64,
+) -> (f64, f64) {
+    if a.is_empty() || b.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+    let mut resample_a = vec![0.0; a.len()];
+    let mut resample_b = vec![0.0; b.len()];
+    let mut estimates: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+        .map(|_| {
+            for (resample, samples) in [(&mut resample_a, a), (&mut resample_b, b)] {
+                for x in resample.iter_mut() {
+                    *x = samples[rng.gen_range(0..samples.len())];
+                }
+                resample.sort_by(f64::total_cmp);
+            }
+            statistic.evaluate(&resample_b) / statistic.evaluate(&resample_a).max(f64::MIN_POSITIVE)
+        })
+        .collect();
+    estimates.sort_by(f64::total_cmp);
+
+    let tail = (1.0 - confidence) / 2.0 * 100.0;
+    (
+        percentile(&estimates, tail),
+        percentile(&estimates, 100.0 - tail),
+    )
+}
+
+/// Sample variance (n - 1 denominator)
+pub fn sample_variance(samples: &[f64]) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(samples);
+    samples.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (samples.len() - 1) as f64
+}
+
+/// Two-sided p-value of Welch's unequal-variance t-test
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> f64 {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    if na < 2.0 || nb < 2.0 {
+        return 1.0;
+    }
+
+    let (va, vb) = (sample_variance(a) / na, sample_variance(b) / nb);
+    let se = (va + vb).sqrt();
+    let diff = mean(a) - mean(b);
+    if se <= 0.0 {
+        return if diff == 0.0 { 1.0 } else { 0.0 };
+    }
+
+    let t = diff / se;
+    let df = (va + vb).powi(2) / (va.powi(2) / (na - 1.0) + vb.powi(2) / (nb - 1.0));
+    student_t_two_sided_p(t, df)
+}
+
+/// Two-sided p-value of the Mann-Whitney U test (normal approximation, tie-corrected)
+pub fn mann_whitney_u_test(a: &[f64], b: &[f64]) -> f64 {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+
+    // Rank the pooled samples, averaging ranks over ties
+    let mut pooled: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let n = pooled.len();
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && pooled[j + 1].0 == pooled[i].0 {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        rank_sum_a += rank * pooled[i..=j].iter().filter(|p| p.1).count() as f64;
+        let ties = (j - i + 1) as f64;
+        tie_term += ties.powi(3) - ties;
+        i = j + 1;
+    }
+
+    let u = rank_sum_a - na * (na + 1.0) / 2.0;
+    let mu = na * nb / 2.0;
+    let n = n as f64;
+    let sigma = (na * nb / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)))).sqrt();
+    if sigma <= 0.0 || !sigma.is_finite() {
+        return 1.0;
+    }
+
+    // Continuity correction towards the mean
+    let z = ((u - mu).abs() - 0.5).max(0.0) / sigma;
+    (2.0 * (1.0 - normal_cdf(z))).min(1.0)
+}
+
+/// Standard normal cumulative distribution function
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, relative error < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let ans = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 {
+        ans
+    } else {
+        2.0 - ans
+    }
+}
+
+/// Two-sided p-value of Student's t distribution with `df` degrees of freedom
+fn student_t_two_sided_p(t: f64, df: f64) -> f64 {
+    regularized_incomplete_beta(df / 2.0, 0.5, df / (df + t * t)).clamp(0.0, 1.0)
+}
+
+/// Natural log of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFS
+        .iter()
+        .enumerate()
+        .fold(1.000000000190015, |acc, (i, c)| {
+            acc + c / (x + 1.0 + i as f64)
+        });
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    // The continued fraction converges fastest on this side of the mean
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz's method)
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 200;
+    const EPSILON: f64 = 3e-14;
+    const TINY: f64 = 1e-300;
+
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+/// Least-squares line through `(x, y)`, returned as `(intercept, slope)`
+pub fn linear_fit(x: &[f64], y: &[f64]) -> (f64, f64) {
+    let (mx, my) = (mean(x), mean(y));
+    let sxx: f64 = x.iter().map(|xi| (xi - mx).powi(2)).sum();
+    let sxy: f64 = x.iter().zip(y).map(|(xi, yi)| (xi - mx) * (yi - my)).sum();
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    (my - slope * mx, slope)
+}
+
+/// Mann-Kendall test for a monotonic trend in a series, returned as `(tau, p_value)`
+///
+/// Tau is positive for an increasing series; the p-value is two-sided.
+pub fn mann_kendall(series: &[f64]) -> (f64, f64) {
+    let n = series.len();
+    if n < 3 {
+        return (0.0, 1.0);
+    }
+
+    let mut s = 0.0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            s += (series[j] - series[i]).signum() * ((series[j] != series[i]) as u8 as f64);
+        }
+    }
+
+    let n = n as f64;
+    let pairs = n * (n - 1.0) / 2.0;
+    let variance = n * (n - 1.0) * (2.0 * n + 5.0) / 18.0;
+
+    // Continuity correction towards zero
+    let z = (s.abs() - 1.0).max(0.0) / variance.sqrt();
+    (s / pairs, (2.0 * (1.0 - normal_cdf(z))).min(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_interpolates_between_ranks() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(median(&sorted), 2.5);
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 100.0), 4.0);
+        assert_eq!(percentile(&sorted, 25.0), 1.75);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+        assert_eq!(percentile(&[7.0], 90.0), 7.0);
+    }
+
+    #[test]
+    fn mad_is_the_median_deviation_from_the_median() {
+        // Median 3, deviations 2, 1, 0, 1, 97 -> MAD 1
+        assert_eq!(mad(&[1.0, 2.0, 3.0, 4.0, 100.0]), 1.0);
+        assert_eq!(mad(&[5.0, 5.0, 5.0]), 0.0);
+    }
+
+    #[test]
+    fn reject_outliers_drops_samples_far_from_the_median() {
+        let sorted = [10.0, 10.5, 11.0, 11.5, 12.0, 50.0];
+        let (kept, rejected) = reject_outliers(&sorted);
+        assert_eq!(rejected, 1);
+        assert_eq!(kept, vec![10.0, 10.5, 11.0, 11.5, 12.0]);
+    }
+
+    #[test]
+    fn reject_outliers_keeps_everything_when_mad_is_zero() {
+        let sorted = [10.0, 10.0, 10.0, 10.0, 99.0];
+        let (kept, rejected) = reject_outliers(&sorted);
+        assert_eq!(rejected, 0);
+        assert_eq!(kept.len(), sorted.len());
+    }
+
+    #[test]
+    fn coefficient_of_variation_of_constant_samples_is_zero() {
+        assert_eq!(coefficient_of_variation(&[3.0, 3.0, 3.0]), 0.0);
+        assert_eq!(coefficient_of_variation(&[]), 0.0);
+    }
+
+    #[test]
+    fn bootstrap_ci_brackets_the_estimate_and_is_reproducible() {
+        let samples: Vec<f64> = (0..50).map(|i| 100.0 + (i % 7) as f64).collect();
+        let (lo, hi) = bootstrap_ci(&samples, Statistic::Mean, 0.95, |x| x);
+        let point = mean(&samples);
+        assert!(
+            lo <= point && point <= hi,
+            "{} not in [{}, {}]",
+            point,
+            lo,
+            hi
+        );
+        assert_eq!(
+            (lo, hi),
+            bootstrap_ci(&samples, Statistic::Mean, 0.95, |x| x)
+        );
+    }
+
+    #[test]
+    fn bootstrap_ci_orders_bounds_under_a_decreasing_map() {
+        let samples: Vec<f64> = (1..=20).map(|i| i as f64).collect();
+        let (lo, hi) = bootstrap_ci(&samples, Statistic::Median, 0.95, |ns| 1.0 / ns);
+        assert!(lo <= hi);
+    }
+
+    #[test]
+    fn bootstrap_ci_of_constant_samples_is_a_point() {
+        assert_eq!(
+            bootstrap_ci(&[4.0; 10], Statistic::Median, 0.95, |x| x),
+            (4.0, 4.0)
+        );
+        assert_eq!(
+            bootstrap_ci(&[], Statistic::Median, 0.95, |x| x),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn samples_for_min_keeps_outliers() {
+        let sorted = [10.0, 10.5, 11.0, 11.5, 12.0, 50.0];
+        assert_eq!(samples_for(&sorted, Statistic::Min).len(), 6);
+        assert_eq!(samples_for(&sorted, Statistic::Median).len(), 5);
+    }
+
+    #[test]
+    fn normal_cdf_matches_known_values() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.96) - 0.025).abs() < 1e-4);
+    }
+
+    #[test]
+    fn welch_t_test_separates_distinct_means() {
+        let a = [10.0, 10.2, 9.9, 10.1, 10.0, 9.8];
+        let b = [12.0, 12.1, 11.9, 12.2, 12.0, 11.8];
+        assert!(welch_t_test(&a, &b) < 0.001);
+        assert!(welch_t_test(&a, &a) > 0.99);
+    }
+
+    #[test]
+    fn welch_t_test_matches_a_reference_p_value() {
+        // t = -1.5, df = 8 (equal sizes and variances); two-sided p = 0.1720
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [2.5, 3.5, 4.5, 5.5, 6.5];
+        assert!((welch_t_test(&a, &b) - 0.1720).abs() < 1e-3);
+    }
+
+    #[test]
+    fn welch_t_test_without_variance() {
+        assert_eq!(welch_t_test(&[1.0, 1.0], &[1.0, 1.0]), 1.0);
+        assert_eq!(welch_t_test(&[1.0, 1.0], &[2.0, 2.0]), 0.0);
+        assert_eq!(welch_t_test(&[1.0], &[2.0, 3.0]), 1.0);
+    }
+
+    #[test]
+    fn mann_whitney_separates_disjoint_samples() {
+        let a: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let b: Vec<f64> = (20..30).map(|i| i as f64).collect();
+        assert!(mann_whitney_u_test(&a, &b) < 0.001);
+        assert!(mann_whitney_u_test(&a, &a) > 0.9);
+        assert_eq!(mann_whitney_u_test(&[5.0; 4], &[5.0; 4]), 1.0);
+    }
+
+    #[test]
+    fn bootstrap_ratio_ci_brackets_the_ratio() {
+        let a: Vec<f64> = (0..30).map(|i| 100.0 + (i % 5) as f64).collect();
+        let b: Vec<f64> = a.iter().map(|x| x * 2.0).collect();
+        let (lo, hi) = bootstrap_ratio_ci(&a, &b, Statistic::Median, 0.95);
+        assert!(lo <= 2.0 && 2.0 <= hi, "2 not in [{}, {}]", lo, hi);
+        assert!(hi - lo < 0.2);
+        assert_eq!(
+            bootstrap_ratio_ci(&[], &b, Statistic::Median, 0.95),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn linear_fit_recovers_a_line() {
+        let x = [0.0, 1.0, 2.0, 3.0];
+        let y: Vec<f64> = x.iter().map(|x| 2.0 + 0.5 * x).collect();
+        assert_eq!(linear_fit(&x, &y), (2.0, 0.5));
+        assert_eq!(linear_fit(&[1.0, 1.0], &[3.0, 5.0]), (4.0, 0.0));
+    }
+
+    #[test]
+    fn mann_kendall_detects_a_monotonic_series() {
+        let rising: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let (tau, p) = mann_kendall(&rising);
+        assert_eq!(tau, 1.0);
+        // S = 45, Var(S) = 125, z = 44 / sqrt(125)
+        assert!((p - 8.3e-5).abs() < 1e-5, "p = {}", p);
+
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        assert_eq!(mann_kendall(&falling), (-1.0, p));
+    }
+
+    #[test]
+    fn mann_kendall_finds_no_trend_in_flat_or_short_series() {
+        let (tau, p) = mann_kendall(&[5.0; 10]);
+        assert_eq!(tau, 0.0);
+        assert!(p > 0.999_999);
+        assert_eq!(mann_kendall(&[1.0, 2.0]), (0.0, 1.0));
+
+        let (tau, p) = mann_kendall(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0]);
+        assert!(tau.abs() < 0.5);
+        assert!(p > 0.05);
+    }
+}
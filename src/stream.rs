@@ -0,0 +1,420 @@
+//! Memory-bandwidth-bound streaming kernels
+//!
+//! Each kernel reads one element from each of two large buffers, applies one
+//! field operation and writes the result to a third, so throughput is bounded
+//! by memory rather than arithmetic. `copy` moves bytes with no arithmetic and
+//! is the bandwidth ceiling the field streams are compared against.
+
+use crate::backend::{BackendCapabilities, BenchmarkBackend, DispatchGrid};
+use crate::config::BenchmarkConfig;
+use crate::stats::{self, Statistic};
+use crate::{BenchmarkError, Operation};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Bytes in a megabyte, as used by `--sizes`
+pub const MB: u64 = 1 << 20;
+
+/// Share of copy bandwidth above which a stream counts as memory-bound
+pub const MEMORY_BOUND_FRACTION: f64 = 0.8;
+
+/// Streaming kernels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamOperation {
+    Copy,
+    M31VecAdd,
+    M31VecMul,
+    Bn254VecAdd,
+    Bn254VecMul,
+}
+
+impl StreamOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StreamOperation::Copy => "copy",
+            StreamOperation::M31VecAdd => "m31_vec_add",
+            StreamOperation::M31VecMul => "m31_vec_mul",
+            StreamOperation::Bn254VecAdd => "bn254_vec_add",
+            StreamOperation::Bn254VecMul => "bn254_vec_mul",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            StreamOperation::Copy => "Buffer copy (bandwidth baseline)",
+            StreamOperation::M31VecAdd => "Mersenne (2^31-1) element-wise addition",
+            StreamOperation::M31VecMul => "Mersenne (2^31-1) element-wise multiplication",
+            StreamOperation::Bn254VecAdd => "BN254 element-wise addition",
+            StreamOperation::Bn254VecMul => "BN254 element-wise Montgomery multiplication",
+        }
+    }
+
+    /// Kernel function name, shared by the WGSL entry point and the Metal kernel
+    pub fn entry_point(&self) -> &'static str {
+        match self {
+            StreamOperation::Copy => "stream_copy",
+            StreamOperation::M31VecAdd => "stream_m31_vec_add",
+            StreamOperation::M31VecMul => "stream_m31_vec_mul",
+            StreamOperation::Bn254VecAdd => "stream_bn254_vec_add",
+            StreamOperation::Bn254VecMul => "stream_bn254_vec_mul",
+        }
+    }
+
+    /// Compute-bound operation whose field (and input data) the stream uses
+    pub fn field_operation(&self) -> Operation {
+        match self {
+            StreamOperation::Copy => Operation::U32Add,
+            StreamOperation::M31VecAdd => Operation::MersenneFieldAdd,
+            StreamOperation::M31VecMul => Operation::MersenneFieldMul,
+            StreamOperation::Bn254VecAdd => Operation::Bn254FieldAdd,
+            StreamOperation::Bn254VecMul => Operation::Bn254FieldMul,
+        }
+    }
+
+    /// Bytes of one element (16 limbs of 16 bits, stored as u32, for BN254)
+    pub fn element_bytes(&self) -> u64 {
+        crate::dataset::element_words(self.field_operation()) as u64 * 4
+    }
+
+    /// Buffers read per element: the copy reads `a` only
+    pub fn buffers_read(&self) -> u64 {
+        match self {
+            StreamOperation::Copy => 1,
+            _ => 2,
+        }
+    }
+
+    /// Look up a stream by name
+    pub fn from_name(name: &str) -> Option<StreamOperation> {
+        Self::all().into_iter().find(|op| op.name() == name)
+    }
+
+    pub fn all() -> Vec<StreamOperation> {
+        vec![
+            StreamOperation::Copy,
+            StreamOperation::M31VecAdd,
+            StreamOperation::M31VecMul,
+            StreamOperation::Bn254VecAdd,
+            StreamOperation::Bn254VecMul,
+        ]
+    }
+}
+
+impl std::fmt::Display for StreamOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Element count, buffer size and dispatch grid of one streaming run
+#[derive(Debug, Clone, Copy)]
+pub struct StreamLayout {
+    /// Elements in each buffer (one thread per element)
+    pub elements: u64,
+
+    /// Bytes of each buffer
+    pub buffer_bytes: u64,
+
+    pub workgroup_size: u32,
+    pub grid: DispatchGrid,
+}
+
+impl StreamLayout {
+    /// Fit `requested_bytes` buffers of whole elements and check them against the device
+    pub fn new(
+        operation: StreamOperation,
+        requested_bytes: u64,
+        workgroup_size: u32,
+        capabilities: &BackendCapabilities,
+    ) -> Result<Self, BenchmarkError> {
+        capabilities.check_workgroup_size(workgroup_size)?;
+        if workgroup_size == 0 {
+            return Err(BenchmarkError::Execution(
+                "workgroup_size must be at least 1".to_string(),
+            ));
+        }
+
+        let elements = requested_bytes / operation.element_bytes();
+        if elements == 0 {
+            return Err(BenchmarkError::Execution(format!(
+                "{} byte buffers hold no {} elements",
+                requested_bytes,
+                operation.name()
+            )));
+        }
+        // The kernels take the element count as a u32
+        if elements > u32::MAX as u64 {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "elements per stream buffer",
+                requested: elements,
+                max: u32::MAX as u64,
+            });
+        }
+
+        let buffer_bytes = elements * operation.element_bytes();
+        if buffer_bytes > capabilities.max_buffer_size {
+            return Err(BenchmarkError::LimitExceeded {
+                limit: "max_buffer_size (bytes)",
+                requested: buffer_bytes,
+                max: capabilities.max_buffer_size,
+            });
+        }
+
+        let workgroups = elements.div_ceil(workgroup_size as u64);
+        let grid = DispatchGrid::covering(workgroups, capabilities.max_workgroups_per_dimension)?;
+        Ok(Self {
+            elements,
+            buffer_bytes,
+            workgroup_size,
+            grid,
+        })
+    }
+}
+
+/// Per-sample times of a streaming run
+#[derive(Debug, Clone, Default)]
+pub struct StreamTimings {
+    /// Wall-clock time of each dispatch, submission to completion
+    pub wall: Vec<Duration>,
+
+    /// GPU time of each dispatch; empty if any sample lacked one
+    pub gpu: Vec<Duration>,
+}
+
+/// Bandwidth and throughput of one stream at one buffer size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamResult {
+    pub backend: String,
+
+    #[serde(default)]
+    pub device_name: String,
+
+    pub operation: String,
+    pub workgroup_size: u32,
+
+    /// Elements in each buffer
+    pub elements: u64,
+
+    /// Bytes of each buffer
+    pub buffer_bytes: u64,
+
+    /// Bytes read and written per dispatch
+    pub bytes_moved: u64,
+
+    /// Statistic of the timings the rates are derived from
+    pub statistic: Statistic,
+    pub time_ns: f64,
+    pub min_ns: u64,
+    pub median_ns: f64,
+
+    /// True if the times are GPU timestamps rather than wall-clock
+    pub gpu_timed: bool,
+
+    pub gb_per_second: f64,
+    pub gops_per_second: f64,
+
+    /// GB/s as a fraction of `copy` at the same buffer size, backend and device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copy_fraction: Option<f64>,
+}
+
+impl StreamResult {
+    /// True if the stream reaches most of the copy bandwidth at its buffer size
+    pub fn memory_bound(&self) -> Option<bool> {
+        self.copy_fraction
+            .map(|fraction| fraction >= MEMORY_BOUND_FRACTION)
+    }
+}
+
+/// Time one stream at one buffer size
+pub fn measure(
+    backend: &mut dyn BenchmarkBackend,
+    operation: StreamOperation,
+    requested_bytes: u64,
+    config: &BenchmarkConfig,
+) -> Result<StreamResult, BenchmarkError> {
+    let layout = StreamLayout::new(
+        operation,
+        requested_bytes,
+        config.workgroup_size,
+        &backend.capabilities(),
+    )?;
+    let timings = backend.run_stream(operation, &layout, config)?;
+
+    // GPU time excludes submission latency, which dominates small buffers
+    let gpu_timed = !timings.gpu.is_empty() && timings.gpu.len() == timings.wall.len();
+    let samples = if gpu_timed {
+        &timings.gpu
+    } else {
+        &timings.wall
+    };
+    let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let time_ns = config
+        .statistic
+        .evaluate(&stats::samples_for(&sorted, config.statistic));
+    let bytes_moved = layout.buffer_bytes * (operation.buffers_read() + 1);
+    let (gb_per_second, gops_per_second) = if time_ns > 0.0 {
+        (
+            bytes_moved as f64 / time_ns,
+            layout.elements as f64 / time_ns,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+
+    Ok(StreamResult {
+        backend: backend.name().to_string(),
+        device_name: backend.device_name(),
+        operation: operation.name().to_string(),
+        workgroup_size: layout.workgroup_size,
+        elements: layout.elements,
+        buffer_bytes: layout.buffer_bytes,
+        bytes_moved,
+        statistic: config.statistic,
+        time_ns,
+        min_ns: sorted.first().copied().unwrap_or(0.0) as u64,
+        median_ns: stats::median(&sorted),
+        gpu_timed,
+        gb_per_second,
+        gops_per_second,
+        copy_fraction: None,
+    })
+}
+
+/// Fill in each result's bandwidth relative to the matching `copy` result
+pub fn relate_to_copy(results: &mut [StreamResult]) {
+    let copies: Vec<(String, String, u64, f64)> = results
+        .iter()
+        .filter(|r| r.operation == StreamOperation::Copy.name())
+        .map(|r| {
+            (
+                r.backend.clone(),
+                r.device_name.clone(),
+                r.buffer_bytes,
+                r.gb_per_second,
+            )
+        })
+        .collect();
+
+    for result in results
+        .iter_mut()
+        .filter(|r| r.operation != StreamOperation::Copy.name())
+    {
+        result.copy_fraction = copies
+            .iter()
+            .find(|(backend, device, bytes, _)| {
+                *backend == result.backend
+                    && *device == result.device_name
+                    && *bytes == result.buffer_bytes
+            })
+            .filter(|(.., copy_gbps)| *copy_gbps > 0.0)
+            .map(|(.., copy_gbps)| result.gb_per_second / copy_gbps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(max_workgroups_per_dimension: u32) -> BackendCapabilities {
+        BackendCapabilities {
+            native_u64: false,
+            timestamp_queries: false,
+            max_workgroup_size: 256,
+            max_workgroups_per_dimension,
+            max_buffer_size: 64 * MB,
+        }
+    }
+
+    fn result(operation: StreamOperation, device: &str, bytes: u64, gbps: f64) -> StreamResult {
+        StreamResult {
+            backend: "WebGPU".to_string(),
+            device_name: device.to_string(),
+            operation: operation.name().to_string(),
+            workgroup_size: 64,
+            elements: bytes / operation.element_bytes(),
+            buffer_bytes: bytes,
+            bytes_moved: bytes * (operation.buffers_read() + 1),
+            statistic: Statistic::Min,
+            time_ns: 1.0,
+            min_ns: 1,
+            median_ns: 1.0,
+            gpu_timed: false,
+            gb_per_second: gbps,
+            gops_per_second: 0.0,
+            copy_fraction: None,
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for operation in StreamOperation::all() {
+            assert_eq!(
+                StreamOperation::from_name(operation.name()),
+                Some(operation)
+            );
+            assert!(operation.entry_point().ends_with(operation.name()));
+        }
+        assert_eq!(StreamOperation::from_name("memcpy"), None);
+    }
+
+    #[test]
+    fn layout_fits_whole_elements() {
+        let layout = StreamLayout::new(
+            StreamOperation::Bn254VecAdd,
+            1000,
+            64,
+            &capabilities(65_535),
+        )
+        .unwrap();
+        // 64-byte elements
+        assert_eq!(layout.elements, 15);
+        assert_eq!(layout.buffer_bytes, 960);
+        assert_eq!((layout.grid.x, layout.grid.y), (1, 1));
+    }
+
+    #[test]
+    fn layout_spills_into_a_second_dimension() {
+        let layout =
+            StreamLayout::new(StreamOperation::Copy, 16 * MB, 64, &capabilities(1024)).unwrap();
+        assert_eq!(layout.elements, 4 * MB);
+        let workgroups = layout.grid.x as u64 * layout.grid.y as u64;
+        assert!(layout.grid.x <= 1024 && layout.grid.y > 1);
+        assert!(workgroups * 64 >= layout.elements);
+    }
+
+    #[test]
+    fn layout_rejects_bad_sizes() {
+        let caps = capabilities(65_535);
+        assert!(StreamLayout::new(StreamOperation::Bn254VecMul, 63, 64, &caps).is_err());
+        assert!(StreamLayout::new(StreamOperation::Copy, 4096, 512, &caps).is_err());
+        assert!(matches!(
+            StreamLayout::new(StreamOperation::Copy, 128 * MB, 64, &caps),
+            Err(BenchmarkError::LimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn relate_to_copy_matches_backend_device_and_size() {
+        let mut results = vec![
+            result(StreamOperation::Copy, "A", MB, 100.0),
+            result(StreamOperation::Copy, "A", 4 * MB, 200.0),
+            result(StreamOperation::M31VecAdd, "A", MB, 90.0),
+            result(StreamOperation::Bn254VecMul, "A", 4 * MB, 20.0),
+            result(StreamOperation::M31VecMul, "B", MB, 50.0),
+        ];
+        relate_to_copy(&mut results);
+
+        assert_eq!(results[0].copy_fraction, None);
+        assert_eq!(results[2].copy_fraction, Some(0.9));
+        assert_eq!(results[3].copy_fraction, Some(0.1));
+        // No copy was measured on device B
+        assert_eq!(results[4].copy_fraction, None);
+
+        assert_eq!(results[2].memory_bound(), Some(true));
+        assert_eq!(results[3].memory_bound(), Some(false));
+        assert_eq!(results[4].memory_bound(), None);
+    }
+}
@@ -0,0 +1,295 @@
+//! Declarative benchmark suites loaded from TOML
+//!
+//! ```toml
+//! output = "results/suite.json"
+//!
+//! [[benchmark]]
+//! name = "m31-mul-wide"
+//! backend = "webgpu"          # optional; every available backend if omitted
+//! operation = "m31_field_mul" # or "all"
+//! workgroup_size = 128
+//! num_workgroups = 4096
+//! ops_per_thread = 10000      # optional; calibrated if omitted
+//! warmup = 3
+//! iterations = 50
+//! seed = 7
+//! input = "dataset"          # or "compute-bound"
+//! verify = true
+//! buffer_strategy = "shared_output" # optional; see --buffer-strategy
+//! output = "results/m31-mul-wide.csv"
+//! ```
+//!
+//! Fields left out of an entry keep the values of the base config (the CLI flags).
+
+use crate::config::{BenchmarkConfig, BufferStrategy, InputMode};
+use crate::{Backend, BenchmarkError, Operation};
+use serde::Deserialize;
+use std::path::Path;
+
+/// A suite file: named entries run in order into one report
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuiteFile {
+    /// Where the combined report is written (`.csv` or JSON)
+    #[serde(default)]
+    pub output: Option<String>,
+
+    #[serde(default, rename = "benchmark")]
+    pub benchmarks: Vec<SuiteEntry>,
+}
+
+/// One named benchmark of a suite
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuiteEntry {
+    pub name: String,
+
+    /// Backend CLI name (metal, metal-wgsl, webgpu); all available if omitted
+    #[serde(default)]
+    pub backend: Option<String>,
+
+    /// Operation name or "all"; all if omitted
+    #[serde(default)]
+    pub operation: Option<String>,
+
+    #[serde(default)]
+    pub workgroup_size: Option<u32>,
+    #[serde(default)]
+    pub num_workgroups: Option<u32>,
+
+    /// Fixed ops per thread; disables calibration for the entry
+    #[serde(default)]
+    pub ops_per_thread: Option<u32>,
+
+    #[serde(default)]
+    pub warmup: Option<u32>,
+    #[serde(default)]
+    pub iterations: Option<u32>,
+    #[serde(default)]
+    pub seed: Option<u32>,
+
+    /// Input mode name (compute-bound, dataset)
+    #[serde(default)]
+    pub input: Option<String>,
+
+    /// Check outputs against the CPU reference
+    #[serde(default)]
+    pub verify: Option<bool>,
+
+    /// Buffer strategy name (default, or choices joined by `+`)
+    #[serde(default)]
+    pub buffer_strategy: Option<String>,
+
+    /// Where this entry's results are also written (`.csv` or JSON)
+    #[serde(default)]
+    pub output: Option<String>,
+}
+
+impl SuiteFile {
+    /// Read and validate a suite file
+    pub fn load(path: &Path) -> Result<Self, BenchmarkError> {
+        Self::parse(&std::fs::read_to_string(path)?, path)
+    }
+
+    /// Parse and validate suite TOML, naming `path` in errors
+    pub fn parse(text: &str, path: &Path) -> Result<Self, BenchmarkError> {
+        let suite: SuiteFile = toml::from_str(text)
+            .map_err(|e| BenchmarkError::InvalidSuite(format!("{}: {}", path.display(), e)))?;
+
+        if suite.benchmarks.is_empty() {
+            return Err(BenchmarkError::InvalidSuite(format!(
+                "{}: no [[benchmark]] entries",
+                path.display()
+            )));
+        }
+        for entry in &suite.benchmarks {
+            entry.validate()?;
+        }
+        Ok(suite)
+    }
+}
+
+impl SuiteEntry {
+    /// Check backend and operation names before anything runs
+    fn validate(&self) -> Result<(), BenchmarkError> {
+        if let Some(name) = &self.backend {
+            if Backend::from_cli_name(name).is_none() {
+                return Err(self.invalid(format!("unknown backend '{}'", name)));
+            }
+        }
+        if let Some(name) = self.operation.as_deref().filter(|name| *name != "all") {
+            if !Operation::all().iter().any(|op| op.name() == name) {
+                return Err(self.invalid(format!("unknown operation '{}'", name)));
+            }
+        }
+        if let Some(name) = &self.input {
+            if InputMode::from_name(name).is_none() {
+                return Err(self.invalid(format!("unknown input mode '{}'", name)));
+            }
+        }
+        if let Some(name) = &self.buffer_strategy {
+            BufferStrategy::from_name(name).map_err(|e| self.invalid(e))?;
+        }
+        for (field, value) in [
+            ("workgroup_size", self.workgroup_size),
+            ("num_workgroups", self.num_workgroups),
+            ("iterations", self.iterations),
+        ] {
+            if value == Some(0) {
+                return Err(self.invalid(format!("{} must be at least 1", field)));
+            }
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, message: String) -> BenchmarkError {
+        BenchmarkError::InvalidSuite(format!("benchmark '{}': {}", self.name, message))
+    }
+
+    /// Backends the entry runs on
+    pub fn backends(&self) -> Vec<Backend> {
+        match self.backend.as_deref().and_then(Backend::from_cli_name) {
+            Some(backend) => vec![backend],
+            None => Backend::available(),
+        }
+    }
+
+    /// Operations the entry runs on `backend`
+    pub fn operations(&self, backend: Backend) -> Vec<Operation> {
+        Operation::available_for(backend)
+            .into_iter()
+            .filter(|op| match self.operation.as_deref() {
+                Some("all") | None => true,
+                Some(name) => op.name() == name,
+            })
+            .collect()
+    }
+
+    /// `base` with the entry's settings applied
+    pub fn config(&self, base: &BenchmarkConfig) -> BenchmarkConfig {
+        let mut config = base.clone();
+        if let Some(size) = self.workgroup_size {
+            config = config.with_workgroup_size(size);
+        }
+        if let Some(count) = self.num_workgroups {
+            config = config.with_num_workgroups(count);
+        }
+        if let Some(ops) = self.ops_per_thread {
+            config = config.with_ops_per_thread(ops).with_auto_calibrate(false);
+        }
+        if let Some(warmup) = self.warmup {
+            config = config.with_warmup_iterations(warmup);
+        }
+        if let Some(iterations) = self.iterations {
+            config = config.with_iterations(iterations);
+        }
+        if let Some(seed) = self.seed {
+            config = config.with_seed(seed);
+        }
+        if let Some(mode) = self.input.as_deref().and_then(InputMode::from_name) {
+            config = config.with_input_mode(mode);
+        }
+        if let Some(verify) = self.verify {
+            config = config.with_verification(verify);
+        }
+        if let Some(Ok(strategy)) = self
+            .buffer_strategy
+            .as_deref()
+            .map(BufferStrategy::from_name)
+        {
+            config = config.with_buffer_strategy(strategy);
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<SuiteFile, BenchmarkError> {
+        SuiteFile::parse(text, Path::new("test.toml"))
+    }
+
+    fn error(text: &str) -> String {
+        match parse(text) {
+            Err(BenchmarkError::InvalidSuite(message)) => message,
+            other => panic!("expected an invalid suite, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn example_suite_parses() {
+        let suite = parse(include_str!("../suites/example.toml")).unwrap();
+        assert!(!suite.benchmarks.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_suites_and_unknown_fields() {
+        assert!(error("output = \"out.json\"").contains("no [[benchmark]] entries"));
+        assert!(error("[[benchmark]]\nname = \"a\"\nthreads = 4").contains("threads"));
+    }
+
+    #[test]
+    fn rejects_unknown_names_per_entry() {
+        let message = error("[[benchmark]]\nname = \"gpu\"\nbackend = \"cuda\"");
+        assert!(message.contains("benchmark 'gpu'"));
+        assert!(message.contains("unknown backend 'cuda'"));
+
+        assert!(
+            error("[[benchmark]]\nname = \"a\"\noperation = \"u128_add\"")
+                .contains("unknown operation")
+        );
+        assert!(
+            error("[[benchmark]]\nname = \"a\"\ninput = \"random\"").contains("unknown input mode")
+        );
+        assert!(
+            error("[[benchmark]]\nname = \"a\"\nbuffer_strategy = \"fast\"")
+                .contains("benchmark 'a'")
+        );
+    }
+
+    #[test]
+    fn rejects_zero_sizes() {
+        assert!(error("[[benchmark]]\nname = \"a\"\niterations = 0")
+            .contains("iterations must be at least 1"));
+        assert!(error("[[benchmark]]\nname = \"a\"\nworkgroup_size = 0").contains("workgroup_size"));
+    }
+
+    #[test]
+    fn entry_overrides_the_base_config() {
+        let suite = parse(
+            "[[benchmark]]\nname = \"a\"\nworkgroup_size = 128\nops_per_thread = 500\n\
+             input = \"dataset\"\nbuffer_strategy = \"storage_params\"",
+        )
+        .unwrap();
+        let base = BenchmarkConfig::default().with_seed(9);
+        let config = suite.benchmarks[0].config(&base);
+
+        assert_eq!(config.workgroup_size, 128);
+        assert_eq!(config.ops_per_thread, 500);
+        assert!(!config.auto_calibrate);
+        assert_eq!(config.input_mode, InputMode::Dataset);
+        assert_eq!(config.buffer_strategy.name(), "storage_params");
+        // Unset fields keep the base values
+        assert_eq!(config.seed, 9);
+        assert_eq!(config.num_workgroups, base.num_workgroups);
+    }
+
+    #[test]
+    fn operations_filter_by_name() {
+        let suite = parse(
+            "[[benchmark]]\nname = \"one\"\noperation = \"m31_field_mul\"\n\
+             [[benchmark]]\nname = \"all\"\noperation = \"all\"",
+        )
+        .unwrap();
+        assert_eq!(
+            suite.benchmarks[0].operations(Backend::WebGPU),
+            vec![Operation::MersenneFieldMul]
+        );
+        assert_eq!(
+            suite.benchmarks[1].operations(Backend::WebGPU),
+            Operation::available_for(Backend::WebGPU)
+        );
+    }
+}
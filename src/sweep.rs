@@ -0,0 +1,232 @@
+//! Parameter sweeps over workgroup size, workgroup count and ops per thread
+
+use crate::config::BenchmarkConfig;
+use crate::results::{BenchmarkReport, BenchmarkResult};
+
+/// Values of each swept parameter; the sweep runs their Cartesian product
+#[derive(Debug, Clone)]
+pub struct SweepSpec {
+    pub workgroup_sizes: Vec<u32>,
+    pub num_workgroups: Vec<u32>,
+    /// None keeps ops_per_thread calibrated for each combination
+    pub ops_per_thread: Option<Vec<u32>>,
+}
+
+impl SweepSpec {
+    /// One config per combination, derived from `base`
+    pub fn configs(&self, base: &BenchmarkConfig) -> Vec<BenchmarkConfig> {
+        let ops_per_thread = match &self.ops_per_thread {
+            Some(values) => values.iter().map(|&ops| Some(ops)).collect(),
+            None => vec![None],
+        };
+
+        let mut configs = Vec::new();
+        for &workgroup_size in &self.workgroup_sizes {
+            for &num_workgroups in &self.num_workgroups {
+                for ops in &ops_per_thread {
+                    let config = base
+                        .clone()
+                        .with_workgroup_size(workgroup_size)
+                        .with_num_workgroups(num_workgroups);
+                    configs.push(match ops {
+                        Some(ops) => config.with_ops_per_thread(*ops).with_auto_calibrate(false),
+                        None => config,
+                    });
+                }
+            }
+        }
+        configs
+    }
+
+    /// Number of combinations
+    pub fn len(&self) -> usize {
+        self.workgroup_sizes.len()
+            * self.num_workgroups.len()
+            * self.ops_per_thread.as_ref().map_or(1, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Parse a sweep range: a list (`64,128,256`), a doubling range (`64..1024`) or a mix
+pub fn parse_values(spec: &str) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+    for part in spec.split(',').map(str::trim) {
+        match part.split_once("..") {
+            Some((start, end)) => {
+                let start = parse_value(start)?;
+                let end = parse_value(end)?;
+                if start > end {
+                    return Err(format!("empty range {}", part));
+                }
+                let mut value = start;
+                while value <= end {
+                    values.push(value);
+                    value = match value.checked_mul(2) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+            }
+            None => values.push(parse_value(part)?),
+        }
+    }
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+fn parse_value(value: &str) -> Result<u32, String> {
+    match value.trim().replace('_', "").parse::<u32>() {
+        Ok(0) => Err("values must be at least 1".to_string()),
+        Ok(v) => Ok(v),
+        Err(_) => Err(format!("invalid value '{}'", value)),
+    }
+}
+
+/// The highest-throughput result per device/backend label and operation, in first-seen order
+pub fn best_per_operation(reports: &[BenchmarkReport]) -> Vec<(String, &BenchmarkResult)> {
+    let mut best: Vec<(String, &BenchmarkResult)> = Vec::new();
+    for report in reports {
+        let label = report.label();
+        for result in &report.results {
+            match best
+                .iter_mut()
+                .find(|(l, r)| *l == label && r.operation == result.operation)
+            {
+                Some(entry) if result.gops_per_second > entry.1.gops_per_second => entry.1 = result,
+                Some(_) => {}
+                None => best.push((label.clone(), result)),
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Backend, Operation};
+    use std::time::Duration;
+
+    fn result(operation: Operation, workgroup_size: u32, ns: u64) -> BenchmarkResult {
+        BenchmarkResult::from_timings(
+            Backend::WebGPU,
+            operation,
+            workgroup_size,
+            1024,
+            100,
+            &[Duration::from_nanos(ns)],
+        )
+    }
+
+    #[test]
+    fn parse_values_lists_and_doubling_ranges() {
+        assert_eq!(parse_values("64,128,256").unwrap(), vec![64, 128, 256]);
+        assert_eq!(
+            parse_values("64..1024").unwrap(),
+            vec![64, 128, 256, 512, 1024]
+        );
+        // A range stops at its last power of two below the end
+        assert_eq!(parse_values("3..20").unwrap(), vec![3, 6, 12]);
+        assert_eq!(parse_values("7").unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn parse_values_sorts_and_dedups() {
+        assert_eq!(
+            parse_values("256, 1..4, 2, 1_024").unwrap(),
+            vec![1, 2, 4, 256, 1024]
+        );
+    }
+
+    #[test]
+    fn parse_values_stops_before_overflow() {
+        let values = parse_values("2147483648..4294967295").unwrap();
+        assert_eq!(values, vec![2_147_483_648]);
+    }
+
+    #[test]
+    fn parse_values_rejects_bad_input() {
+        assert!(parse_values("0").is_err());
+        assert!(parse_values("1024..64").is_err());
+        assert!(parse_values("64,abc").is_err());
+        assert!(parse_values("").is_err());
+    }
+
+    #[test]
+    fn configs_cover_the_cartesian_product() {
+        let spec = SweepSpec {
+            workgroup_sizes: vec![64, 256],
+            num_workgroups: vec![1, 2, 4],
+            ops_per_thread: Some(vec![10, 20]),
+        };
+        let configs = spec.configs(&BenchmarkConfig::default());
+        assert_eq!(spec.len(), 12);
+        assert_eq!(configs.len(), 12);
+        assert!(configs.iter().all(|c| !c.auto_calibrate));
+        assert_eq!(
+            (
+                configs[0].workgroup_size,
+                configs[0].num_workgroups,
+                configs[0].ops_per_thread
+            ),
+            (64, 1, 10)
+        );
+        assert_eq!(
+            (
+                configs[11].workgroup_size,
+                configs[11].num_workgroups,
+                configs[11].ops_per_thread
+            ),
+            (256, 4, 20)
+        );
+    }
+
+    #[test]
+    fn configs_keep_calibration_without_ops_per_thread() {
+        let base = BenchmarkConfig::default().with_auto_calibrate(true);
+        let spec = SweepSpec {
+            workgroup_sizes: vec![64],
+            num_workgroups: vec![8, 16],
+            ops_per_thread: None,
+        };
+        let configs = spec.configs(&base);
+        assert_eq!(spec.len(), 2);
+        assert!(configs.iter().all(|c| c.auto_calibrate));
+
+        let empty = SweepSpec {
+            workgroup_sizes: Vec::new(),
+            ..spec
+        };
+        assert!(empty.is_empty());
+        assert!(empty.configs(&base).is_empty());
+    }
+
+    #[test]
+    fn best_per_operation_keeps_the_fastest_per_label() {
+        let mut first = BenchmarkReport::new("GPU".to_string(), "A".to_string());
+        first.add_result(result(Operation::U32Add, 64, 2000));
+        first.add_result(result(Operation::U32Add, 256, 1000));
+        first.add_result(result(Operation::MersenneFieldMul, 64, 4000));
+        let mut second = BenchmarkReport::new("GPU".to_string(), "B".to_string());
+        second.add_result(result(Operation::U32Add, 128, 3000));
+
+        let reports = [first, second];
+        let best = best_per_operation(&reports);
+        let summary: Vec<(&str, &str, u32)> = best
+            .iter()
+            .map(|(label, r)| (label.as_str(), r.operation.as_str(), r.workgroup_size))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("A", Operation::U32Add.name(), 256),
+                ("A", Operation::MersenneFieldMul.name(), 64),
+                ("B", Operation::U32Add.name(), 128),
+            ]
+        );
+    }
+}
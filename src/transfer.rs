@@ -0,0 +1,279 @@
+//! Host-device transfer benchmarks
+//!
+//! Uploads move host data into a buffer the kernels can read; downloads bring a
+//! device buffer back into host memory. Each method is one API path, timed on the
+//! host from the first call until the data is usable on the other side, so small
+//! sizes measure latency and large ones bandwidth.
+
+use crate::backend::BenchmarkBackend;
+use crate::config::BenchmarkConfig;
+use crate::stats::{self, Statistic};
+use crate::{Backend, BenchmarkError};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Bytes in a kilobyte, as used by `--sizes`
+pub const KB: u64 = 1 << 10;
+
+/// Which way a transfer moves data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferDirection {
+    Upload,
+    Download,
+}
+
+impl TransferDirection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransferDirection::Upload => "upload",
+            TransferDirection::Download => "download",
+        }
+    }
+}
+
+/// Host-device transfer paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransferMethod {
+    /// `queue.write_buffer` into an existing storage buffer
+    WriteBuffer,
+    /// A new storage buffer from `create_buffer_init`
+    CreateBufferInit,
+    /// A new storage buffer filled through its mapped-at-creation range
+    MappedAtCreation,
+    /// `COPY_SRC` storage buffer copied to a `MAP_READ` staging buffer, then `map_async`
+    StagingReadback,
+    /// Copy into the contents of a shared buffer
+    SharedUpload,
+    /// Copy into a shared staging buffer, then blit it into a private buffer
+    PrivateBlitUpload,
+    /// Copy out of the contents of a shared buffer
+    SharedReadback,
+    /// Blit a private buffer into a shared staging buffer, then copy out of it
+    PrivateBlitReadback,
+}
+
+impl TransferMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransferMethod::WriteBuffer => "write_buffer",
+            TransferMethod::CreateBufferInit => "create_buffer_init",
+            TransferMethod::MappedAtCreation => "mapped_at_creation",
+            TransferMethod::StagingReadback => "staging_readback",
+            TransferMethod::SharedUpload => "shared_upload",
+            TransferMethod::PrivateBlitUpload => "private_blit_upload",
+            TransferMethod::SharedReadback => "shared_readback",
+            TransferMethod::PrivateBlitReadback => "private_blit_readback",
+        }
+    }
+
+    pub fn direction(&self) -> TransferDirection {
+        match self {
+            TransferMethod::WriteBuffer
+            | TransferMethod::CreateBufferInit
+            | TransferMethod::MappedAtCreation
+            | TransferMethod::SharedUpload
+            | TransferMethod::PrivateBlitUpload => TransferDirection::Upload,
+            TransferMethod::StagingReadback
+            | TransferMethod::SharedReadback
+            | TransferMethod::PrivateBlitReadback => TransferDirection::Download,
+        }
+    }
+
+    /// Methods implemented by a backend's API
+    pub fn for_backend(backend: Backend) -> Vec<TransferMethod> {
+        match backend {
+            Backend::WebGPU => vec![
+                TransferMethod::WriteBuffer,
+                TransferMethod::CreateBufferInit,
+                TransferMethod::MappedAtCreation,
+                TransferMethod::StagingReadback,
+            ],
+            Backend::Metal | Backend::MetalFromWgsl => vec![
+                TransferMethod::SharedUpload,
+                TransferMethod::PrivateBlitUpload,
+                TransferMethod::SharedReadback,
+                TransferMethod::PrivateBlitReadback,
+            ],
+        }
+    }
+
+    /// Look up a method by name
+    pub fn from_name(name: &str) -> Option<TransferMethod> {
+        Self::all().into_iter().find(|method| method.name() == name)
+    }
+
+    pub fn all() -> Vec<TransferMethod> {
+        let mut all = Self::for_backend(Backend::WebGPU);
+        all.extend(Self::for_backend(Backend::Metal));
+        all
+    }
+}
+
+impl std::fmt::Display for TransferMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Bandwidth and latency of one transfer method at one size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferResult {
+    pub backend: String,
+
+    #[serde(default)]
+    pub device_name: String,
+
+    pub method: String,
+    pub direction: TransferDirection,
+
+    /// Bytes moved per transfer
+    pub bytes: u64,
+
+    /// Statistic of the timings the bandwidth is derived from
+    pub statistic: Statistic,
+
+    /// Latency of one transfer under `statistic`
+    pub time_ns: f64,
+    pub min_ns: u64,
+    pub median_ns: f64,
+
+    pub gb_per_second: f64,
+}
+
+/// Run `transfer` for the warmup iterations, then time it for the measurement iterations
+///
+/// Whatever `transfer` returns (e.g. a freshly created buffer) is dropped after the
+/// sample is taken, so releasing it is not timed.
+pub fn time_iterations<T>(
+    config: &BenchmarkConfig,
+    mut transfer: impl FnMut() -> Result<T, BenchmarkError>,
+) -> Result<Vec<Duration>, BenchmarkError> {
+    for _ in 0..config.warmup_iterations {
+        transfer()?;
+    }
+
+    let mut samples = Vec::with_capacity(config.measurement_iterations as usize);
+    for _ in 0..config.measurement_iterations {
+        let start = Instant::now();
+        let kept = transfer()?;
+        samples.push(start.elapsed());
+        drop(kept);
+    }
+    Ok(samples)
+}
+
+/// Check a download against the data it was filled with
+pub fn check_download(expected: &[u8], downloaded: &[u8]) -> Result<(), BenchmarkError> {
+    match expected.iter().zip(downloaded).position(|(a, b)| a != b) {
+        None if expected.len() == downloaded.len() => Ok(()),
+        None => Err(BenchmarkError::Execution(format!(
+            "Downloaded {} bytes, expected {}",
+            downloaded.len(),
+            expected.len()
+        ))),
+        Some(offset) => Err(BenchmarkError::Execution(format!(
+            "Downloaded data differs from the source at byte {}",
+            offset
+        ))),
+    }
+}
+
+/// Time one transfer method at one size
+pub fn measure(
+    backend: &mut dyn BenchmarkBackend,
+    method: TransferMethod,
+    bytes: u64,
+    config: &BenchmarkConfig,
+) -> Result<TransferResult, BenchmarkError> {
+    // wgpu copies and buffer sizes must be whole words
+    if bytes == 0 || !bytes.is_multiple_of(4) {
+        return Err(BenchmarkError::Execution(format!(
+            "Transfer size must be a non-zero multiple of 4 bytes, got {}",
+            bytes
+        )));
+    }
+    let max_buffer_size = backend.capabilities().max_buffer_size;
+    if bytes > max_buffer_size {
+        return Err(BenchmarkError::LimitExceeded {
+            limit: "max_buffer_size (bytes)",
+            requested: bytes,
+            max: max_buffer_size,
+        });
+    }
+
+    let timings = backend.run_transfer(method, bytes, config)?;
+    let mut sorted: Vec<f64> = timings.iter().map(|d| d.as_nanos() as f64).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let time_ns = config
+        .statistic
+        .evaluate(&stats::samples_for(&sorted, config.statistic));
+    let gb_per_second = if time_ns > 0.0 {
+        bytes as f64 / time_ns
+    } else {
+        0.0
+    };
+
+    Ok(TransferResult {
+        backend: backend.name().to_string(),
+        device_name: backend.device_name(),
+        method: method.name().to_string(),
+        direction: method.direction(),
+        bytes,
+        statistic: config.statistic,
+        time_ns,
+        min_ns: sorted.first().copied().unwrap_or(0.0) as u64,
+        median_ns: stats::median(&sorted),
+        gb_per_second,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn methods_round_trip_by_name() {
+        for method in TransferMethod::all() {
+            assert_eq!(TransferMethod::from_name(method.name()), Some(method));
+        }
+        assert_eq!(TransferMethod::from_name("dma"), None);
+    }
+
+    #[test]
+    fn each_backend_has_both_directions() {
+        for backend in Backend::all() {
+            let methods = TransferMethod::for_backend(backend);
+            for direction in [TransferDirection::Upload, TransferDirection::Download] {
+                assert!(methods.iter().any(|m| m.direction() == direction));
+            }
+        }
+    }
+
+    #[test]
+    fn check_download_reports_the_first_difference() {
+        assert!(check_download(&[1, 2, 3], &[1, 2, 3]).is_ok());
+
+        let err = check_download(&[1, 2, 3, 4], &[1, 2, 9, 9]).unwrap_err();
+        assert!(err.to_string().contains("at byte 2"));
+
+        let err = check_download(&[1, 2, 3], &[1, 2]).unwrap_err();
+        assert!(err.to_string().contains("Downloaded 2 bytes, expected 3"));
+    }
+
+    #[test]
+    fn time_iterations_skips_warmup_samples() {
+        let config = BenchmarkConfig::default()
+            .with_warmup_iterations(2)
+            .with_iterations(5);
+        let mut calls = 0;
+        let samples = time_iterations(&config, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!(samples.len(), 5);
+        assert_eq!(calls, 7);
+    }
+}
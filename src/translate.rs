@@ -0,0 +1,427 @@
+//! WGSL kernel translation to MSL, SPIR-V and HLSL with naga
+//!
+//! Lets the Metal backend run exactly the same kernel source as WebGPU, so
+//! differences between hand-written Metal and WGSL kernels can be separated
+//! from API/driver overhead.
+
+use crate::shaders::{self, KernelSpec};
+use crate::stream::StreamOperation;
+use crate::{BenchmarkError, Operation};
+use naga::back::{hlsl, msl, spv};
+use naga::valid::{Capabilities, ModuleInfo, ValidationFlags, Validator};
+use naga::{Module, ResourceBinding};
+use std::path::{Path, PathBuf};
+
+/// Shader language to translate WGSL kernels into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderTarget {
+    Msl,
+    SpirV,
+    Hlsl,
+}
+
+impl ShaderTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShaderTarget::Msl => "MSL",
+            ShaderTarget::SpirV => "SPIR-V",
+            ShaderTarget::Hlsl => "HLSL",
+        }
+    }
+
+    /// File extension for exported shaders
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ShaderTarget::Msl => "metal",
+            ShaderTarget::SpirV => "spv",
+            ShaderTarget::Hlsl => "hlsl",
+        }
+    }
+
+    pub fn all() -> Vec<ShaderTarget> {
+        vec![ShaderTarget::Msl, ShaderTarget::SpirV, ShaderTarget::Hlsl]
+    }
+}
+
+impl std::fmt::Display for ShaderTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// MSL translation of a WGSL kernel
+#[derive(Debug, Clone)]
+pub struct MslKernel {
+    /// Generated Metal Shading Language source
+    pub source: String,
+
+    /// Kernel function name in the generated source
+    pub entry_point: String,
+
+    /// Buffer index of the runtime-array sizes buffer naga requires
+    pub sizes_buffer: u64,
+}
+
+/// Buffer index of the params, whether a bound buffer or push constants (`set_bytes`)
+pub const MSL_PARAMS_SLOT: u8 = 2;
+
+/// Buffer index naga-generated MSL expects the array sizes buffer at
+///
+/// The buffer holds one `u32` byte length per runtime-sized array binding
+/// (input, output), after the three benchmark buffers.
+pub const MSL_SIZES_BUFFER_SLOT: u8 = 3;
+
+/// Parse and validate the WGSL kernel for an operation, specialized to `spec`
+fn parse_wgsl(
+    operation: Operation,
+    spec: KernelSpec,
+) -> Result<(Module, ModuleInfo), BenchmarkError> {
+    let source = shaders::wgsl_source(operation).ok_or_else(|| {
+        BenchmarkError::ShaderCompilation(format!(
+            "No WGSL shader for operation: {}",
+            operation.name()
+        ))
+    })?;
+    let source = shaders::specialize(source, spec)?;
+    parse_source(&source, &spec.entry_point(operation))
+}
+
+/// Parse and validate specialized WGSL source, naming it `name` in errors
+fn parse_source(source: &str, name: &str) -> Result<(Module, ModuleInfo), BenchmarkError> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| {
+        BenchmarkError::ShaderCompilation(
+            e.emit_to_string_with_path(source, format!("{}.wgsl", name)),
+        )
+    })?;
+
+    let info = Validator::new(ValidationFlags::all(), Capabilities::PUSH_CONSTANT)
+        .validate(&module)
+        .map_err(|e| BenchmarkError::ShaderCompilation(format!("{:?}", e.into_inner())))?;
+
+    Ok((module, info))
+}
+
+/// Translate an operation's WGSL kernel to MSL
+///
+/// Bindings `@group(0) @binding(n)` map to `[[buffer(n)]]`, matching the
+/// argument layout of the hand-written Metal kernels.
+pub fn to_msl(operation: Operation) -> Result<MslKernel, BenchmarkError> {
+    to_msl_specialized(operation, KernelSpec::default())
+}
+
+/// Like [`to_msl`], for the kernel specialized to `spec`
+pub fn to_msl_specialized(
+    operation: Operation,
+    spec: KernelSpec,
+) -> Result<MslKernel, BenchmarkError> {
+    let (module, info) = parse_wgsl(operation, spec)?;
+    write_msl(
+        &module,
+        &info,
+        &spec.entry_point(operation),
+        MSL_SIZES_BUFFER_SLOT,
+        1,
+        Some(MSL_PARAMS_SLOT),
+    )
+}
+
+/// Translate a streaming kernel to MSL, specialized to `workgroup_size`
+///
+/// Buffers `a`, `b`, output and params map to `[[buffer(0..4)]]`; the sizes
+/// buffer follows at index 4.
+pub fn stream_to_msl(
+    operation: StreamOperation,
+    workgroup_size: u32,
+) -> Result<MslKernel, BenchmarkError> {
+    let (module, info) = parse_stream(operation, workgroup_size)?;
+    write_msl(&module, &info, operation.entry_point(), 4, 2, None)
+}
+
+/// Parse and validate a streaming kernel, specialized to `workgroup_size`
+fn parse_stream(
+    operation: StreamOperation,
+    workgroup_size: u32,
+) -> Result<(Module, ModuleInfo), BenchmarkError> {
+    let source =
+        shaders::specialize_workgroup_size(shaders::stream_wgsl_source(operation), workgroup_size)?;
+    parse_source(&source, operation.entry_point())
+}
+
+/// Write MSL for `entry_point`, mapping bindings 0..`buffers` to the same buffer
+/// indices (only `output_slot` mutable), push constants to `push_constant_slot`
+/// and the sizes buffer to index `buffers`
+fn write_msl(
+    module: &Module,
+    info: &ModuleInfo,
+    entry_point: &str,
+    buffers: u8,
+    output_slot: u8,
+    push_constant_slot: Option<u8>,
+) -> Result<MslKernel, BenchmarkError> {
+    let resources = (0..buffers)
+        .map(|slot| {
+            (
+                ResourceBinding {
+                    group: 0,
+                    binding: slot as u32,
+                },
+                msl::BindTarget {
+                    buffer: Some(slot),
+                    mutable: slot == output_slot,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    let mut options = msl::Options {
+        lang_version: (2, 0),
+        fake_missing_bindings: false,
+        ..Default::default()
+    };
+    options.per_entry_point_map.insert(
+        entry_point.to_string(),
+        msl::EntryPointResources {
+            resources,
+            push_constant_buffer: push_constant_slot,
+            sizes_buffer: Some(buffers),
+        },
+    );
+
+    let (source, translation) =
+        msl::write_string(module, info, &options, &msl::PipelineOptions::default())
+            .map_err(|e| BenchmarkError::ShaderCompilation(format!("MSL: {}", e)))?;
+
+    let translated_entry = module
+        .entry_points
+        .iter()
+        .zip(translation.entry_point_names)
+        .find(|(ep, _)| ep.name == entry_point)
+        .map(|(_, name)| name)
+        .ok_or_else(|| {
+            BenchmarkError::ShaderCompilation(format!("Entry point '{}' not found", entry_point))
+        })?
+        .map_err(|e| BenchmarkError::ShaderCompilation(format!("MSL: {}", e)))?;
+
+    Ok(MslKernel {
+        source,
+        entry_point: translated_entry,
+        sizes_buffer: buffers as u64,
+    })
+}
+
+/// Translate an operation's WGSL kernel to a SPIR-V binary
+pub fn to_spirv(operation: Operation) -> Result<Vec<u32>, BenchmarkError> {
+    let (module, info) = parse_wgsl(operation, KernelSpec::default())?;
+    write_spirv(&module, &info, shaders::wgsl_entry_point(operation))
+}
+
+/// Write SPIR-V for `entry_point`
+fn write_spirv(
+    module: &Module,
+    info: &ModuleInfo,
+    entry_point: &str,
+) -> Result<Vec<u32>, BenchmarkError> {
+    let pipeline_options = spv::PipelineOptions {
+        shader_stage: naga::ShaderStage::Compute,
+        entry_point: entry_point.to_string(),
+    };
+
+    spv::write_vec(
+        module,
+        info,
+        &spv::Options::default(),
+        Some(&pipeline_options),
+    )
+    .map_err(|e| BenchmarkError::ShaderCompilation(format!("SPIR-V: {}", e)))
+}
+
+/// Translate an operation's WGSL kernel to HLSL (shader model 5.1)
+pub fn to_hlsl(operation: Operation) -> Result<String, BenchmarkError> {
+    let (module, info) = parse_wgsl(operation, KernelSpec::default())?;
+    write_hlsl(&module, &info)
+}
+
+/// Write HLSL for every entry point of a module
+fn write_hlsl(module: &Module, info: &ModuleInfo) -> Result<String, BenchmarkError> {
+    // HLSL has no workgroup-count builtin; like wgpu's DX12 backend, read it
+    // from a constant buffer the host fills with the dispatch size
+    let options = hlsl::Options {
+        special_constants_binding: Some(hlsl::BindTarget {
+            space: 1,
+            register: 0,
+            binding_array_size: None,
+        }),
+        ..hlsl::Options::default()
+    };
+    let mut source = String::new();
+    hlsl::Writer::new(&mut source, &options)
+        .write(module, info, None)
+        .map_err(|e| BenchmarkError::ShaderCompilation(format!("HLSL: {}", e)))?;
+
+    Ok(source)
+}
+
+/// Translate an operation's WGSL kernel to the given target, as file contents
+pub fn translate(operation: Operation, target: ShaderTarget) -> Result<Vec<u8>, BenchmarkError> {
+    match target {
+        ShaderTarget::Msl => Ok(to_msl(operation)?.source.into_bytes()),
+        ShaderTarget::SpirV => Ok(to_spirv(operation)?
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()),
+        ShaderTarget::Hlsl => Ok(to_hlsl(operation)?.into_bytes()),
+    }
+}
+
+/// Translate a streaming kernel (64-thread workgroups) to the given target, as file contents
+pub fn translate_stream(
+    operation: StreamOperation,
+    target: ShaderTarget,
+) -> Result<Vec<u8>, BenchmarkError> {
+    let workgroup_size = KernelSpec::default().workgroup_size;
+    match target {
+        ShaderTarget::Msl => Ok(stream_to_msl(operation, workgroup_size)?
+            .source
+            .into_bytes()),
+        ShaderTarget::SpirV => {
+            let (module, info) = parse_stream(operation, workgroup_size)?;
+            Ok(write_spirv(&module, &info, operation.entry_point())?
+                .iter()
+                .flat_map(|word| word.to_le_bytes())
+                .collect())
+        }
+        ShaderTarget::Hlsl => {
+            let (module, info) = parse_stream(operation, workgroup_size)?;
+            Ok(write_hlsl(&module, &info)?.into_bytes())
+        }
+    }
+}
+
+/// Translate every WGSL kernel, benchmark and streaming, to every target and
+/// write them to `dir`
+///
+/// Files are named `<entry_point>.<ext>`; returns the written paths.
+pub fn export_shaders(dir: &Path) -> Result<Vec<PathBuf>, BenchmarkError> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+    for operation in Operation::all() {
+        if shaders::wgsl_source(operation).is_none() {
+            continue;
+        }
+
+        for target in ShaderTarget::all() {
+            let path = dir.join(format!(
+                "{}.{}",
+                shaders::wgsl_entry_point(operation),
+                target.extension()
+            ));
+            std::fs::write(&path, translate(operation, target)?)?;
+            written.push(path);
+        }
+    }
+
+    for operation in StreamOperation::all() {
+        for target in ShaderTarget::all() {
+            let path = dir.join(format!(
+                "{}.{}",
+                operation.entry_point(),
+                target.extension()
+            ));
+            std::fs::write(&path, translate_stream(operation, target)?)?;
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ParamsBinding;
+
+    #[test]
+    fn every_kernel_translates_to_every_target() {
+        for operation in Operation::all() {
+            if shaders::wgsl_source(operation).is_none() {
+                assert!(translate(operation, ShaderTarget::Msl).is_err());
+                continue;
+            }
+            for target in ShaderTarget::all() {
+                assert!(!translate(operation, target).unwrap().is_empty());
+            }
+        }
+        for operation in StreamOperation::all() {
+            for target in ShaderTarget::all() {
+                assert!(!translate_stream(operation, target).unwrap().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn msl_keeps_the_benchmark_buffer_layout() {
+        let kernel = to_msl(Operation::MersenneFieldMul).unwrap();
+        assert_eq!(kernel.entry_point, "bench_m31_field_mul");
+        assert_eq!(kernel.sizes_buffer, MSL_SIZES_BUFFER_SLOT as u64);
+        for slot in 0..=MSL_SIZES_BUFFER_SLOT {
+            assert!(kernel.source.contains(&format!("[[buffer({})]]", slot)));
+        }
+    }
+
+    #[test]
+    fn export_writes_benchmark_and_stream_kernels() {
+        let dir =
+            std::env::temp_dir().join(format!("field-ops-export-test-{}", std::process::id()));
+        let written = export_shaders(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let kernels = Operation::all()
+            .into_iter()
+            .filter(|&op| shaders::wgsl_source(op).is_some())
+            .count()
+            + StreamOperation::all().len();
+        assert_eq!(written.len(), kernels * ShaderTarget::all().len());
+        assert!(written.iter().any(|path| path.ends_with("stream_copy.spv")));
+        assert!(written
+            .iter()
+            .any(|path| path.ends_with("bench_u32_add.metal")));
+    }
+
+    #[test]
+    fn chain_kernels_translate_to_msl() {
+        for operation in Operation::all() {
+            if shaders::wgsl_source(operation).is_none() {
+                continue;
+            }
+            for independent_chains in shaders::CHAIN_COUNTS {
+                let spec = KernelSpec {
+                    independent_chains,
+                    ..KernelSpec::default()
+                };
+                let kernel = to_msl_specialized(operation, spec).unwrap();
+                assert_eq!(kernel.entry_point, spec.entry_point(operation));
+                assert!(kernel.source.contains(&kernel.entry_point));
+            }
+        }
+    }
+
+    #[test]
+    fn params_bindings_translate_to_msl_slot_2() {
+        for params in ParamsBinding::all() {
+            let spec = KernelSpec {
+                params,
+                ..KernelSpec::default()
+            };
+            let kernel = to_msl_specialized(Operation::Bn254FieldAdd, spec).unwrap();
+            assert!(
+                kernel
+                    .source
+                    .contains(&format!("[[buffer({})]]", MSL_PARAMS_SLOT)),
+                "{}",
+                params.name()
+            );
+        }
+    }
+}
@@ -119,12 +119,9 @@ impl InteractiveTui {
         let all_backends = Backend::all();
         let items: Vec<String> = all_backends
             .iter()
-            .map(|b| {
-                if b.is_available() {
-                    format!("{} (available)", b.name())
-                } else {
-                    format!("{} (not available)", b.name())
-                }
+            .map(|b| match b.availability() {
+                Ok(()) => format!("{} (available)", b.name()),
+                Err(reason) => format!("{} (not available: {})", b.name(), reason),
             })
             .collect();
 
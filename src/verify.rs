@@ -0,0 +1,440 @@
+//! CPU reference for the benchmark kernels
+//!
+//! Each kernel's arithmetic is ported limb for limb, so a sampled thread's
+//! output can be recomputed on the host and compared with what the GPU wrote.
+
+use crate::config::{BenchmarkConfig, InputMode};
+use crate::dataset::{self, BN254_P, MERSENNE_P};
+use crate::Operation;
+use serde::{Deserialize, Serialize};
+
+/// Threads recomputed on the host per operation (spread across the grid)
+pub const SAMPLED_THREADS: u64 = 64;
+
+/// Constant the kernels use for Montgomery reduction
+const MONTGOMERY_INV: u32 = 0x63B9;
+const W_MASK: u32 = 0xFFFF;
+
+/// Outcome of checking sampled threads against the CPU reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verification {
+    pub threads_checked: u64,
+    pub mismatches: u64,
+
+    /// First thread whose output differed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_mismatch: Option<u64>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+/// Check `output` (read back after one full dispatch) against the CPU reference
+///
+/// Recomputes up to [`SAMPLED_THREADS`] threads, always including the first and last.
+pub fn verify(operation: Operation, config: &BenchmarkConfig, output: &[u32]) -> Verification {
+    let input = dataset::generate(operation, config);
+    let total = config.total_threads();
+    let words = dataset::output_words(operation);
+
+    let samples = SAMPLED_THREADS.min(total);
+    let mut threads: Vec<u64> = (0..samples)
+        .map(|i| i * total.saturating_sub(1) / samples.saturating_sub(1).max(1))
+        .collect();
+    threads.dedup();
+
+    let mut mismatches = 0;
+    let mut first_mismatch = None;
+    for &tid in &threads {
+        let expected = reference(operation, config, &input, tid as u32);
+        let start = tid as usize * words;
+        if output.get(start..start + words) != Some(&expected[..words]) {
+            mismatches += 1;
+            first_mismatch.get_or_insert(tid);
+        }
+    }
+
+    Verification {
+        threads_checked: threads.len() as u64,
+        mismatches,
+        first_mismatch,
+    }
+}
+
+/// Operands of chain `c` of thread `tid`: `words` words each of a and b
+fn operands<'a>(
+    input: &'a [u32],
+    config: &BenchmarkConfig,
+    tid: u32,
+    c: u32,
+    words: usize,
+) -> (&'a [u32], &'a [u32]) {
+    let base = (tid as usize * config.independent_chains as usize + c as usize) * 2 * words;
+    (
+        &input[base..base + words],
+        &input[base + words..base + 2 * words],
+    )
+}
+
+/// Output words of one thread
+fn reference(operation: Operation, config: &BenchmarkConfig, input: &[u32], tid: u32) -> [u32; 2] {
+    let chains = config.independent_chains;
+    let iterations = config.ops_per_thread;
+    let seed = config.seed;
+    let dataset = config.input_mode == InputMode::Dataset;
+    let shared = |i: u32| input[(i % dataset::COMPUTE_BOUND_WORDS as u32) as usize];
+    let chain_seed = |c: u32| seed ^ tid ^ c.wrapping_mul(0x9E3779B9);
+
+    match operation {
+        Operation::U32Add => {
+            let mut result = 0;
+            for c in 0..chains {
+                let (mut acc, mut b) = if dataset {
+                    let (a, b) = operands(input, config, tid, c, 1);
+                    (a[0], b[0])
+                } else {
+                    (chain_seed(c), shared(tid.wrapping_add(8 + c)))
+                };
+                for _ in 0..iterations {
+                    acc = acc.wrapping_add(b);
+                    b ^= acc & 0xFF;
+                }
+                result ^= acc;
+            }
+            [result, 0]
+        }
+        Operation::U64AddNative | Operation::U64AddEmulated => {
+            let native = operation == Operation::U64AddNative;
+            let mut result = 0u64;
+            for c in 0..chains {
+                let (mut acc, mut b) = if dataset {
+                    let (a, b) = operands(input, config, tid, c, 2);
+                    (u64_from(a[0], a[1]), u64_from(b[0], b[1]))
+                } else {
+                    let (lo, hi) = (
+                        shared(tid.wrapping_add(2 + c)),
+                        shared(tid.wrapping_add(3 + c)),
+                    );
+                    // The native kernel packs the two input words the other way round
+                    let b = if native {
+                        u64_from(hi, lo)
+                    } else {
+                        u64_from(lo, hi)
+                    };
+                    (u64_from(chain_seed(c), seed), b)
+                };
+                for _ in 0..iterations {
+                    acc = acc.wrapping_add(b);
+                    b ^= acc & 0xFF;
+                }
+                result ^= acc;
+            }
+            if native {
+                [result as u32, (result >> 32) as u32]
+            } else {
+                // The emulated kernel folds both halves into one word
+                [result as u32 ^ (result >> 32) as u32, 0]
+            }
+        }
+        Operation::MersenneFieldAdd | Operation::MersenneFieldMul => {
+            let mul = operation == Operation::MersenneFieldMul;
+            let nonzero = |x: u32| if mul && x == 0 { 1 } else { x };
+            let mut result = 0;
+            for c in 0..chains {
+                let (mut acc, mut b) = if dataset {
+                    let (a, b) = operands(input, config, tid, c, 1);
+                    (a[0], b[0])
+                } else {
+                    (
+                        nonzero(chain_seed(c) & MERSENNE_P),
+                        nonzero(shared(tid.wrapping_add(8 + c)) & MERSENNE_P),
+                    )
+                };
+                for _ in 0..iterations {
+                    acc = if mul {
+                        mersenne_mul(acc, b)
+                    } else {
+                        mersenne_add(acc, b)
+                    };
+                    b = nonzero((b ^ (acc & 0xFF)) & MERSENNE_P);
+                }
+                result ^= acc;
+            }
+            [result, 0]
+        }
+        Operation::Bn254FieldAdd | Operation::Bn254FieldMul => {
+            let mul = operation == Operation::Bn254FieldMul;
+            let mut result = 0;
+            for c in 0..chains {
+                let mut a_c = [0u32; 16];
+                let mut b_c = [0u32; 16];
+                if dataset {
+                    let (a, b) = operands(input, config, tid, c, 16);
+                    a_c.copy_from_slice(a);
+                    b_c.copy_from_slice(b);
+                } else {
+                    for i in 0..16u32 {
+                        a_c[i as usize] =
+                            (shared(tid.wrapping_add(i + c)) ^ tid.wrapping_mul(i + 1)) & W_MASK;
+                        b_c[i as usize] = (shared(tid.wrapping_add(i + c + 8))
+                            ^ tid.wrapping_mul(i + 17))
+                            & W_MASK;
+                    }
+                }
+                let mut acc = field_reduce(a_c);
+                let mut b = field_reduce(b_c);
+                for _ in 0..iterations {
+                    acc = if mul {
+                        mont_mul_cios(&acc, &b)
+                    } else {
+                        field_add(&acc, &b)
+                    };
+                    b[0] = (b[0] ^ (acc[0] & 0xFF)) & W_MASK;
+                }
+                result = acc.iter().fold(result, |r, limb| r ^ limb);
+            }
+            [result, 0]
+        }
+    }
+}
+
+fn u64_from(lo: u32, hi: u32) -> u64 {
+    (hi as u64) << 32 | lo as u64
+}
+
+fn mersenne_reduce(x: u32) -> u32 {
+    let r = (x & MERSENNE_P) + (x >> 31);
+    if r >= MERSENNE_P {
+        r - MERSENNE_P
+    } else {
+        r
+    }
+}
+
+fn mersenne_add(a: u32, b: u32) -> u32 {
+    mersenne_reduce(a.wrapping_add(b))
+}
+
+fn mersenne_mul(a: u32, b: u32) -> u32 {
+    let product = a as u64 * b as u64;
+    let (lo, hi) = (product as u32, (product >> 32) as u32);
+    let chunk0 = lo & MERSENNE_P;
+    let chunk1 = ((hi << 1) | (lo >> 31)) & MERSENNE_P;
+    let sum = chunk0 + chunk1;
+    let sum = (sum & MERSENNE_P) + (sum >> 31);
+    if sum >= MERSENNE_P {
+        sum - MERSENNE_P
+    } else {
+        sum
+    }
+}
+
+fn bigint_gte_p(a: &[u32; 16]) -> bool {
+    for i in (0..16).rev() {
+        if a[i] > BN254_P[i] {
+            return true;
+        }
+        if a[i] < BN254_P[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn bigint_sub_p(a: &[u32; 16]) -> [u32; 16] {
+    let mut result = [0u32; 16];
+    let mut borrow = 0;
+    for i in 0..16 {
+        if a[i] >= BN254_P[i] + borrow {
+            result[i] = a[i] - BN254_P[i] - borrow;
+            borrow = 0;
+        } else {
+            result[i] = ((1 << 16) + a[i]) - BN254_P[i] - borrow;
+            borrow = 1;
+        }
+    }
+    result
+}
+
+fn field_reduce(a: [u32; 16]) -> [u32; 16] {
+    if bigint_gte_p(&a) {
+        bigint_sub_p(&a)
+    } else {
+        a
+    }
+}
+
+fn field_add(a: &[u32; 16], b: &[u32; 16]) -> [u32; 16] {
+    let mut result = [0u32; 16];
+    let mut carry = 0;
+    for i in 0..16 {
+        let sum = a[i] + b[i] + carry;
+        result[i] = sum & W_MASK;
+        carry = sum >> 16;
+    }
+    if carry != 0 || bigint_gte_p(&result) {
+        bigint_sub_p(&result)
+    } else {
+        result
+    }
+}
+
+fn mont_mul_cios(a: &[u32; 16], b: &[u32; 16]) -> [u32; 16] {
+    let mut t = [0u32; 18];
+    for &a_i in a {
+        // Multiply-accumulate a[i] * b
+        let mut c = 0u32;
+        for j in 0..16 {
+            let prod = a_i.wrapping_mul(b[j]);
+            let sum = t[j].wrapping_add(prod & W_MASK).wrapping_add(c);
+            t[j] = sum & W_MASK;
+            c = (prod >> 16).wrapping_add(sum >> 16);
+        }
+        let sum16 = t[16].wrapping_add(c);
+        t[16] = sum16 & W_MASK;
+        t[17] = t[17].wrapping_add(sum16 >> 16);
+
+        // Add m * p
+        let m = t[0].wrapping_mul(MONTGOMERY_INV) & W_MASK;
+        c = 0;
+        for j in 0..16 {
+            let prod = m.wrapping_mul(BN254_P[j]);
+            let sum = t[j].wrapping_add(prod & W_MASK).wrapping_add(c);
+            t[j] = sum & W_MASK;
+            c = (prod >> 16).wrapping_add(sum >> 16);
+        }
+        let sum16 = t[16].wrapping_add(c).wrapping_add(t[17]);
+        t[16] = sum16 & W_MASK;
+        t[17] = sum16 >> 16;
+
+        // Shift right one limb
+        t.copy_within(1..18, 0);
+        t[17] = 0;
+    }
+
+    let mut result = [0u32; 16];
+    result.copy_from_slice(&t[..16]);
+    field_reduce(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bn254_p_minus(k: u32) -> [u32; 16] {
+        let mut limbs = BN254_P;
+        limbs[0] -= k;
+        limbs
+    }
+
+    fn small(value: u32) -> [u32; 16] {
+        let mut limbs = [0u32; 16];
+        limbs[0] = value;
+        limbs
+    }
+
+    /// Output of every thread, as the GPU should write it
+    fn expected_output(operation: Operation, config: &BenchmarkConfig) -> Vec<u32> {
+        let input = dataset::generate(operation, config);
+        let words = dataset::output_words(operation);
+        (0..config.total_threads() as u32)
+            .flat_map(|tid| reference(operation, config, &input, tid)[..words].to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn mersenne_arithmetic_matches_modular_reference() {
+        let p = MERSENNE_P as u64;
+        let values = [
+            0,
+            1,
+            2,
+            12_345,
+            MERSENNE_P / 2,
+            MERSENNE_P - 2,
+            MERSENNE_P - 1,
+        ];
+        for &a in &values {
+            for &b in &values {
+                assert_eq!(mersenne_add(a, b) as u64, (a as u64 + b as u64) % p);
+                assert_eq!(mersenne_mul(a, b) as u64, (a as u64 * b as u64) % p);
+            }
+        }
+        assert_eq!(mersenne_reduce(MERSENNE_P), 0);
+        assert_eq!(mersenne_reduce(u32::MAX), 1);
+    }
+
+    #[test]
+    fn field_add_wraps_at_the_modulus() {
+        assert_eq!(field_add(&small(2), &small(3)), small(5));
+        assert_eq!(field_add(&bn254_p_minus(1), &small(1)), [0; 16]);
+        assert_eq!(field_add(&bn254_p_minus(1), &small(3)), small(2));
+        assert_eq!(field_reduce(BN254_P), [0; 16]);
+        assert_eq!(field_reduce(bn254_p_minus(1)), bn254_p_minus(1));
+    }
+
+    #[test]
+    fn mont_mul_stays_reduced() {
+        assert_eq!(mont_mul_cios(&[0; 16], &bn254_p_minus(1)), [0; 16]);
+        let a = bn254_p_minus(5);
+        let b = small(0xBEEF);
+        let product = mont_mul_cios(&a, &b);
+        assert_eq!(product, mont_mul_cios(&b, &a));
+        assert!(product.iter().all(|&limb| limb <= W_MASK));
+        assert!(!bigint_gte_p(&product));
+    }
+
+    #[test]
+    fn verify_passes_the_reference_output() {
+        for mode in InputMode::all() {
+            let config = BenchmarkConfig::default()
+                .with_workgroup_size(32)
+                .with_num_workgroups(4)
+                .with_ops_per_thread(10)
+                .with_independent_chains(2)
+                .with_input_mode(mode);
+            for operation in Operation::all() {
+                let output = expected_output(operation, &config);
+                let verification = verify(operation, &config, &output);
+                assert!(verification.passed(), "{} {:?}", operation.name(), mode);
+                assert_eq!(verification.threads_checked, SAMPLED_THREADS);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_reports_the_first_mismatch() {
+        let config = BenchmarkConfig::default()
+            .with_workgroup_size(32)
+            .with_num_workgroups(4)
+            .with_ops_per_thread(10);
+        let mut output = expected_output(Operation::U32Add, &config);
+
+        // The last thread is always sampled
+        *output.last_mut().unwrap() ^= 1;
+        let verification = verify(Operation::U32Add, &config, &output);
+        assert_eq!(verification.mismatches, 1);
+        assert_eq!(verification.first_mismatch, Some(127));
+
+        // Missing output counts as a mismatch
+        let verification = verify(Operation::U32Add, &config, &output[..64]);
+        assert!(!verification.passed());
+        assert_eq!(verification.first_mismatch, Some(64));
+    }
+
+    #[test]
+    fn verify_samples_small_grids_entirely() {
+        let config = BenchmarkConfig::default()
+            .with_workgroup_size(8)
+            .with_num_workgroups(1)
+            .with_ops_per_thread(3);
+        let output = expected_output(Operation::MersenneFieldMul, &config);
+        assert_eq!(
+            verify(Operation::MersenneFieldMul, &config, &output).threads_checked,
+            8
+        );
+    }
+}
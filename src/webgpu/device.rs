@@ -1,6 +1,7 @@
 //! WebGPU device and queue management
 
-use crate::BenchmarkError;
+use crate::device::{DeviceInfo, DeviceSelector, DeviceType};
+use crate::{Backend, BenchmarkError};
 use wgpu::{Adapter, Device, Instance, Queue};
 
 /// WebGPU context
@@ -12,32 +13,61 @@ pub struct WebGpuContext {
 }
 
 impl WebGpuContext {
-    /// Create a new WebGPU context
+    /// Create a new WebGPU context on the high-performance adapter
     pub fn new() -> Result<Self, BenchmarkError> {
-        pollster::block_on(Self::new_async())
+        Self::with_selector(&DeviceSelector::Default)
     }
 
-    async fn new_async() -> Result<Self, BenchmarkError> {
-        let instance = Instance::new(wgpu::InstanceDescriptor {
+    /// Create a new WebGPU context on the selected adapter
+    pub fn with_selector(selector: &DeviceSelector) -> Result<Self, BenchmarkError> {
+        pollster::block_on(Self::new_async(selector))
+    }
+
+    fn create_instance() -> Instance {
+        Instance::new(wgpu::InstanceDescriptor {
             backends: wgpu::Backends::all(),
             ..Default::default()
-        });
-
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                compatible_surface: None,
-                force_fallback_adapter: false,
-            })
-            .await
-            .ok_or(BenchmarkError::NoDevice)?;
+        })
+    }
+
+    async fn new_async(selector: &DeviceSelector) -> Result<Self, BenchmarkError> {
+        let instance = Self::create_instance();
+
+        let adapter = match selector {
+            DeviceSelector::Default => instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::HighPerformance,
+                    compatible_surface: None,
+                    force_fallback_adapter: false,
+                })
+                .await
+                .ok_or(BenchmarkError::NoDevice)?,
+            DeviceSelector::Software => instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::None,
+                    compatible_surface: None,
+                    force_fallback_adapter: true,
+                })
+                .await
+                .ok_or_else(|| BenchmarkError::DeviceNotFound(selector.to_string()))?,
+            _ => instance
+                .enumerate_adapters(wgpu::Backends::all())
+                .into_iter()
+                .enumerate()
+                .find(|(i, a)| selector.matches(*i, &a.get_info().name))
+                .map(|(_, a)| a)
+                .ok_or_else(|| BenchmarkError::DeviceNotFound(selector.to_string()))?,
+        };
+
+        let required_features = device_features(&adapter);
+        let required_limits = device_limits(&adapter);
 
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: Some("Benchmark Device"),
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
+                    required_features,
+                    required_limits,
                     memory_hints: Default::default(),
                 },
                 None,
@@ -53,16 +83,161 @@ impl WebGpuContext {
         })
     }
 
+    /// Describe every adapter visible to wgpu, in enumeration order
+    pub fn enumerate_devices() -> Vec<DeviceInfo> {
+        Self::create_instance()
+            .enumerate_adapters(wgpu::Backends::all())
+            .iter()
+            .enumerate()
+            .map(|(index, adapter)| describe_adapter(index, adapter))
+            .collect()
+    }
+
     /// Get device name
     pub fn device_name(&self) -> String {
         let info = self.adapter.get_info();
         format!("{} ({})", info.name, info.backend.to_str())
     }
 
-    /// Check if timestamp queries are supported
+    /// Get the adapter's device class
+    pub fn device_type(&self) -> DeviceType {
+        self.adapter.get_info().device_type.into()
+    }
+
+    /// Check if timestamp queries are supported (and enabled on the device)
     pub fn supports_timestamp_queries(&self) -> bool {
-        self.adapter
+        self.device
             .features()
             .contains(wgpu::Features::TIMESTAMP_QUERY)
     }
+
+    /// Check if storage buffers may be created with `MAP_READ` (and enabled on the device)
+    pub fn supports_mappable_storage(&self) -> bool {
+        self.device
+            .features()
+            .contains(wgpu::Features::MAPPABLE_PRIMARY_BUFFERS)
+    }
+
+    /// Check if push constants of `bytes` are supported (and enabled on the device)
+    pub fn supports_push_constants(&self, bytes: u32) -> bool {
+        self.device
+            .features()
+            .contains(wgpu::Features::PUSH_CONSTANTS)
+            && self.device.limits().max_push_constant_size >= bytes
+    }
+
+    /// Create an empty pipeline cache, if the device supports them
+    pub fn create_pipeline_cache(&self) -> Option<wgpu::PipelineCache> {
+        if !self
+            .device
+            .features()
+            .contains(wgpu::Features::PIPELINE_CACHE)
+        {
+            return None;
+        }
+
+        // SAFETY: no initial data is passed, so there is nothing to validate
+        Some(unsafe {
+            self.device
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: Some("Benchmark Pipeline Cache"),
+                    data: None,
+                    fallback: true,
+                })
+        })
+    }
+}
+
+/// Features requested on the device
+///
+/// GPU timestamps, pipeline caching, mappable storage buffers and push constants are
+/// enabled when the adapter has them; the latter two only affect pipelines and buffers
+/// that opt in through the buffer strategy.
+fn device_features(adapter: &Adapter) -> wgpu::Features {
+    adapter.features()
+        & (wgpu::Features::TIMESTAMP_QUERY
+            | wgpu::Features::PIPELINE_CACHE
+            | wgpu::Features::MAPPABLE_PRIMARY_BUFFERS
+            | wgpu::Features::PUSH_CONSTANTS)
+}
+
+/// Limits requested on the device
+///
+/// The compute limits are raised to the adapter's, for workgroup sizes and grids, the
+/// buffer limits for large streaming buffers, and the push constant size when push
+/// constants are enabled. `list-devices` reports these, so it matches what the runner
+/// validates against.
+fn device_limits(adapter: &Adapter) -> wgpu::Limits {
+    let adapter_limits = adapter.limits();
+    let push_constants = device_features(adapter).contains(wgpu::Features::PUSH_CONSTANTS);
+
+    wgpu::Limits {
+        max_compute_workgroup_size_x: adapter_limits.max_compute_workgroup_size_x,
+        max_compute_workgroup_size_y: adapter_limits.max_compute_workgroup_size_y,
+        max_compute_workgroup_size_z: adapter_limits.max_compute_workgroup_size_z,
+        max_compute_invocations_per_workgroup: adapter_limits.max_compute_invocations_per_workgroup,
+        max_compute_workgroups_per_dimension: adapter_limits.max_compute_workgroups_per_dimension,
+        max_compute_workgroup_storage_size: adapter_limits.max_compute_workgroup_storage_size,
+        max_storage_buffer_binding_size: adapter_limits.max_storage_buffer_binding_size,
+        max_buffer_size: adapter_limits.max_buffer_size,
+        max_push_constant_size: if push_constants {
+            adapter_limits.max_push_constant_size
+        } else {
+            0
+        },
+        ..wgpu::Limits::default()
+    }
+}
+
+/// Collect adapter info, device limits and features into a `DeviceInfo`
+fn describe_adapter(index: usize, adapter: &Adapter) -> DeviceInfo {
+    let info = adapter.get_info();
+    let limits = device_limits(adapter);
+
+    DeviceInfo {
+        backend: Backend::WebGPU,
+        index,
+        name: info.name,
+        device_type: info.device_type.into(),
+        driver: format!(
+            "{} {} {}",
+            info.backend.to_str(),
+            info.driver,
+            info.driver_info
+        )
+        .trim()
+        .to_string(),
+        limits: vec![
+            (
+                "max_compute_workgroup_size_x",
+                limits.max_compute_workgroup_size_x as u64,
+            ),
+            (
+                "max_compute_invocations_per_workgroup",
+                limits.max_compute_invocations_per_workgroup as u64,
+            ),
+            (
+                "max_compute_workgroups_per_dimension",
+                limits.max_compute_workgroups_per_dimension as u64,
+            ),
+            (
+                "max_compute_workgroup_storage_size",
+                limits.max_compute_workgroup_storage_size as u64,
+            ),
+            (
+                "max_storage_buffer_binding_size",
+                limits.max_storage_buffer_binding_size as u64,
+            ),
+            ("max_buffer_size", limits.max_buffer_size),
+            (
+                "max_push_constant_size",
+                limits.max_push_constant_size as u64,
+            ),
+        ],
+        features: adapter
+            .features()
+            .iter_names()
+            .map(|(name, _)| name.to_string())
+            .collect(),
+    }
 }
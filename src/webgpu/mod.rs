@@ -5,5 +5,5 @@ mod pipeline;
 mod runner;
 
 pub use device::WebGpuContext;
-pub use pipeline::WebGpuPipeline;
+pub use pipeline::{WebGpuPipeline, BENCH_PARAMS_BYTES};
 pub use runner::WebGpuRunner;
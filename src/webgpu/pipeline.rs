@@ -1,13 +1,23 @@
 //! WebGPU compute pipeline management
 
+use crate::config::ParamsBinding;
 use crate::BenchmarkError;
-use wgpu::{BindGroupLayout, ComputePipeline, Device};
+use std::time::{Duration, Instant};
+use wgpu::{BindGroupLayout, BindGroupLayoutEntry, ComputePipeline, Device, PipelineCache};
+
+/// Size of the kernels' `BenchParams` (four `u32`), the push constant range when
+/// params are pushed
+pub const BENCH_PARAMS_BYTES: u32 = 16;
 
 /// WebGPU compute pipeline for a benchmark kernel
 pub struct WebGpuPipeline {
     pub pipeline: ComputePipeline,
     pub bind_group_layout: BindGroupLayout,
     pub workgroup_size: u32,
+    /// Time spent creating the shader module
+    pub shader_module_time: Duration,
+    /// Time spent creating the compute pipeline (layouts included)
+    pub pipeline_time: Duration,
 }
 
 impl WebGpuPipeline {
@@ -17,58 +27,110 @@ impl WebGpuPipeline {
         shader_source: &str,
         entry_point: &str,
         workgroup_size: u32,
+    ) -> Result<Self, BenchmarkError> {
+        Self::with_cache(
+            device,
+            shader_source,
+            entry_point,
+            workgroup_size,
+            ParamsBinding::Uniform,
+            None,
+        )
+    }
+
+    /// Create a new pipeline, compiling through a pipeline cache when given
+    pub fn with_cache(
+        device: &Device,
+        shader_source: &str,
+        entry_point: &str,
+        workgroup_size: u32,
+        params: ParamsBinding,
+        cache: Option<&PipelineCache>,
+    ) -> Result<Self, BenchmarkError> {
+        // Input and output buffers, then the params unless they are push constants
+        let mut entries = vec![storage_entry(0, true), storage_entry(1, false)];
+        let push_constant_bytes = match params {
+            ParamsBinding::Uniform => {
+                entries.push(uniform_entry(2));
+                0
+            }
+            ParamsBinding::Storage => {
+                entries.push(storage_entry(2, true));
+                0
+            }
+            ParamsBinding::PushConstants => BENCH_PARAMS_BYTES,
+        };
+
+        Self::create(
+            device,
+            shader_source,
+            entry_point,
+            workgroup_size,
+            cache,
+            &entries,
+            push_constant_bytes,
+        )
+    }
+
+    /// Create a pipeline for a streaming kernel: inputs `a` and `b`, output and params
+    pub fn for_stream(
+        device: &Device,
+        shader_source: &str,
+        entry_point: &str,
+        workgroup_size: u32,
+    ) -> Result<Self, BenchmarkError> {
+        Self::create(
+            device,
+            shader_source,
+            entry_point,
+            workgroup_size,
+            None,
+            &[
+                storage_entry(0, true),
+                storage_entry(1, true),
+                storage_entry(2, false),
+                uniform_entry(3),
+            ],
+            0,
+        )
+    }
+
+    fn create(
+        device: &Device,
+        shader_source: &str,
+        entry_point: &str,
+        workgroup_size: u32,
+        cache: Option<&PipelineCache>,
+        layout_entries: &[BindGroupLayoutEntry],
+        push_constant_bytes: u32,
     ) -> Result<Self, BenchmarkError> {
         // Create shader module
+        let start = Instant::now();
         let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some(entry_point),
             source: wgpu::ShaderSource::Wgsl(shader_source.into()),
         });
+        let shader_module_time = start.elapsed();
 
         // Create bind group layout
+        let start = Instant::now();
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Benchmark Bind Group Layout"),
-            entries: &[
-                // Input buffer
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: true },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                // Output buffer
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                // Params buffer
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-            ],
+            entries: layout_entries,
         });
 
         // Create pipeline layout
+        let push_constant_ranges: Vec<wgpu::PushConstantRange> = (push_constant_bytes > 0)
+            .then_some(wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::COMPUTE,
+                range: 0..push_constant_bytes,
+            })
+            .into_iter()
+            .collect();
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Benchmark Pipeline Layout"),
             bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
+            push_constant_ranges: &push_constant_ranges,
         });
 
         // Create compute pipeline
@@ -78,13 +140,44 @@ impl WebGpuPipeline {
             module: &shader_module,
             entry_point: Some(entry_point),
             compilation_options: Default::default(),
-            cache: None,
+            cache,
         });
+        let pipeline_time = start.elapsed();
 
         Ok(Self {
             pipeline,
             bind_group_layout,
             workgroup_size,
+            shader_module_time,
+            pipeline_time,
         })
     }
 }
+
+/// Compute-visible storage buffer binding
+fn storage_entry(binding: u32, read_only: bool) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Compute-visible uniform buffer binding
+fn uniform_entry(binding: u32) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
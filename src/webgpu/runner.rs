@@ -1,70 +1,634 @@
 //! WebGPU benchmark execution
 
-use crate::config::BenchmarkConfig;
-use crate::results::BenchmarkResult;
+use crate::backend::{BackendCapabilities, BenchmarkBackend, DispatchGrid};
+use crate::config::{BenchmarkConfig, InputMode, OutputUsage, ParamsBinding, SubmitBatching};
+use crate::dataset;
+use crate::device::{DeviceSelector, DeviceType};
+use crate::results::{BenchmarkResult, StageLatency, StartupLatency};
+use crate::sampling;
+use crate::shaders::{self, KernelSpec};
+use crate::stream::{StreamLayout, StreamOperation, StreamTimings};
+use crate::transfer::{self, TransferMethod};
+use crate::verify::{self, Verification};
 use crate::{Backend, BenchmarkError, Operation};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use wgpu::util::DeviceExt;
 
-use super::{WebGpuContext, WebGpuPipeline};
+use super::{WebGpuContext, WebGpuPipeline, BENCH_PARAMS_BYTES};
+
+/// Kernel parameters, laid out as the WGSL `BenchParams`
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct BenchParams {
+    iterations: u32,
+    seed: u32,
+    dataset: u32,
+    threads: u32,
+}
+
+impl BenchParams {
+    fn from_config(config: &BenchmarkConfig) -> Self {
+        Self {
+            iterations: config.ops_per_thread,
+            seed: config.seed,
+            dataset: (config.input_mode == InputMode::Dataset) as u32,
+            threads: config.total_threads() as u32,
+        }
+    }
+}
+
+/// Bind group of one params variant, and the params themselves when they are pushed
+struct KernelBinding {
+    bind_group: wgpu::BindGroup,
+    push_constants: Option<BenchParams>,
+}
+
+/// Pipeline and buffers prepared for one operation
+struct PreparedBenchmark {
+    operation: Operation,
+    config: BenchmarkConfig,
+    pipeline: WebGpuPipeline,
+    grid: DispatchGrid,
+    binding: KernelBinding,
+    /// Same buffers with zero-iteration params, for overhead calibration
+    empty_binding: KernelBinding,
+    /// Read back for verification
+    output_buffer: wgpu::Buffer,
+    // Kept alive for the bind groups
+    _buffers: Vec<wgpu::Buffer>,
+    /// Present when the device supports timestamp queries
+    timestamps: Option<TimestampQueries>,
+}
+
+/// Query set and buffers used to read back GPU timestamps for one pass
+struct TimestampQueries {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+}
+
+/// Bytes needed to hold the start and end timestamps
+const TIMESTAMP_BYTES: u64 = 2 * std::mem::size_of::<u64>() as u64;
 
 /// Benchmark runner for WebGPU
 pub struct WebGpuRunner {
     ctx: WebGpuContext,
     shaders: HashMap<Operation, String>,
+    prepared: Option<PreparedBenchmark>,
 }
 
 impl WebGpuRunner {
     pub fn new() -> Result<Self, BenchmarkError> {
-        let ctx = WebGpuContext::new()?;
-        let shaders = Self::load_shaders();
-        Ok(Self { ctx, shaders })
+        Self::with_selector(&DeviceSelector::Default)
     }
 
-    pub fn device_name(&self) -> String {
-        self.ctx.device_name()
+    /// Create a runner on the selected adapter
+    pub fn with_selector(selector: &DeviceSelector) -> Result<Self, BenchmarkError> {
+        let ctx = WebGpuContext::with_selector(selector)?;
+        let shaders = Self::load_shaders();
+        Ok(Self {
+            ctx,
+            shaders,
+            prepared: None,
+        })
     }
 
     /// Load all WGSL shaders
     fn load_shaders() -> HashMap<Operation, String> {
-        let mut shaders = HashMap::new();
+        Operation::all()
+            .into_iter()
+            .filter_map(|op| shaders::wgsl_source(op).map(|src| (op, src.to_string())))
+            .collect()
+    }
 
-        // Include shaders at compile time
-        shaders.insert(
-            Operation::U32Add,
-            include_str!("../../shaders/wgsl/bench_u32_add.wgsl").to_string(),
-        );
-        shaders.insert(
-            Operation::U64AddEmulated,
-            include_str!("../../shaders/wgsl/bench_u64_add.wgsl").to_string(),
-        );
-        shaders.insert(
-            Operation::Bn254FieldMul,
-            include_str!("../../shaders/wgsl/bench_bn254_field_mul.wgsl").to_string(),
-        );
-        shaders.insert(
-            Operation::Bn254FieldAdd,
-            include_str!("../../shaders/wgsl/bench_bn254_field_add.wgsl").to_string(),
-        );
-        shaders.insert(
-            Operation::MersenneFieldAdd,
-            include_str!("../../shaders/wgsl/bench_m31_field_add.wgsl").to_string(),
+    /// Create input buffer with the operation's seeded data
+    fn create_input_buffer(&self, operation: Operation, config: &BenchmarkConfig) -> wgpu::Buffer {
+        let data = dataset::generate(operation, config);
+
+        self.ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Input Buffer"),
+                contents: bytemuck::cast_slice(&data),
+                usage: wgpu::BufferUsages::STORAGE,
+            })
+    }
+
+    /// Create output buffer of `words` u32 words with the strategy's usages
+    ///
+    /// A storage-only output gains `COPY_SRC` when `verify` has to copy it to a
+    /// staging buffer.
+    fn create_output_buffer(&self, words: usize, usage: OutputUsage, verify: bool) -> wgpu::Buffer {
+        let readback = match usage {
+            OutputUsage::StorageOnly if verify => wgpu::BufferUsages::COPY_SRC,
+            OutputUsage::StorageOnly => wgpu::BufferUsages::empty(),
+            OutputUsage::CopySrc | OutputUsage::MappedAtCreation => wgpu::BufferUsages::COPY_SRC,
+            OutputUsage::MapRead => wgpu::BufferUsages::MAP_READ,
+        };
+        let mapped_at_creation = usage == OutputUsage::MappedAtCreation;
+
+        let buffer = self.ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Output Buffer"),
+            size: (words * std::mem::size_of::<u32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | readback,
+            mapped_at_creation,
+        });
+        if mapped_at_creation {
+            buffer.slice(..).get_mapped_range_mut().fill(0);
+            buffer.unmap();
+        }
+        buffer
+    }
+
+    /// Create parameters buffer
+    fn create_params_buffer(&self, config: &BenchmarkConfig) -> wgpu::Buffer {
+        let usage = match config.buffer_strategy.params {
+            ParamsBinding::Storage => wgpu::BufferUsages::STORAGE,
+            ParamsBinding::Uniform | ParamsBinding::PushConstants => wgpu::BufferUsages::UNIFORM,
+        };
+        self.ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Params Buffer"),
+                contents: bytemuck::cast_slice(&[BenchParams::from_config(config)]),
+                usage,
+            })
+    }
+
+    /// Bind the input, output and (unless pushed) params buffers to the pipeline's layout
+    fn create_bind_group(
+        &self,
+        pipeline: &WebGpuPipeline,
+        input_buffer: &wgpu::Buffer,
+        output_buffer: &wgpu::Buffer,
+        params_buffer: Option<&wgpu::Buffer>,
+    ) -> wgpu::BindGroup {
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: input_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: output_buffer.as_entire_binding(),
+            },
+        ];
+        if let Some(params_buffer) = params_buffer {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 2,
+                resource: params_buffer.as_entire_binding(),
+            });
+        }
+
+        self.ctx
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Benchmark Bind Group"),
+                layout: &pipeline.bind_group_layout,
+                entries: &entries,
+            })
+    }
+
+    /// Create the timestamp query set and buffers, if the device supports them
+    fn create_timestamp_queries(&self) -> Option<TimestampQueries> {
+        if !self.ctx.supports_timestamp_queries() {
+            return None;
+        }
+
+        let query_set = self.ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Timestamp Queries"),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+
+        let resolve_buffer = self.ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Resolve Buffer"),
+            size: TIMESTAMP_BYTES,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let readback_buffer = self.ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Timestamp Readback Buffer"),
+            size: TIMESTAMP_BYTES,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(TimestampQueries {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+        })
+    }
+
+    /// Submit `dispatches_per_submit` dispatches with the given bind group and wait
+    fn dispatch(&self, prepared: &PreparedBenchmark, binding: &KernelBinding) {
+        let count = prepared.config.dispatches_per_submit.max(1);
+
+        // Timestamps bracket the whole submission: start of the first pass, end of the last
+        let command_buffers: Vec<_> = match prepared.config.submit_batching {
+            SubmitBatching::SinglePass => {
+                vec![self.encode(prepared, binding, count, true, true)]
+            }
+            SubmitBatching::CommandBuffers => (0..count)
+                .map(|i| self.encode(prepared, binding, 1, i == 0, i == count - 1))
+                .collect(),
+        };
+
+        self.ctx.queue.submit(command_buffers);
+        self.ctx.device.poll(wgpu::Maintain::Wait);
+    }
+
+    /// Record `count` dispatches into one compute pass
+    fn encode(
+        &self,
+        prepared: &PreparedBenchmark,
+        binding: &KernelBinding,
+        count: u32,
+        write_start: bool,
+        write_end: bool,
+    ) -> wgpu::CommandBuffer {
+        let mut encoder = self
+            .ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Benchmark Encoder"),
+            });
+
+        {
+            let timestamp_writes = prepared
+                .timestamps
+                .as_ref()
+                .filter(|_| write_start || write_end)
+                .map(|ts| wgpu::ComputePassTimestampWrites {
+                    query_set: &ts.query_set,
+                    beginning_of_pass_write_index: write_start.then_some(0),
+                    end_of_pass_write_index: write_end.then_some(1),
+                });
+
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Benchmark Compute Pass"),
+                timestamp_writes,
+            });
+
+            compute_pass.set_pipeline(&prepared.pipeline.pipeline);
+            // Params are bound per dispatch, as successive kernels with their own params would be
+            for _ in 0..count {
+                compute_pass.set_bind_group(0, &binding.bind_group, &[]);
+                if let Some(params) = &binding.push_constants {
+                    compute_pass.set_push_constants(0, bytemuck::bytes_of(params));
+                }
+                compute_pass.dispatch_workgroups(prepared.grid.x, prepared.grid.y, 1);
+            }
+        }
+
+        encoder.finish()
+    }
+
+    /// Read back the GPU time of the last dispatch (outside the wall-clock measurement)
+    fn read_gpu_time(
+        &self,
+        timestamps: &TimestampQueries,
+    ) -> Result<Option<Duration>, BenchmarkError> {
+        let mut encoder = self
+            .ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Timestamp Resolve Encoder"),
+            });
+        encoder.resolve_query_set(&timestamps.query_set, 0..2, &timestamps.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &timestamps.resolve_buffer,
+            0,
+            &timestamps.readback_buffer,
+            0,
+            TIMESTAMP_BYTES,
         );
-        shaders.insert(
-            Operation::MersenneFieldMul,
-            include_str!("../../shaders/wgsl/bench_m31_field_mul.wgsl").to_string(),
+        self.ctx.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = timestamps.readback_buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.ctx.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|e| BenchmarkError::Execution(e.to_string()))?
+            .map_err(|e| BenchmarkError::Execution(format!("Timestamp readback failed: {}", e)))?;
+
+        let ticks: [u64; 2] = {
+            let data = slice.get_mapped_range();
+            let values: &[u64] = bytemuck::cast_slice(&data);
+            [values[0], values[1]]
+        };
+        timestamps.readback_buffer.unmap();
+
+        // Some drivers report zeros or out-of-order values; treat those as unavailable
+        if ticks[1] <= ticks[0] {
+            return Ok(None);
+        }
+        let period_ns = self.ctx.queue.get_timestamp_period() as f64;
+        let elapsed_ns = (ticks[1] - ticks[0]) as f64 * period_ns;
+        Ok(Some(Duration::from_nanos(elapsed_ns as u64)))
+    }
+
+    /// Run one full dispatch, read the output back and check it against the CPU reference
+    fn verify_output(&self, prepared: &PreparedBenchmark) -> Result<Verification, BenchmarkError> {
+        self.dispatch(prepared, &prepared.binding);
+
+        // A MAP_READ output is mapped in place; any other is copied to a staging buffer
+        let staging_buffer;
+        let readback_buffer = if prepared.config.buffer_strategy.wgpu_output == OutputUsage::MapRead
+        {
+            &prepared.output_buffer
+        } else {
+            let size = prepared.output_buffer.size();
+            staging_buffer = self.ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Output Readback Buffer"),
+                size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let mut encoder =
+                self.ctx
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: Some("Output Readback Encoder"),
+                    });
+            encoder.copy_buffer_to_buffer(&prepared.output_buffer, 0, &staging_buffer, 0, size);
+            self.ctx.queue.submit(std::iter::once(encoder.finish()));
+            &staging_buffer
+        };
+
+        let slice = readback_buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.ctx.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|e| BenchmarkError::Execution(e.to_string()))?
+            .map_err(|e| BenchmarkError::Execution(format!("Output readback failed: {}", e)))?;
+
+        let output: Vec<u32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback_buffer.unmap();
+
+        Ok(verify::verify(
+            prepared.operation,
+            &prepared.config,
+            &output,
+        ))
+    }
+
+    /// Time a streaming kernel with its own pipeline and buffers (see [`BenchmarkBackend::run_stream`])
+    fn time_stream(
+        &self,
+        operation: StreamOperation,
+        layout: &StreamLayout,
+        config: &BenchmarkConfig,
+    ) -> Result<StreamTimings, BenchmarkError> {
+        #[repr(C)]
+        #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+        struct StreamParams {
+            count: u32,
+            _pad: [u32; 3],
+        }
+
+        let source = shaders::specialize_workgroup_size(
+            shaders::stream_wgsl_source(operation),
+            layout.workgroup_size,
+        )?;
+        let pipeline = WebGpuPipeline::for_stream(
+            &self.ctx.device,
+            &source,
+            operation.entry_point(),
+            layout.workgroup_size,
+        )?;
+
+        // Reduced operands for the stream's field; the copy only reads `a`
+        let reads = operation.buffers_read() as usize;
+        let data = dataset::random_elements(
+            operation.field_operation(),
+            layout.elements as usize * reads,
+            config.seed,
         );
+        let words = data.len() / reads;
+        let create_input = |label, contents: &[u32]| {
+            self.ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents: bytemuck::cast_slice(contents),
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+        };
+        let a_buffer = create_input("Stream A Buffer", &data[..words]);
+        let b_buffer = (reads > 1).then(|| create_input("Stream B Buffer", &data[words..]));
+        drop(data);
 
-        shaders
+        let out_buffer = self.ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Stream Output Buffer"),
+            size: layout.buffer_bytes,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let params = StreamParams {
+            count: layout.elements as u32,
+            _pad: [0; 3],
+        };
+        let params_buffer = self
+            .ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Stream Params Buffer"),
+                contents: bytemuck::cast_slice(&[params]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let buffers = [
+            &a_buffer,
+            b_buffer.as_ref().unwrap_or(&a_buffer),
+            &out_buffer,
+            &params_buffer,
+        ];
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self
+            .ctx
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Stream Bind Group"),
+                layout: &pipeline.bind_group_layout,
+                entries: &entries,
+            });
+        let timestamps = self.create_timestamp_queries();
+
+        let dispatch = || {
+            let mut encoder =
+                self.ctx
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: Some("Stream Encoder"),
+                    });
+            {
+                let timestamp_writes =
+                    timestamps
+                        .as_ref()
+                        .map(|ts| wgpu::ComputePassTimestampWrites {
+                            query_set: &ts.query_set,
+                            beginning_of_pass_write_index: Some(0),
+                            end_of_pass_write_index: Some(1),
+                        });
+                let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Stream Compute Pass"),
+                    timestamp_writes,
+                });
+                compute_pass.set_pipeline(&pipeline.pipeline);
+                compute_pass.set_bind_group(0, &bind_group, &[]);
+                compute_pass.dispatch_workgroups(layout.grid.x, layout.grid.y, 1);
+            }
+            self.ctx.queue.submit(std::iter::once(encoder.finish()));
+            self.ctx.device.poll(wgpu::Maintain::Wait);
+        };
+
+        for _ in 0..config.warmup_iterations {
+            dispatch();
+        }
+
+        let mut timings = StreamTimings::default();
+        for _ in 0..config.measurement_iterations {
+            let start = Instant::now();
+            dispatch();
+            timings.wall.push(start.elapsed());
+
+            if let Some(timestamps) = &timestamps {
+                timings.gpu.extend(self.read_gpu_time(timestamps)?);
+            }
+        }
+        Ok(timings)
     }
 
-    /// Run a benchmark with the given configuration
-    pub fn run_benchmark(
+    /// Time one host-device transfer path (see [`BenchmarkBackend::run_transfer`])
+    fn time_transfer(
         &self,
+        method: TransferMethod,
+        bytes: u64,
+        config: &BenchmarkConfig,
+    ) -> Result<Vec<Duration>, BenchmarkError> {
+        let device = &self.ctx.device;
+        let queue = &self.ctx.queue;
+        let words = dataset::random_elements(Operation::U32Add, (bytes / 4) as usize, config.seed);
+        let data: &[u8] = bytemuck::cast_slice(&words);
+
+        // An empty submit flushes pending writes; waiting on it completes the transfer
+        let finish = || {
+            queue.submit(std::iter::empty());
+            device.poll(wgpu::Maintain::Wait);
+        };
+
+        match method {
+            TransferMethod::WriteBuffer => {
+                let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Transfer Destination Buffer"),
+                    size: bytes,
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                transfer::time_iterations(config, || {
+                    queue.write_buffer(&buffer, 0, data);
+                    finish();
+                    Ok(())
+                })
+            }
+            TransferMethod::CreateBufferInit => transfer::time_iterations(config, || {
+                let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Transfer Init Buffer"),
+                    contents: data,
+                    usage: wgpu::BufferUsages::STORAGE,
+                });
+                finish();
+                Ok(buffer)
+            }),
+            TransferMethod::MappedAtCreation => transfer::time_iterations(config, || {
+                let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Transfer Mapped Buffer"),
+                    size: bytes,
+                    usage: wgpu::BufferUsages::STORAGE,
+                    mapped_at_creation: true,
+                });
+                buffer
+                    .slice(..)
+                    .get_mapped_range_mut()
+                    .copy_from_slice(data);
+                buffer.unmap();
+                finish();
+                Ok(buffer)
+            }),
+            TransferMethod::StagingReadback => {
+                let source_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Transfer Source Buffer"),
+                    contents: data,
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                });
+                let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Transfer Staging Buffer"),
+                    size: bytes,
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                let mut host = vec![0u8; bytes as usize];
+
+                let samples = transfer::time_iterations(config, || {
+                    let mut encoder =
+                        device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                            label: Some("Transfer Readback Encoder"),
+                        });
+                    encoder.copy_buffer_to_buffer(&source_buffer, 0, &staging_buffer, 0, bytes);
+                    queue.submit(std::iter::once(encoder.finish()));
+
+                    let slice = staging_buffer.slice(..);
+                    let (tx, rx) = std::sync::mpsc::channel();
+                    slice.map_async(wgpu::MapMode::Read, move |result| {
+                        let _ = tx.send(result);
+                    });
+                    device.poll(wgpu::Maintain::Wait);
+                    rx.recv()
+                        .map_err(|e| BenchmarkError::Execution(e.to_string()))?
+                        .map_err(|e| {
+                            BenchmarkError::Execution(format!("Readback failed: {}", e))
+                        })?;
+
+                    host.copy_from_slice(&slice.get_mapped_range());
+                    staging_buffer.unmap();
+                    Ok(())
+                })?;
+                transfer::check_download(data, &host)?;
+                Ok(samples)
+            }
+            _ => Err(BenchmarkError::BackendNotAvailable(format!(
+                "{} has no {} transfer",
+                Backend::WebGPU.name(),
+                method
+            ))),
+        }
+    }
+
+    /// Create the pipeline (through `cache`, if given) and buffers for an operation
+    fn prepare_with_cache(
+        &mut self,
         operation: Operation,
         config: &BenchmarkConfig,
-    ) -> Result<BenchmarkResult, BenchmarkError> {
+        cache: Option<&wgpu::PipelineCache>,
+    ) -> Result<(), BenchmarkError> {
         // Get shader source
         let shader_source = self.shaders.get(&operation).ok_or_else(|| {
             BenchmarkError::ShaderCompilation(format!(
@@ -73,160 +637,263 @@ impl WebGpuRunner {
             ))
         })?;
 
+        let grid = self.capabilities().validate(operation, config)?;
+        let strategy = config.buffer_strategy;
+        strategy.check(Backend::WebGPU)?;
+        if strategy.wgpu_output == OutputUsage::MapRead && !self.ctx.supports_mappable_storage() {
+            return Err(BenchmarkError::BackendNotAvailable(
+                "map_read_output needs MAPPABLE_PRIMARY_BUFFERS, which this adapter lacks"
+                    .to_string(),
+            ));
+        }
+        if strategy.params == ParamsBinding::PushConstants
+            && !self.ctx.supports_push_constants(BENCH_PARAMS_BYTES)
+        {
+            return Err(BenchmarkError::BackendNotAvailable(
+                "push_constant_params needs PUSH_CONSTANTS, which this adapter lacks".to_string(),
+            ));
+        }
+        let spec = KernelSpec::from_config(config);
+        let shader_source = shaders::specialize(shader_source, spec)?;
+
         // Create pipeline
-        let entry_point = operation_to_entry_point(operation);
-        let pipeline = WebGpuPipeline::new(
+        let entry_point = spec.entry_point(operation);
+        let pipeline = WebGpuPipeline::with_cache(
             &self.ctx.device,
-            shader_source,
-            entry_point,
+            &shader_source,
+            &entry_point,
             config.workgroup_size,
+            strategy.params,
+            cache,
         )?;
 
         // Create buffers
         let total_threads = config.total_threads() as usize;
-        let input_buffer = self.create_input_buffer(config.seed);
-        let output_buffer = self.create_output_buffer(total_threads);
-        let params_buffer = self.create_params_buffer(config);
+        let input_buffer = self.create_input_buffer(operation, config);
+        let output_buffer = self.create_output_buffer(
+            total_threads * dataset::output_words(operation),
+            strategy.wgpu_output,
+            config.verify,
+        );
+        let timestamps = self.create_timestamp_queries();
 
-        // Create bind group
-        let bind_group = self
-            .ctx
-            .device
-            .create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Benchmark Bind Group"),
-                layout: &pipeline.bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: input_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: output_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: params_buffer.as_entire_binding(),
-                    },
-                ],
-            });
+        // Full and zero-iteration params, each pushed or in its own buffer
+        let bind = |params_config: &BenchmarkConfig| {
+            let params_buffer = (strategy.params != ParamsBinding::PushConstants)
+                .then(|| self.create_params_buffer(params_config));
+            let binding = KernelBinding {
+                bind_group: self.create_bind_group(
+                    &pipeline,
+                    &input_buffer,
+                    &output_buffer,
+                    params_buffer.as_ref(),
+                ),
+                push_constants: params_buffer
+                    .is_none()
+                    .then(|| BenchParams::from_config(params_config)),
+            };
+            (binding, params_buffer)
+        };
+        let (binding, params_buffer) = bind(config);
+        let (empty_binding, empty_params_buffer) = bind(&config.clone().with_ops_per_thread(0));
+
+        self.prepared = Some(PreparedBenchmark {
+            operation,
+            config: config.clone(),
+            pipeline,
+            grid,
+            binding,
+            empty_binding,
+            output_buffer,
+            _buffers: [Some(input_buffer), params_buffer, empty_params_buffer]
+                .into_iter()
+                .flatten()
+                .collect(),
+            timestamps,
+        });
+
+        Ok(())
+    }
+
+    /// Prepare, time the first dispatch and tear down, returning each stage's latency
+    fn time_startup(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+        cache: Option<&wgpu::PipelineCache>,
+    ) -> Result<StageLatency, BenchmarkError> {
+        self.prepare_with_cache(operation, config, cache)?;
+        let first_dispatch = self.dispatch_once();
+        let stages = self
+            .prepared
+            .as_ref()
+            .map(|p| (p.pipeline.shader_module_time, p.pipeline.pipeline_time));
+        self.teardown();
+
+        let (shader_module, pipeline) =
+            stages.ok_or_else(|| BenchmarkError::Execution("No benchmark prepared".to_string()))?;
+        Ok(StageLatency::new(shader_module, pipeline, first_dispatch?))
+    }
+}
+
+impl BenchmarkBackend for WebGpuRunner {
+    fn name(&self) -> &str {
+        Backend::WebGPU.name()
+    }
+
+    fn device_name(&self) -> String {
+        self.ctx.device_name()
+    }
+
+    fn device_type(&self) -> DeviceType {
+        self.ctx.device_type()
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        let limits = self.ctx.device.limits();
+        BackendCapabilities {
+            native_u64: Backend::WebGPU.has_native_u64(),
+            timestamp_queries: self.ctx.supports_timestamp_queries(),
+            max_workgroup_size: limits
+                .max_compute_workgroup_size_x
+                .min(limits.max_compute_invocations_per_workgroup),
+            max_workgroups_per_dimension: limits.max_compute_workgroups_per_dimension,
+            max_buffer_size: (limits.max_storage_buffer_binding_size as u64)
+                .min(limits.max_buffer_size),
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<(), BenchmarkError> {
+        self.prepare_with_cache(operation, config, None)
+    }
+
+    fn run(&mut self) -> Result<BenchmarkResult, BenchmarkError> {
+        let prepared = self
+            .prepared
+            .as_ref()
+            .ok_or_else(|| BenchmarkError::Execution("No benchmark prepared".to_string()))?;
+        let config = &prepared.config;
 
         // Warmup runs
         for _ in 0..config.warmup_iterations {
-            self.dispatch(&pipeline, &bind_group, config);
+            self.dispatch(prepared, &prepared.binding);
         }
 
         // Timed runs
-        let mut timings = Vec::with_capacity(config.measurement_iterations as usize);
+        let mut gpu_timings = Vec::with_capacity(config.measurement_iterations as usize);
 
-        for _ in 0..config.measurement_iterations {
+        let samples = sampling::collect_samples(config, || {
             let start = Instant::now();
-            self.dispatch(&pipeline, &bind_group, config);
-            timings.push(start.elapsed());
+            self.dispatch(prepared, &prepared.binding);
+            let elapsed = start.elapsed();
+
+            if let Some(timestamps) = &prepared.timestamps {
+                gpu_timings.extend(self.read_gpu_time(timestamps)?);
+            }
+            Ok(elapsed)
+        })?;
+
+        // Fall back to wall-clock only if any sample lacked a GPU time
+        if gpu_timings.len() != samples.timings.len() {
+            gpu_timings.clear();
+        }
+
+        // Empty-kernel runs: same pipeline and buffers, zero iterations
+        let mut overhead_timings = Vec::new();
+        if config.measure_overhead {
+            self.dispatch(prepared, &prepared.empty_binding);
+            for _ in 0..config.measurement_iterations {
+                let start = Instant::now();
+                self.dispatch(prepared, &prepared.empty_binding);
+                overhead_timings.push(start.elapsed());
+            }
         }
 
+        // Verification runs after timing so the readback never lands in a sample
+        let verification = if config.verify {
+            Some(self.verify_output(prepared)?)
+        } else {
+            None
+        };
+
         // Create result
-        Ok(BenchmarkResult::from_timings(
-            Backend::WebGPU,
-            operation,
+        Ok(BenchmarkResult::unsummarized(
+            self.name(),
+            prepared.operation,
             config.workgroup_size,
             config.total_threads(),
             config.ops_per_thread,
-            &timings,
-        ))
+            &samples.timings,
+        )
+        .with_independent_chains(config.independent_chains)
+        .with_dispatches_per_submit(config.dispatches_per_submit, config.submit_batching)
+        .with_statistic(config.statistic)
+        .with_sample_offsets(&samples.offsets)
+        .with_convergence(samples.convergence)
+        .with_gpu_timings(&gpu_timings)
+        .with_dispatch_overhead(&overhead_timings)
+        .with_verification(config.input_mode, verification)
+        .with_buffer_strategy(config.buffer_strategy))
     }
 
-    /// Create input buffer with random data
-    fn create_input_buffer(&self, seed: u32) -> wgpu::Buffer {
-        let data: Vec<u32> = (0..16u32)
-            .map(|i| seed.wrapping_add(i).wrapping_mul(0x9E3779B9))
-            .collect();
+    fn dispatch_once(&mut self) -> Result<Duration, BenchmarkError> {
+        let prepared = self
+            .prepared
+            .as_ref()
+            .ok_or_else(|| BenchmarkError::Execution("No benchmark prepared".to_string()))?;
 
-        self.ctx
-            .device
-            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Input Buffer"),
-                contents: bytemuck::cast_slice(&data),
-                usage: wgpu::BufferUsages::STORAGE,
-            })
+        let start = Instant::now();
+        self.dispatch(prepared, &prepared.binding);
+        Ok(start.elapsed())
     }
 
-    /// Create output buffer
-    fn create_output_buffer(&self, count: usize) -> wgpu::Buffer {
-        self.ctx.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Output Buffer"),
-            size: (count * std::mem::size_of::<u32>()) as u64,
-            usage: wgpu::BufferUsages::STORAGE,
-            mapped_at_creation: false,
-        })
+    fn teardown(&mut self) {
+        self.prepared = None;
     }
 
-    /// Create parameters buffer
-    fn create_params_buffer(&self, config: &BenchmarkConfig) -> wgpu::Buffer {
-        #[repr(C)]
-        #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
-        struct BenchParams {
-            iterations: u32,
-            seed: u32,
-            _pad0: u32,
-            _pad1: u32,
-        }
-
-        let params = BenchParams {
-            iterations: config.ops_per_thread,
-            seed: config.seed,
-            _pad0: 0,
-            _pad1: 0,
-        };
-
-        self.ctx
-            .device
-            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Params Buffer"),
-                contents: bytemuck::cast_slice(&[params]),
-                usage: wgpu::BufferUsages::UNIFORM,
-            })
+    fn run_stream(
+        &mut self,
+        operation: StreamOperation,
+        layout: &StreamLayout,
+        config: &BenchmarkConfig,
+    ) -> Result<StreamTimings, BenchmarkError> {
+        self.time_stream(operation, layout, config)
     }
 
-    /// Dispatch the compute shader
-    fn dispatch(
-        &self,
-        pipeline: &WebGpuPipeline,
-        bind_group: &wgpu::BindGroup,
+    fn run_transfer(
+        &mut self,
+        method: TransferMethod,
+        bytes: u64,
         config: &BenchmarkConfig,
-    ) {
-        let mut encoder = self
-            .ctx
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Benchmark Encoder"),
-            });
-
-        {
-            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("Benchmark Compute Pass"),
-                timestamp_writes: None,
-            });
+    ) -> Result<Vec<Duration>, BenchmarkError> {
+        self.time_transfer(method, bytes, config)
+    }
 
-            compute_pass.set_pipeline(&pipeline.pipeline);
-            compute_pass.set_bind_group(0, bind_group, &[]);
-            compute_pass.dispatch_workgroups(config.num_workgroups, 1, 1);
-        }
+    fn measure_startup(
+        &mut self,
+        operation: Operation,
+        config: &BenchmarkConfig,
+    ) -> Result<Option<StartupLatency>, BenchmarkError> {
+        let cold = self.time_startup(operation, config, None)?;
+        let warm = self.time_startup(operation, config, None)?;
 
-        self.ctx.queue.submit(std::iter::once(encoder.finish()));
-        self.ctx.device.poll(wgpu::Maintain::Wait);
-    }
-}
+        // Populate a fresh cache, then time a creation that can hit it
+        let pipeline_cache = match self.ctx.create_pipeline_cache() {
+            Some(cache) => {
+                self.time_startup(operation, config, Some(&cache))?;
+                Some(self.time_startup(operation, config, Some(&cache))?)
+            }
+            None => None,
+        };
 
-/// Map operation to WGSL entry point name
-fn operation_to_entry_point(operation: Operation) -> &'static str {
-    match operation {
-        Operation::U32Add => "bench_u32_add",
-        Operation::U64AddNative => "bench_u64_add", // Not available in WebGPU
-        Operation::U64AddEmulated => "bench_u64_add",
-        Operation::Bn254FieldMul => "bench_bn254_field_mul",
-        Operation::Bn254FieldAdd => "bench_bn254_field_add",
-        Operation::MersenneFieldAdd => "bench_m31_field_add",
-        Operation::MersenneFieldMul => "bench_m31_field_mul",
+        Ok(Some(StartupLatency {
+            cold,
+            warm,
+            pipeline_cache,
+        }))
     }
 }